@@ -0,0 +1,218 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Data, DeriveInput, Error, Expr, Fields, Ident, LitInt, LitStr, Result};
+
+/**
+ * How a value is written, either through its own `BinaryCodec` implementation or an encoding module.
+*/
+enum Encoding {
+  Codec,
+  Module(syn::Path),
+}
+
+impl Encoding {
+  fn encode(&self, ty: TokenStream, value: TokenStream) -> TokenStream {
+    match self {
+      Encoding::Codec => quote!(<#ty as ::serenityrs::protocol::binary::codec::BinaryCodec>::encode(#value, stream)?),
+      Encoding::Module(path) => quote!(#path::encode(#value, stream)?),
+    }
+  }
+
+  fn decode(&self, ty: TokenStream) -> TokenStream {
+    match self {
+      Encoding::Codec => quote!(<#ty as ::serenityrs::protocol::binary::codec::BinaryCodec>::decode(stream)?),
+      Encoding::Module(path) => quote!(#path::decode(stream)?),
+    }
+  }
+}
+
+fn encoding_module(ident: &Ident) -> Encoding {
+  Encoding::Module(syn::parse_quote!(::serenityrs::protocol::binary::codec::#ident))
+}
+
+fn parse_field_encoding(field: &syn::Field) -> Result<Encoding> {
+  let mut encoding = Encoding::Codec;
+
+  for attr in field.attrs.iter().filter(|a| a.path().is_ident("codec")) {
+    attr.parse_nested_meta(|meta| {
+      if meta.path.is_ident("with") {
+        encoding = Encoding::Module(meta.value()?.parse::<LitStr>()?.parse()?);
+      } else if let Some(ident) = meta.path.get_ident() {
+        encoding = encoding_module(ident);
+      } else {
+        return Err(meta.error("expected an encoding such as `var_int`, or `with = \"path\"`"));
+      }
+
+      Ok(())
+    })?;
+  }
+
+  Ok(encoding)
+}
+
+/**
+ * Reads the encoding of an enum tag, `#[codec(tag = var_u32)]`, which defaults to an `u8`.
+*/
+fn parse_tag(input: &DeriveInput) -> Result<(TokenStream, Encoding)> {
+  let mut tag = (quote!(u8), Encoding::Codec);
+
+  for attr in input.attrs.iter().filter(|a| a.path().is_ident("codec")) {
+    attr.parse_nested_meta(|meta| {
+      if !meta.path.is_ident("tag") {
+        return Err(meta.error("expected `tag`"));
+      }
+
+      let ident = meta.value()?.parse::<Ident>()?;
+      tag = match ident.to_string().as_str() {
+        "u8" | "i8" | "u16" | "i16" | "u32" | "i32" | "u64" | "i64" => (quote!(#ident), Encoding::Codec),
+        _ => (quote!(_), encoding_module(&ident)),
+      };
+
+      Ok(())
+    })?;
+  }
+
+  Ok(tag)
+}
+
+/**
+ * Gets the tag of a variant, from `#[codec(id = 3)]`, its discriminant, or the tag before it plus one.
+*/
+fn parse_variant_id(variant: &syn::Variant, next: i64) -> Result<i64> {
+  let mut id = None;
+
+  for attr in variant.attrs.iter().filter(|a| a.path().is_ident("codec")) {
+    attr.parse_nested_meta(|meta| {
+      if meta.path.is_ident("id") {
+        id = Some(meta.value()?.parse::<LitInt>()?.base10_parse()?);
+        Ok(())
+      } else {
+        Err(meta.error("expected `id`"))
+      }
+    })?;
+  }
+
+  if let (None, Some((_, discriminant))) = (id, &variant.discriminant) {
+    id = Some(parse_discriminant(discriminant)?);
+  }
+
+  Ok(id.unwrap_or(next))
+}
+
+fn parse_discriminant(expr: &Expr) -> Result<i64> {
+  match expr {
+    Expr::Lit(syn::ExprLit { lit: syn::Lit::Int(lit), .. }) => lit.base10_parse(),
+    Expr::Unary(syn::ExprUnary { op: syn::UnOp::Neg(_), expr, .. }) => Ok(-parse_discriminant(expr)?),
+    _ => Err(Error::new_spanned(expr, "BinaryCodec requires integer literal discriminants"))
+  }
+}
+
+pub fn expand(input: DeriveInput) -> Result<TokenStream> {
+  let ident = &input.ident;
+  let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+  let (encode, decode) = match &input.data {
+    Data::Struct(data) => {
+      let Fields::Named(fields) = &data.fields else {
+        return Err(Error::new_spanned(ident, "BinaryCodec requires a struct with named fields"));
+      };
+
+      // Fields are written and read in declaration order
+      let mut encodes = Vec::new();
+      let mut decodes = Vec::new();
+      for field in &fields.named {
+        let name = field.ident.as_ref().unwrap();
+        let ty = &field.ty;
+        let encoding = parse_field_encoding(field)?;
+
+        encodes.push(encoding.encode(quote!(#ty), quote!(&self.#name)));
+        let value = encoding.decode(quote!(#ty));
+        decodes.push(quote!(#name: #value));
+      }
+
+      (quote!(#(#encodes;)*), quote!(Ok(#ident { #(#decodes),* })))
+    },
+    Data::Enum(data) => {
+      let (tag_type, tag) = parse_tag(&input)?;
+      let enum_name = ident.to_string();
+
+      let mut encodes = Vec::new();
+      let mut decodes = Vec::new();
+      let mut next = 0;
+      for variant in &data.variants {
+        let variant_ident = &variant.ident;
+        let id = parse_variant_id(variant, next)?;
+        next = id + 1;
+
+        let id = LitInt::new(&id.to_string(), variant_ident.span());
+        let encode_tag = tag.encode(tag_type.clone(), quote!(&#id));
+
+        // Variants with fields write them after the tag
+        match &variant.fields {
+          Fields::Unit => {
+            encodes.push(quote!(#ident::#variant_ident => { #encode_tag; }));
+            decodes.push(quote!(#id => Ok(#ident::#variant_ident)));
+          },
+          Fields::Named(fields) => {
+            let mut names = Vec::new();
+            let mut field_encodes = Vec::new();
+            let mut field_decodes = Vec::new();
+            for field in &fields.named {
+              let name = field.ident.as_ref().unwrap();
+              let ty = &field.ty;
+              let encoding = parse_field_encoding(field)?;
+
+              names.push(name.clone());
+              field_encodes.push(encoding.encode(quote!(#ty), quote!(#name)));
+              let value = encoding.decode(quote!(#ty));
+              field_decodes.push(quote!(#name: #value));
+            }
+
+            encodes.push(quote!(#ident::#variant_ident { #(#names),* } => { #encode_tag; #(#field_encodes;)* }));
+            decodes.push(quote!(#id => Ok(#ident::#variant_ident { #(#field_decodes),* })));
+          },
+          Fields::Unnamed(fields) => {
+            let names = (0..fields.unnamed.len()).map(|index| format_ident!("field{}", index)).collect::<Vec<_>>();
+            let mut field_encodes = Vec::new();
+            let mut field_decodes = Vec::new();
+            for (name, field) in names.iter().zip(&fields.unnamed) {
+              let ty = &field.ty;
+              let encoding = parse_field_encoding(field)?;
+
+              field_encodes.push(encoding.encode(quote!(#ty), quote!(#name)));
+              field_decodes.push(encoding.decode(quote!(#ty)));
+            }
+
+            encodes.push(quote!(#ident::#variant_ident(#(#names),*) => { #encode_tag; #(#field_encodes;)* }));
+            decodes.push(quote!(#id => Ok(#ident::#variant_ident(#(#field_decodes),*))));
+          },
+        }
+      }
+
+      let decode_tag = tag.decode(tag_type);
+      let decode = quote! {
+        match #decode_tag {
+          #(#decodes,)*
+          value => Err(::serenityrs::protocol::binary::error::BinaryError::UnknownVariant { name: #enum_name, value: value as i64 }),
+        }
+      };
+
+      (quote!(match self { #(#encodes)* }), decode)
+    },
+    Data::Union(_) => return Err(Error::new_spanned(ident, "BinaryCodec cannot be derived for unions"))
+  };
+
+  Ok(quote! {
+    impl #impl_generics ::serenityrs::protocol::binary::codec::BinaryCodec for #ident #ty_generics #where_clause {
+      fn encode(&self, stream: &mut ::serenityrs::protocol::binary::stream::BinaryStream) -> ::std::result::Result<(), ::serenityrs::protocol::binary::error::BinaryError> {
+        #encode
+
+        ::std::result::Result::Ok(())
+      }
+
+      fn decode(stream: &mut ::serenityrs::protocol::binary::stream::BinaryStream) -> ::std::result::Result<Self, ::serenityrs::protocol::binary::error::BinaryError> {
+        #decode
+      }
+    }
+  })
+}
@@ -0,0 +1,71 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{Attribute, Error, FnArg, LitStr, Pat, Result, Signature, Token, Visibility};
+
+use crate::utils::to_camel_case;
+
+pub struct JsMethodName(Option<LitStr>);
+
+impl Parse for JsMethodName {
+  fn parse(input: ParseStream) -> Result<Self> {
+    match input.is_empty() {
+      true => Ok(JsMethodName(None)),
+      false => Ok(JsMethodName(Some(input.parse()?)))
+    }
+  }
+}
+
+/**
+ * A method declared without a body, `pub fn send_message(&self, message: &str) -> Result<(), SerenityError>;`
+*/
+pub struct JsMethod {
+  attrs: Vec<Attribute>,
+  vis: Visibility,
+  sig: Signature,
+}
+
+impl Parse for JsMethod {
+  fn parse(input: ParseStream) -> Result<Self> {
+    let attrs = input.call(Attribute::parse_outer)?;
+    let vis = input.parse()?;
+    let sig = input.parse()?;
+    input.parse::<Token![;]>()?;
+
+    Ok(JsMethod { attrs, vis, sig })
+  }
+}
+
+pub fn expand(name: JsMethodName, method: JsMethod) -> Result<TokenStream> {
+  let JsMethod { attrs, vis, sig } = method;
+  let js_name = name.0.map(|n| n.value()).unwrap_or_else(|| to_camel_case(&sig.ident.to_string()));
+
+  // The method must borrow the wrapper
+  match sig.inputs.first() {
+    Some(FnArg::Receiver(receiver)) if receiver.reference.is_some() && receiver.mutability.is_none() => {},
+    _ => return Err(Error::new_spanned(&sig, "#[js_method] requires a `&self` receiver"))
+  }
+
+  // Convert every argument into a JS value
+  let mut args = Vec::new();
+  for input in sig.inputs.iter().skip(1) {
+    let FnArg::Typed(typed) = input else { unreachable!() };
+    let Pat::Ident(pat) = &*typed.pat else {
+      return Err(Error::new_spanned(&typed.pat, "#[js_method] arguments must be plain identifiers"));
+    };
+
+    let ident = &pat.ident;
+    args.push(quote! {
+      ::serenityrs::utils::js_wrapper::ToNodeValue::to_node_value(&#ident, self.env, <Self as ::serenityrs::utils::js_wrapper::JsWrapper>::WRAPPER)?
+    });
+  }
+
+  Ok(quote! {
+    #(#attrs)*
+    #vis #sig {
+      let args = ::std::vec![#(#args),*];
+
+      ::serenityrs::utils::js_wrapper::call_wrapper_method(self.env, &self.object, <Self as ::serenityrs::utils::js_wrapper::JsWrapper>::WRAPPER, #js_name, &args)
+    }
+  })
+}
@@ -0,0 +1,156 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Error, Fields, LitStr, Result};
+
+use crate::utils::to_camel_case;
+
+struct FieldOptions {
+  name: Option<String>,
+  optional: bool,
+  default: bool,
+  nested: bool,
+}
+
+fn parse_field_options(field: &syn::Field) -> Result<FieldOptions> {
+  let mut options = FieldOptions { name: None, optional: false, default: false, nested: false };
+
+  for attr in field.attrs.iter().filter(|a| a.path().is_ident("js")) {
+    attr.parse_nested_meta(|meta| {
+      if meta.path.is_ident("name") {
+        options.name = Some(meta.value()?.parse::<LitStr>()?.value());
+      } else if meta.path.is_ident("optional") {
+        options.optional = true;
+      } else if meta.path.is_ident("default") {
+        options.default = true;
+      } else if meta.path.is_ident("nested") {
+        options.nested = true;
+      } else {
+        return Err(meta.error("expected `name`, `optional`, `default` or `nested`"));
+      }
+
+      Ok(())
+    })?;
+  }
+
+  if options.optional && options.default {
+    return Err(Error::new_spanned(field, "a field cannot be both `optional` and `default`"));
+  }
+
+  Ok(options)
+}
+
+fn parse_wrapper_name(input: &DeriveInput) -> Result<String> {
+  let mut wrapper = input.ident.to_string();
+
+  for attr in input.attrs.iter().filter(|a| a.path().is_ident("js")) {
+    attr.parse_nested_meta(|meta| {
+      if meta.path.is_ident("wrapper") {
+        wrapper = meta.value()?.parse::<LitStr>()?.value();
+        Ok(())
+      } else {
+        Err(meta.error("expected `wrapper`"))
+      }
+    })?;
+  }
+
+  Ok(wrapper)
+}
+
+pub fn expand(input: DeriveInput) -> Result<TokenStream> {
+  let ident = &input.ident;
+  let wrapper = parse_wrapper_name(&input)?;
+  let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+  // Only structs with named fields can be wrapped
+  let fields = match &input.data {
+    Data::Struct(data) => match &data.fields {
+      Fields::Named(fields) => &fields.named,
+      _ => return Err(Error::new_spanned(ident, "JsWrapper requires a struct with named fields"))
+    },
+    _ => return Err(Error::new_spanned(ident, "JsWrapper can only be derived for structs"))
+  };
+
+  // The object and env fields hold the JS side of the wrapper
+  for required in ["object", "env"] {
+    if !fields.iter().any(|f| f.ident.as_ref().is_some_and(|i| i == required)) {
+      return Err(Error::new_spanned(ident, format!("JsWrapper requires a `{}` field", required)));
+    }
+  }
+
+  let mut reads = Vec::new();
+  let mut names = Vec::new();
+
+  for field in fields {
+    let field_ident = field.ident.as_ref().unwrap();
+    names.push(field_ident.clone());
+
+    if field_ident == "object" || field_ident == "env" {
+      continue;
+    }
+
+    let options = parse_field_options(field)?;
+    let js_name = options.name.unwrap_or_else(|| to_camel_case(&field_ident.to_string()));
+
+    let read = match (options.nested, options.optional || options.default) {
+      (false, false) => quote!(read_node_property),
+      (false, true) => quote!(read_optional_node_property),
+      (true, false) => quote!(read_nested_node_property),
+      (true, true) => quote!(read_optional_nested_node_property),
+    };
+
+    let value = quote! {
+      ::serenityrs::utils::js_wrapper::#read(env, &object, <Self as ::serenityrs::utils::js_wrapper::JsWrapper>::WRAPPER, #js_name)?
+    };
+
+    reads.push(match options.default {
+      true => quote!(let #field_ident = #value.unwrap_or_default();),
+      false => quote!(let #field_ident = #value;),
+    });
+  }
+
+  Ok(quote! {
+    impl #impl_generics #ident #ty_generics #where_clause {
+      pub fn new(env: ::serenityrs::__private::napi::Env, object: ::serenityrs::__private::napi::JsObject) -> ::std::result::Result<Self, ::serenityrs::error::error::SerenityError> {
+        #(#reads)*
+
+        Ok(#ident { #(#names),* })
+      }
+    }
+
+    impl #impl_generics ::serenityrs::utils::js_wrapper::JsWrapper for #ident #ty_generics #where_clause {
+      const WRAPPER: &'static str = #wrapper;
+
+      fn from_js_object(env: ::serenityrs::__private::napi::Env, object: ::serenityrs::__private::napi::JsObject) -> ::std::result::Result<Self, ::serenityrs::error::error::SerenityError> {
+        Self::new(env, object)
+      }
+
+      fn js_object(&self) -> &::serenityrs::__private::napi::JsObject {
+        &self.object
+      }
+
+      fn js_object_mut(&mut self) -> &mut ::serenityrs::__private::napi::JsObject {
+        &mut self.object
+      }
+
+      fn js_env(&self) -> ::serenityrs::__private::napi::Env {
+        self.env
+      }
+    }
+
+    impl #impl_generics ::serenityrs::utils::js_wrapper::FromNodeValue for #ident #ty_generics #where_clause {
+      fn from_node_value(env: ::serenityrs::__private::napi::Env, value: ::serenityrs::__private::napi::JsUnknown, wrapper: &'static str, name: &str) -> ::std::result::Result<Self, ::serenityrs::error::error::SerenityError> {
+        Self::new(env, ::serenityrs::utils::node_converter::node_value_to_object(value, wrapper, name)?)
+      }
+    }
+
+    impl #impl_generics ::serenityrs::__private::napi::bindgen_prelude::FromNapiValue for #ident #ty_generics #where_clause {
+      unsafe fn from_napi_value(env: ::serenityrs::__private::napi::sys::napi_env, value: ::serenityrs::__private::napi::sys::napi_value) -> ::serenityrs::__private::napi::Result<Self> {
+        // Create the JsObject from the napi_value
+        let object = ::serenityrs::utils::node_converter::object_from_raw(env, value, <Self as ::serenityrs::utils::js_wrapper::JsWrapper>::WRAPPER)?;
+
+        // Return the wrapper instance
+        Ok(Self::new(env.into(), object)?)
+      }
+    }
+  })
+}
@@ -0,0 +1,67 @@
+use proc_macro::TokenStream;
+
+mod binary_codec;
+mod js_method;
+mod js_wrapper;
+mod utils;
+
+/**
+ * Derives the constructor, `JsWrapper`, `FromNodeValue` and `FromNapiValue` implementations
+ * for a struct backed by a SerenityJS object.
+ *
+ * The struct must have an `object: napi::JsObject` and an `env: napi::Env` field.
+ * Every other field is read from the property of the same name in camelCase,
+ * and can be configured with `#[js(...)]`:
+ * - `name = "viewDistance"` reads the field from a differently named property.
+ * - `optional` reads a `Option<T>` field, a missing, undefined or null property becomes `None`.
+ * - `default` falls back to `Default::default()` when the property is missing.
+ * - `nested` reads the field as another `JsWrapper` type.
+ *
+ * The wrapper name used in errors defaults to the struct name, and can be set with `#[js(wrapper = "...")]`.
+*/
+#[proc_macro_derive(JsWrapper, attributes(js))]
+pub fn derive_js_wrapper(input: TokenStream) -> TokenStream {
+  let input = syn::parse_macro_input!(input as syn::DeriveInput);
+
+  js_wrapper::expand(input)
+    .unwrap_or_else(|e| e.to_compile_error())
+    .into()
+}
+
+/**
+ * Generates the body of a method that calls the JS method of the same name in camelCase,
+ * or the name given as `#[js_method("sendMessage")]`.
+ *
+ * The method is declared without a body, takes `&self` on a `JsWrapper` type, and returns `Result<T, SerenityError>`.
+ * Arguments are converted with `ToNodeValue`, the result with `FromNodeValue`.
+*/
+#[proc_macro_attribute]
+pub fn js_method(attr: TokenStream, item: TokenStream) -> TokenStream {
+  let name = syn::parse_macro_input!(attr as js_method::JsMethodName);
+  let method = syn::parse_macro_input!(item as js_method::JsMethod);
+
+  js_method::expand(name, method)
+    .unwrap_or_else(|e| e.to_compile_error())
+    .into()
+}
+
+/**
+ * Derives `BinaryCodec` for a struct with named fields, or an enum.
+ *
+ * Struct fields are encoded in declaration order with their own `BinaryCodec` implementation,
+ * or the encoding module named in `#[codec(...)]`:
+ * - `#[codec(var_int)]`, `var_u32`, `var_long`, `var_u64` or `network_block_position` use the module of that name.
+ * - `#[codec(with = "path::to::module")]` uses any module with matching `encode` and `decode` functions.
+ *   Its `encode` returns `Result<(), BinaryError>` like `BinaryCodec::encode`.
+ *
+ * Enums are encoded as a tag followed by the fields of the variant, the tag is an `u8` unless set with
+ * `#[codec(tag = var_int)]`. The tag of a variant is its discriminant, or is set with `#[codec(id = 3)]`.
+*/
+#[proc_macro_derive(BinaryCodec, attributes(codec))]
+pub fn derive_binary_codec(input: TokenStream) -> TokenStream {
+  let input = syn::parse_macro_input!(input as syn::DeriveInput);
+
+  binary_codec::expand(input)
+    .unwrap_or_else(|e| e.to_compile_error())
+    .into()
+}
@@ -0,0 +1,20 @@
+/**
+ * Converts a snake_case Rust identifier into the camelCase name used by SerenityJS.
+*/
+pub fn to_camel_case(name: &str) -> String {
+  let mut result = String::with_capacity(name.len());
+  let mut upper = false;
+
+  for c in name.trim_start_matches("r#").chars() {
+    if c == '_' {
+      upper = !result.is_empty();
+    } else if upper {
+      result.extend(c.to_uppercase());
+      upper = false;
+    } else {
+      result.push(c);
+    }
+  }
+
+  result
+}
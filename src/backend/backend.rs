@@ -0,0 +1,121 @@
+use crate::block::block_permutation::BlockPermutation;
+use crate::block::block_volume::BlockVolume;
+use crate::error::error::SerenityError;
+use crate::protocol::types::block_position::BlockPosition;
+use crate::protocol::types::cuboid::Cuboid;
+use crate::protocol::types::rotation::Rotation;
+use crate::protocol::types::vector3f::Vector3f;
+use crate::world::player::player::PLAYER_EYE_HEIGHT;
+
+/**
+ * The logger operations a plugin performs.
+ * Implemented by the N-API `Logger`, and by `MockLogger` for tests.
+*/
+pub trait LoggerBackend {
+  fn log(&self, message: &str) -> Result<(), SerenityError>;
+  fn info(&self, message: &str) -> Result<(), SerenityError>;
+  fn warn(&self, message: &str) -> Result<(), SerenityError>;
+  fn error(&self, message: &str) -> Result<(), SerenityError>;
+  fn success(&self, message: &str) -> Result<(), SerenityError>;
+  fn debug(&self, message: &str) -> Result<(), SerenityError>;
+}
+
+/**
+ * The dimension operations a plugin performs.
+ * Implemented by the N-API `Dimension`, and by `MockDimension` for tests.
+*/
+pub trait DimensionBackend {
+  fn identifier(&self) -> String;
+  fn view_distance(&self) -> i32;
+  fn simulation_distance(&self) -> i32;
+
+  /**
+   * Gets the identifier of the type of the block at the position.
+  */
+  fn block_identifier(&self, position: BlockPosition) -> Result<String, SerenityError>;
+
+  /**
+   * Gets the permutation of the block at the position.
+  */
+  fn block_permutation(&self, position: BlockPosition) -> Result<BlockPermutation, SerenityError>;
+
+  /**
+   * Sets the block at the position to the permutation.
+  */
+  fn set_block(&self, position: BlockPosition, permutation: &BlockPermutation) -> Result<(), SerenityError>;
+
+  /**
+   * Gets every block of the region, block by block unless the backend can batch the reads.
+  */
+  fn get_blocks(&self, region: Cuboid) -> Result<BlockVolume, SerenityError> {
+    let mut volume = BlockVolume::new(region, BlockPermutation::new("minecraft:air"))?;
+    for position in region {
+      volume.set(position, &self.block_permutation(position)?);
+    }
+    volume.compact();
+
+    Ok(volume)
+  }
+
+  /**
+   * Sets every block of the volume's region, block by block unless the backend can batch the writes.
+  */
+  fn set_blocks(&self, volume: &BlockVolume) -> Result<(), SerenityError> {
+    for (position, permutation) in volume.iter() {
+      self.set_block(position, permutation)?;
+    }
+
+    Ok(())
+  }
+}
+
+/**
+ * The player operations a plugin performs.
+ * Implemented by the N-API `Player`, and by `MockPlayer` for tests.
+*/
+pub trait PlayerBackend {
+  type Dimension: DimensionBackend;
+
+  fn username(&self) -> String;
+  fn xuid(&self) -> String;
+  fn uuid(&self) -> String;
+  fn position(&self) -> Vector3f;
+  fn rotation(&self) -> Rotation;
+  fn dimension(&self) -> &Self::Dimension;
+
+  fn get_gamemode(&self) -> Result<i32, SerenityError>;
+  fn set_gamemode(&self, gamemode: i32) -> Result<(), SerenityError>;
+  fn is_op(&self) -> Result<bool, SerenityError>;
+  fn send_message(&self, message: &str) -> Result<(), SerenityError>;
+  fn send_toast(&self, title: &str, message: &str) -> Result<(), SerenityError>;
+  fn teleport(&self, position: Vector3f) -> Result<(), SerenityError>;
+  fn teleport_with_rotation(&self, position: Vector3f, rotation: Rotation) -> Result<(), SerenityError>;
+  fn transfer(&self, address: &str, port: i32) -> Result<(), SerenityError>;
+  fn kill(&self) -> Result<(), SerenityError>;
+  fn get_total_experience(&self) -> Result<i32, SerenityError>;
+  fn add_experience(&self, amount: i32) -> Result<(), SerenityError>;
+
+  fn view_direction(&self) -> Vector3f {
+    self.rotation().direction()
+  }
+
+  fn eye_position(&self) -> Vector3f {
+    self.position() + Vector3f::UP * PLAYER_EYE_HEIGHT
+  }
+}
+
+/**
+ * The world operations a plugin performs.
+ * Implemented by the N-API `World`, and by `MockWorld` for tests.
+*/
+pub trait WorldBackend {
+  type Player: PlayerBackend;
+  type Logger: LoggerBackend;
+
+  fn identifier(&self) -> String;
+  fn current_tick(&self) -> u64;
+  fn logger(&self) -> &Self::Logger;
+
+  fn send_message(&self, message: &str) -> Result<(), SerenityError>;
+  fn get_players(&self) -> Result<Vec<Self::Player>, SerenityError>;
+}
@@ -0,0 +1,72 @@
+use crate::error::error::SerenityError;
+
+use super::backend::{PlayerBackend, WorldBackend};
+
+/**
+ * A player joined the world.
+ * Implemented by `PlayerJoinSignal`, and by `MockPlayerJoinSignal` for tests.
+*/
+pub trait PlayerJoinEvent {
+  type World: WorldBackend;
+  type Player: PlayerBackend;
+
+  fn world(&self) -> &Self::World;
+  fn player(&self) -> &Self::Player;
+}
+
+/**
+ * A player left the world.
+ * Implemented by `PlayerLeaveSignal`, and by `MockPlayerLeaveSignal` for tests.
+*/
+pub trait PlayerLeaveEvent {
+  type World: WorldBackend;
+  type Player: PlayerBackend;
+
+  fn world(&self) -> &Self::World;
+  fn player(&self) -> &Self::Player;
+  fn reason(&self) -> i32;
+  fn message(&self) -> String;
+  fn set_message(&mut self, message: &str) -> Result<(), SerenityError>;
+}
+
+/**
+ * A player sent a chat message, which can be modified or cancelled.
+ * Implemented by `PlayerChatSignal`, and by `MockPlayerChatSignal` for tests.
+*/
+pub trait PlayerChatEvent {
+  type World: WorldBackend;
+  type Player: PlayerBackend;
+
+  fn world(&self) -> &Self::World;
+  fn player(&self) -> &Self::Player;
+  fn message(&self) -> String;
+  fn set_message(&mut self, message: &str) -> Result<(), SerenityError>;
+  fn cancel(&mut self) -> Result<(), SerenityError>;
+  fn is_cancelled(&self) -> Result<bool, SerenityError>;
+}
+
+/**
+ * A player executed a command, which can be modified or cancelled.
+ * Implemented by `PlayerExecuteCommandSignal`, and by `MockPlayerExecuteCommandSignal` for tests.
+*/
+pub trait PlayerExecuteCommandEvent {
+  type World: WorldBackend;
+  type Player: PlayerBackend;
+
+  fn world(&self) -> &Self::World;
+  fn player(&self) -> &Self::Player;
+  fn command(&self) -> String;
+  fn set_command(&mut self, command: &str) -> Result<(), SerenityError>;
+  fn cancel(&mut self) -> Result<(), SerenityError>;
+  fn is_cancelled(&self) -> Result<bool, SerenityError>;
+}
+
+/**
+ * The world ticked.
+ * Implemented by `WorldTickSignal`, and by `MockWorldTickSignal` for tests.
+*/
+pub trait WorldTickEvent {
+  type World: WorldBackend;
+
+  fn world(&self) -> &Self::World;
+}
@@ -0,0 +1,701 @@
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use crate::block::block_permutation::BlockPermutation;
+use crate::block::block_volume::BlockVolume;
+use crate::error::error::SerenityError;
+use crate::protocol::types::block_position::BlockPosition;
+use crate::protocol::types::cuboid::Cuboid;
+use crate::protocol::types::rotation::Rotation;
+use crate::protocol::types::vector3f::Vector3f;
+
+use super::backend::*;
+use super::events::*;
+
+/**
+ * An outgoing call made on a mock, recorded so tests can assert on it.
+*/
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "replay", derive(serde::Serialize, serde::Deserialize))]
+pub struct MockCall {
+  /** The username of the player, or the identifier of the world or logger, the call was made on. */
+  pub receiver: String,
+  /** The name of the backend method that was called. */
+  pub method: String,
+  /** The arguments of the call, formatted as strings. */
+  pub args: Vec<String>,
+}
+
+impl MockCall {
+  pub fn new(receiver: &str, method: &str, args: &[&str]) -> Self {
+    MockCall { receiver: receiver.to_string(), method: method.to_string(), args: args.iter().map(|arg| arg.to_string()).collect() }
+  }
+}
+
+type CallLog = Rc<RefCell<Vec<MockCall>>>;
+
+fn record(calls: &CallLog, receiver: &str, method: &'static str, args: Vec<String>) {
+  calls.borrow_mut().push(MockCall { receiver: receiver.to_string(), method: method.to_string(), args });
+}
+
+/**
+ * An in-memory host for testing plugin logic without Node.
+ * Every mock created through the host records its outgoing calls into the host.
+ *
+ * ```ignore
+ * let host = MockHost::new();
+ * let world = host.world("default");
+ * let player = world.add_player("Steve");
+ *
+ * on_join(&MockPlayerJoinSignal::new(&world, &player))?;
+ *
+ * assert_eq!(host.calls_to("send_message").len(), 1);
+ * ```
+*/
+#[derive(Clone, Default)]
+pub struct MockHost {
+  calls: CallLog,
+  next_player: Rc<Cell<u32>>,
+}
+
+impl MockHost {
+  pub fn new() -> Self {
+    MockHost::default()
+  }
+
+  /**
+   * Creates a world with an overworld dimension.
+  */
+  pub fn world(&self, identifier: &str) -> MockWorld {
+    MockWorld {
+      host: self.clone(),
+      logger: MockLogger { identifier: identifier.to_string(), calls: self.calls.clone() },
+      dimension: MockDimension::with_calls("minecraft:overworld", self.calls.clone()),
+      state: Rc::new(RefCell::new(MockWorldState { identifier: identifier.to_string(), current_tick: 0, players: Vec::new() })),
+    }
+  }
+
+  /**
+   * Gets every call recorded so far.
+  */
+  pub fn calls(&self) -> Vec<MockCall> {
+    self.calls.borrow().clone()
+  }
+
+  /**
+   * Gets the recorded calls of the given backend method.
+  */
+  pub fn calls_to(&self, method: &str) -> Vec<MockCall> {
+    self.calls.borrow().iter().filter(|call| call.method == method).cloned().collect()
+  }
+
+  /**
+   * Forgets the calls recorded so far.
+  */
+  pub fn clear_calls(&self) {
+    self.calls.borrow_mut().clear();
+  }
+}
+
+/**
+ * A logger recording every message it is given.
+*/
+#[derive(Clone)]
+pub struct MockLogger {
+  identifier: String,
+  calls: CallLog,
+}
+
+impl MockLogger {
+  fn write(&self, method: &'static str, message: &str) -> Result<(), SerenityError> {
+    record(&self.calls, &self.identifier, method, vec![message.to_string()]);
+    Ok(())
+  }
+}
+
+impl LoggerBackend for MockLogger {
+  fn log(&self, message: &str) -> Result<(), SerenityError> {
+    self.write("log", message)
+  }
+
+  fn info(&self, message: &str) -> Result<(), SerenityError> {
+    self.write("info", message)
+  }
+
+  fn warn(&self, message: &str) -> Result<(), SerenityError> {
+    self.write("warn", message)
+  }
+
+  fn error(&self, message: &str) -> Result<(), SerenityError> {
+    self.write("error", message)
+  }
+
+  fn success(&self, message: &str) -> Result<(), SerenityError> {
+    self.write("success", message)
+  }
+
+  fn debug(&self, message: &str) -> Result<(), SerenityError> {
+    self.write("debug", message)
+  }
+}
+
+struct MockDimensionState {
+  identifier: String,
+  view_distance: i32,
+  simulation_distance: i32,
+  blocks: HashMap<BlockPosition, BlockPermutation>,
+  read_only: bool,
+}
+
+/**
+ * A dimension with an in-memory block map, every unset block is air.
+*/
+#[derive(Clone)]
+pub struct MockDimension {
+  state: Rc<RefCell<MockDimensionState>>,
+  calls: CallLog,
+}
+
+impl MockDimension {
+  pub fn new(identifier: &str) -> Self {
+    MockDimension::with_calls(identifier, CallLog::default())
+  }
+
+  fn with_calls(identifier: &str, calls: CallLog) -> Self {
+    let state = MockDimensionState {
+      identifier: identifier.to_string(),
+      view_distance: 10,
+      simulation_distance: 4,
+      blocks: HashMap::new(),
+      read_only: false,
+    };
+
+    MockDimension { state: Rc::new(RefCell::new(state)), calls }
+  }
+
+  /**
+   * Makes every later block write fail, like writes to an unloaded chunk do.
+  */
+  pub fn set_read_only(&self, read_only: bool) {
+    self.state.borrow_mut().read_only = read_only;
+  }
+
+  fn check_writable(&self, method: &str) -> Result<(), SerenityError> {
+    if !self.state.borrow().read_only {
+      return Ok(());
+    }
+
+    Err(SerenityError::JsException { wrapper: "MockDimension", method: method.to_string(), message: "the dimension is read only".to_string() })
+  }
+
+  /**
+   * Sets the block at the position, without recording a call.
+  */
+  pub fn set_current_block(&self, position: BlockPosition, permutation: &BlockPermutation) {
+    self.state.borrow_mut().blocks.insert(position, permutation.clone());
+  }
+}
+
+impl DimensionBackend for MockDimension {
+  fn identifier(&self) -> String {
+    self.state.borrow().identifier.clone()
+  }
+
+  fn view_distance(&self) -> i32 {
+    self.state.borrow().view_distance
+  }
+
+  fn simulation_distance(&self) -> i32 {
+    self.state.borrow().simulation_distance
+  }
+
+  fn block_identifier(&self, position: BlockPosition) -> Result<String, SerenityError> {
+    Ok(self.block_permutation(position)?.identifier)
+  }
+
+  fn block_permutation(&self, position: BlockPosition) -> Result<BlockPermutation, SerenityError> {
+    let state = self.state.borrow();
+    let block = state.blocks.get(&position);
+
+    Ok(block.cloned().unwrap_or_else(|| BlockPermutation::new("minecraft:air")))
+  }
+
+  fn set_block(&self, position: BlockPosition, permutation: &BlockPermutation) -> Result<(), SerenityError> {
+    self.check_writable("setPermutation")?;
+    let args = vec![position.x.to_string(), position.y.to_string(), position.z.to_string(), permutation.to_string()];
+    record(&self.calls, &self.state.borrow().identifier, "set_block", args);
+    self.set_current_block(position, permutation);
+
+    Ok(())
+  }
+
+  fn set_blocks(&self, volume: &BlockVolume) -> Result<(), SerenityError> {
+    self.check_writable("setPermutation")?;
+    // Record the whole volume as one call, like a single call to the N-API backend
+    let Cuboid { min, max } = volume.region();
+    let args = [min.x, min.y, min.z, max.x, max.y, max.z].iter().map(|value| value.to_string()).collect();
+    record(&self.calls, &self.state.borrow().identifier, "set_blocks", args);
+
+    for (position, permutation) in volume.iter() {
+      self.set_current_block(position, permutation);
+    }
+
+    Ok(())
+  }
+}
+
+struct MockPlayerState {
+  username: String,
+  xuid: String,
+  uuid: String,
+  position: Vector3f,
+  rotation: Rotation,
+  gamemode: i32,
+  op: bool,
+  experience: i32,
+}
+
+/**
+ * A player whose state is updated by the calls made on it, e.g. `teleport` moves the player.
+*/
+#[derive(Clone)]
+pub struct MockPlayer {
+  state: Rc<RefCell<MockPlayerState>>,
+  dimension: MockDimension,
+  calls: CallLog,
+}
+
+impl MockPlayer {
+  /**
+   * Moves the player, without recording a call.
+  */
+  pub fn set_position(&self, position: Vector3f) {
+    self.state.borrow_mut().position = position;
+  }
+
+  /**
+   * Turns the player, without recording a call.
+  */
+  pub fn set_rotation(&self, rotation: Rotation) {
+    self.state.borrow_mut().rotation = rotation;
+  }
+
+  /**
+   * Sets the gamemode of the player, without recording a call.
+  */
+  pub fn set_current_gamemode(&self, gamemode: i32) {
+    self.state.borrow_mut().gamemode = gamemode;
+  }
+
+  /**
+   * Sets whether the player is an operator.
+  */
+  pub fn set_op(&self, op: bool) {
+    self.state.borrow_mut().op = op;
+  }
+
+  fn record(&self, method: &'static str, args: Vec<String>) {
+    record(&self.calls, &self.state.borrow().username, method, args);
+  }
+}
+
+impl PlayerBackend for MockPlayer {
+  type Dimension = MockDimension;
+
+  fn username(&self) -> String {
+    self.state.borrow().username.clone()
+  }
+
+  fn xuid(&self) -> String {
+    self.state.borrow().xuid.clone()
+  }
+
+  fn uuid(&self) -> String {
+    self.state.borrow().uuid.clone()
+  }
+
+  fn position(&self) -> Vector3f {
+    self.state.borrow().position
+  }
+
+  fn rotation(&self) -> Rotation {
+    self.state.borrow().rotation
+  }
+
+  fn dimension(&self) -> &MockDimension {
+    &self.dimension
+  }
+
+  fn get_gamemode(&self) -> Result<i32, SerenityError> {
+    Ok(self.state.borrow().gamemode)
+  }
+
+  fn set_gamemode(&self, gamemode: i32) -> Result<(), SerenityError> {
+    self.record("set_gamemode", vec![gamemode.to_string()]);
+    self.state.borrow_mut().gamemode = gamemode;
+
+    Ok(())
+  }
+
+  fn is_op(&self) -> Result<bool, SerenityError> {
+    Ok(self.state.borrow().op)
+  }
+
+  fn send_message(&self, message: &str) -> Result<(), SerenityError> {
+    self.record("send_message", vec![message.to_string()]);
+
+    Ok(())
+  }
+
+  fn send_toast(&self, title: &str, message: &str) -> Result<(), SerenityError> {
+    self.record("send_toast", vec![title.to_string(), message.to_string()]);
+
+    Ok(())
+  }
+
+  fn teleport(&self, position: Vector3f) -> Result<(), SerenityError> {
+    self.record("teleport", vec![position.x.to_string(), position.y.to_string(), position.z.to_string()]);
+    self.state.borrow_mut().position = position;
+
+    Ok(())
+  }
+
+  fn teleport_with_rotation(&self, position: Vector3f, rotation: Rotation) -> Result<(), SerenityError> {
+    self.record("teleport_with_rotation", vec![
+      position.x.to_string(), position.y.to_string(), position.z.to_string(),
+      rotation.pitch.to_string(), rotation.yaw.to_string(), rotation.head_yaw.to_string(),
+    ]);
+
+    let mut state = self.state.borrow_mut();
+    state.position = position;
+    state.rotation = rotation;
+
+    Ok(())
+  }
+
+  fn transfer(&self, address: &str, port: i32) -> Result<(), SerenityError> {
+    self.record("transfer", vec![address.to_string(), port.to_string()]);
+
+    Ok(())
+  }
+
+  fn kill(&self) -> Result<(), SerenityError> {
+    self.record("kill", Vec::new());
+
+    Ok(())
+  }
+
+  fn get_total_experience(&self) -> Result<i32, SerenityError> {
+    Ok(self.state.borrow().experience)
+  }
+
+  fn add_experience(&self, amount: i32) -> Result<(), SerenityError> {
+    self.record("add_experience", vec![amount.to_string()]);
+    self.state.borrow_mut().experience += amount;
+
+    Ok(())
+  }
+}
+
+struct MockWorldState {
+  identifier: String,
+  current_tick: u64,
+  players: Vec<MockPlayer>,
+}
+
+/**
+ * A world holding the players added to it.
+*/
+#[derive(Clone)]
+pub struct MockWorld {
+  host: MockHost,
+  logger: MockLogger,
+  dimension: MockDimension,
+  state: Rc<RefCell<MockWorldState>>,
+}
+
+impl MockWorld {
+  /**
+   * Adds a player to the world, at the origin of its dimension.
+  */
+  pub fn add_player(&self, username: &str) -> MockPlayer {
+    // Give every player of the host a distinct xuid and uuid
+    let id = self.host.next_player.get() + 1;
+    self.host.next_player.set(id);
+
+    self.add_player_with_ids(username, &id.to_string(), &format!("00000000-0000-0000-0000-{:012}", id))
+  }
+
+  /**
+   * Adds a player with the given xuid and uuid to the world, at the origin of its dimension.
+  */
+  pub fn add_player_with_ids(&self, username: &str, xuid: &str, uuid: &str) -> MockPlayer {
+    let state = MockPlayerState {
+      username: username.to_string(),
+      xuid: xuid.to_string(),
+      uuid: uuid.to_string(),
+      position: Vector3f { x: 0.0, y: 0.0, z: 0.0 },
+      rotation: Rotation { pitch: 0.0, yaw: 0.0, head_yaw: 0.0 },
+      gamemode: 0,
+      op: false,
+      experience: 0,
+    };
+    let player = MockPlayer { state: Rc::new(RefCell::new(state)), dimension: self.dimension.clone(), calls: self.host.calls.clone() };
+
+    self.state.borrow_mut().players.push(player.clone());
+
+    player
+  }
+
+  /**
+   * Removes a player from the world.
+  */
+  pub fn remove_player(&self, player: &MockPlayer) {
+    self.state.borrow_mut().players.retain(|p| !Rc::ptr_eq(&p.state, &player.state));
+  }
+
+  /**
+   * Finds the player with the uuid in the world.
+  */
+  pub fn find_player(&self, uuid: &str) -> Option<MockPlayer> {
+    self.state.borrow().players.iter().find(|player| player.state.borrow().uuid == uuid).cloned()
+  }
+
+  /**
+   * Gets the dimension of the world.
+  */
+  pub fn dimension(&self) -> &MockDimension {
+    &self.dimension
+  }
+
+  /**
+   * Advances the current tick of the world.
+  */
+  pub fn advance_tick(&self) {
+    self.state.borrow_mut().current_tick += 1;
+  }
+
+  /**
+   * Sets the current tick of the world.
+  */
+  pub fn set_current_tick(&self, tick: u64) {
+    self.state.borrow_mut().current_tick = tick;
+  }
+}
+
+impl WorldBackend for MockWorld {
+  type Player = MockPlayer;
+  type Logger = MockLogger;
+
+  fn identifier(&self) -> String {
+    self.state.borrow().identifier.clone()
+  }
+
+  fn current_tick(&self) -> u64 {
+    self.state.borrow().current_tick
+  }
+
+  fn logger(&self) -> &MockLogger {
+    &self.logger
+  }
+
+  fn send_message(&self, message: &str) -> Result<(), SerenityError> {
+    record(&self.host.calls, &self.state.borrow().identifier, "send_message", vec![message.to_string()]);
+
+    Ok(())
+  }
+
+  fn get_players(&self) -> Result<Vec<MockPlayer>, SerenityError> {
+    Ok(self.state.borrow().players.clone())
+  }
+}
+
+/**
+ * A fake `PlayerJoinSignal`.
+*/
+pub struct MockPlayerJoinSignal {
+  pub world: MockWorld,
+  pub player: MockPlayer,
+}
+
+impl MockPlayerJoinSignal {
+  pub fn new(world: &MockWorld, player: &MockPlayer) -> Self {
+    MockPlayerJoinSignal { world: world.clone(), player: player.clone() }
+  }
+}
+
+impl PlayerJoinEvent for MockPlayerJoinSignal {
+  type World = MockWorld;
+  type Player = MockPlayer;
+
+  fn world(&self) -> &MockWorld {
+    &self.world
+  }
+
+  fn player(&self) -> &MockPlayer {
+    &self.player
+  }
+}
+
+/**
+ * A fake `PlayerLeaveSignal`.
+*/
+pub struct MockPlayerLeaveSignal {
+  pub world: MockWorld,
+  pub player: MockPlayer,
+  pub reason: i32,
+  pub message: String,
+}
+
+impl MockPlayerLeaveSignal {
+  pub fn new(world: &MockWorld, player: &MockPlayer, reason: i32, message: &str) -> Self {
+    MockPlayerLeaveSignal { world: world.clone(), player: player.clone(), reason, message: message.to_string() }
+  }
+}
+
+impl PlayerLeaveEvent for MockPlayerLeaveSignal {
+  type World = MockWorld;
+  type Player = MockPlayer;
+
+  fn world(&self) -> &MockWorld {
+    &self.world
+  }
+
+  fn player(&self) -> &MockPlayer {
+    &self.player
+  }
+
+  fn reason(&self) -> i32 {
+    self.reason
+  }
+
+  fn message(&self) -> String {
+    self.message.clone()
+  }
+
+  fn set_message(&mut self, message: &str) -> Result<(), SerenityError> {
+    self.message = message.to_string();
+
+    Ok(())
+  }
+}
+
+/**
+ * A fake `PlayerChatSignal`.
+*/
+pub struct MockPlayerChatSignal {
+  pub world: MockWorld,
+  pub player: MockPlayer,
+  pub message: String,
+  pub cancelled: bool,
+}
+
+impl MockPlayerChatSignal {
+  pub fn new(world: &MockWorld, player: &MockPlayer, message: &str) -> Self {
+    MockPlayerChatSignal { world: world.clone(), player: player.clone(), message: message.to_string(), cancelled: false }
+  }
+}
+
+impl PlayerChatEvent for MockPlayerChatSignal {
+  type World = MockWorld;
+  type Player = MockPlayer;
+
+  fn world(&self) -> &MockWorld {
+    &self.world
+  }
+
+  fn player(&self) -> &MockPlayer {
+    &self.player
+  }
+
+  fn message(&self) -> String {
+    self.message.clone()
+  }
+
+  fn set_message(&mut self, message: &str) -> Result<(), SerenityError> {
+    self.message = message.to_string();
+
+    Ok(())
+  }
+
+  fn cancel(&mut self) -> Result<(), SerenityError> {
+    self.cancelled = true;
+
+    Ok(())
+  }
+
+  fn is_cancelled(&self) -> Result<bool, SerenityError> {
+    Ok(self.cancelled)
+  }
+}
+
+/**
+ * A fake `PlayerExecuteCommandSignal`.
+*/
+pub struct MockPlayerExecuteCommandSignal {
+  pub world: MockWorld,
+  pub player: MockPlayer,
+  pub command: String,
+  pub cancelled: bool,
+}
+
+impl MockPlayerExecuteCommandSignal {
+  pub fn new(world: &MockWorld, player: &MockPlayer, command: &str) -> Self {
+    MockPlayerExecuteCommandSignal { world: world.clone(), player: player.clone(), command: command.to_string(), cancelled: false }
+  }
+}
+
+impl PlayerExecuteCommandEvent for MockPlayerExecuteCommandSignal {
+  type World = MockWorld;
+  type Player = MockPlayer;
+
+  fn world(&self) -> &MockWorld {
+    &self.world
+  }
+
+  fn player(&self) -> &MockPlayer {
+    &self.player
+  }
+
+  fn command(&self) -> String {
+    self.command.clone()
+  }
+
+  fn set_command(&mut self, command: &str) -> Result<(), SerenityError> {
+    self.command = command.to_string();
+
+    Ok(())
+  }
+
+  fn cancel(&mut self) -> Result<(), SerenityError> {
+    self.cancelled = true;
+
+    Ok(())
+  }
+
+  fn is_cancelled(&self) -> Result<bool, SerenityError> {
+    Ok(self.cancelled)
+  }
+}
+
+/**
+ * A fake `WorldTickSignal`.
+*/
+pub struct MockWorldTickSignal {
+  pub world: MockWorld,
+}
+
+impl MockWorldTickSignal {
+  pub fn new(world: &MockWorld) -> Self {
+    MockWorldTickSignal { world: world.clone() }
+  }
+}
+
+impl WorldTickEvent for MockWorldTickSignal {
+  type World = MockWorld;
+
+  fn world(&self) -> &MockWorld {
+    &self.world
+  }
+}
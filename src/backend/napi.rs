@@ -0,0 +1,281 @@
+use crate::block::block_permutation::BlockPermutation;
+use crate::block::block_volume::BlockVolume;
+use crate::error::error::SerenityError;
+use crate::logger::logger::Logger;
+use crate::protocol::types::block_position::BlockPosition;
+use crate::protocol::types::cuboid::Cuboid;
+use crate::protocol::types::rotation::Rotation;
+use crate::protocol::types::vector3f::Vector3f;
+use crate::world::events::cancellable::Cancellable;
+use crate::world::events::player_chat::PlayerChatSignal;
+use crate::world::events::player_execute_command::PlayerExecuteCommandSignal;
+use crate::world::events::player_join::PlayerJoinSignal;
+use crate::world::events::player_leave::PlayerLeaveSignal;
+use crate::world::events::world_tick::WorldTickSignal;
+use crate::world::player::player::Player;
+use crate::world::world::dimension::Dimension;
+use crate::world::world::world::World;
+
+use super::backend::*;
+use super::events::*;
+
+impl LoggerBackend for Logger {
+  fn log(&self, message: &str) -> Result<(), SerenityError> {
+    Logger::log(self, message)
+  }
+
+  fn info(&self, message: &str) -> Result<(), SerenityError> {
+    Logger::info(self, message)
+  }
+
+  fn warn(&self, message: &str) -> Result<(), SerenityError> {
+    Logger::warn(self, message)
+  }
+
+  fn error(&self, message: &str) -> Result<(), SerenityError> {
+    Logger::error(self, message)
+  }
+
+  fn success(&self, message: &str) -> Result<(), SerenityError> {
+    Logger::success(self, message)
+  }
+
+  fn debug(&self, message: &str) -> Result<(), SerenityError> {
+    Logger::debug(self, message)
+  }
+}
+
+impl DimensionBackend for Dimension {
+  fn identifier(&self) -> String {
+    self.identifier.clone()
+  }
+
+  fn view_distance(&self) -> i32 {
+    self.view_distance
+  }
+
+  fn simulation_distance(&self) -> i32 {
+    self.simulation_distance
+  }
+
+  fn block_identifier(&self, position: BlockPosition) -> Result<String, SerenityError> {
+    Ok(self.get_block(position)?.get_type()?.identifier)
+  }
+
+  fn block_permutation(&self, position: BlockPosition) -> Result<BlockPermutation, SerenityError> {
+    self.get_block(position)?.get_permutation()
+  }
+
+  fn set_block(&self, position: BlockPosition, permutation: &BlockPermutation) -> Result<(), SerenityError> {
+    Dimension::set_block(self, position, permutation)
+  }
+
+  fn get_blocks(&self, region: Cuboid) -> Result<BlockVolume, SerenityError> {
+    Dimension::get_blocks(self, region)
+  }
+
+  fn set_blocks(&self, volume: &BlockVolume) -> Result<(), SerenityError> {
+    Dimension::set_blocks(self, volume)
+  }
+}
+
+impl PlayerBackend for Player {
+  type Dimension = Dimension;
+
+  fn username(&self) -> String {
+    self.username.clone()
+  }
+
+  fn xuid(&self) -> String {
+    self.xuid.clone()
+  }
+
+  fn uuid(&self) -> String {
+    self.uuid.clone()
+  }
+
+  fn position(&self) -> Vector3f {
+    self.position
+  }
+
+  fn rotation(&self) -> Rotation {
+    self.rotation
+  }
+
+  fn dimension(&self) -> &Dimension {
+    &self.dimension
+  }
+
+  fn get_gamemode(&self) -> Result<i32, SerenityError> {
+    Player::get_gamemode(self)
+  }
+
+  fn set_gamemode(&self, gamemode: i32) -> Result<(), SerenityError> {
+    Player::set_gamemode(self, gamemode)
+  }
+
+  fn is_op(&self) -> Result<bool, SerenityError> {
+    Player::is_op(self)
+  }
+
+  fn send_message(&self, message: &str) -> Result<(), SerenityError> {
+    Player::send_message(self, message)
+  }
+
+  fn send_toast(&self, title: &str, message: &str) -> Result<(), SerenityError> {
+    Player::send_toast(self, title, message)
+  }
+
+  fn teleport(&self, position: Vector3f) -> Result<(), SerenityError> {
+    Player::teleport(self, position)
+  }
+
+  fn teleport_with_rotation(&self, position: Vector3f, rotation: Rotation) -> Result<(), SerenityError> {
+    Player::teleport_with_rotation(self, position, rotation)
+  }
+
+  fn transfer(&self, address: &str, port: i32) -> Result<(), SerenityError> {
+    Player::transfer(self, address, port)
+  }
+
+  fn kill(&self) -> Result<(), SerenityError> {
+    Player::kill(self)
+  }
+
+  fn get_total_experience(&self) -> Result<i32, SerenityError> {
+    Player::get_total_experience(self)
+  }
+
+  fn add_experience(&self, amount: i32) -> Result<(), SerenityError> {
+    Player::add_experience(self, amount)
+  }
+}
+
+impl WorldBackend for World {
+  type Player = Player;
+  type Logger = Logger;
+
+  fn identifier(&self) -> String {
+    self.identifier.clone()
+  }
+
+  fn current_tick(&self) -> u64 {
+    self.current_tick
+  }
+
+  fn logger(&self) -> &Logger {
+    &self.logger
+  }
+
+  fn send_message(&self, message: &str) -> Result<(), SerenityError> {
+    World::send_message(self, message)
+  }
+
+  fn get_players(&self) -> Result<Vec<Player>, SerenityError> {
+    World::get_players(self)
+  }
+}
+
+impl PlayerJoinEvent for PlayerJoinSignal {
+  type World = World;
+  type Player = Player;
+
+  fn world(&self) -> &World {
+    &self.world
+  }
+
+  fn player(&self) -> &Player {
+    &self.player
+  }
+}
+
+impl PlayerLeaveEvent for PlayerLeaveSignal {
+  type World = World;
+  type Player = Player;
+
+  fn world(&self) -> &World {
+    &self.world
+  }
+
+  fn player(&self) -> &Player {
+    &self.player
+  }
+
+  fn reason(&self) -> i32 {
+    self.reason
+  }
+
+  fn message(&self) -> String {
+    self.message.clone()
+  }
+
+  fn set_message(&mut self, message: &str) -> Result<(), SerenityError> {
+    PlayerLeaveSignal::set_message(self, message)
+  }
+}
+
+impl PlayerChatEvent for PlayerChatSignal {
+  type World = World;
+  type Player = Player;
+
+  fn world(&self) -> &World {
+    &self.world
+  }
+
+  fn player(&self) -> &Player {
+    &self.player
+  }
+
+  fn message(&self) -> String {
+    self.message.clone()
+  }
+
+  fn set_message(&mut self, message: &str) -> Result<(), SerenityError> {
+    PlayerChatSignal::set_message(self, message)
+  }
+
+  fn cancel(&mut self) -> Result<(), SerenityError> {
+    Cancellable::cancel(self)
+  }
+
+  fn is_cancelled(&self) -> Result<bool, SerenityError> {
+    Cancellable::is_cancelled(self)
+  }
+}
+
+impl PlayerExecuteCommandEvent for PlayerExecuteCommandSignal {
+  type World = World;
+  type Player = Player;
+
+  fn world(&self) -> &World {
+    &self.world
+  }
+
+  fn player(&self) -> &Player {
+    &self.player
+  }
+
+  fn command(&self) -> String {
+    self.command.clone()
+  }
+
+  fn set_command(&mut self, command: &str) -> Result<(), SerenityError> {
+    PlayerExecuteCommandSignal::set_command(self, command)
+  }
+
+  fn cancel(&mut self) -> Result<(), SerenityError> {
+    Cancellable::cancel(self)
+  }
+
+  fn is_cancelled(&self) -> Result<bool, SerenityError> {
+    Cancellable::is_cancelled(self)
+  }
+}
+
+impl WorldTickEvent for WorldTickSignal {
+  type World = World;
+
+  fn world(&self) -> &World {
+    &self.world
+  }
+}
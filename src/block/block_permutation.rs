@@ -0,0 +1,180 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use napi::{JsObject, JsUnknown, ValueType};
+
+use crate::error::error::SerenityError;
+use crate::utils::js_wrapper::{FromNodeValue, ToNodeValue};
+use crate::utils::node_converter::*;
+
+const WRAPPER: &str = "BlockPermutation";
+
+/**
+ * The value of a single block state.
+*/
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum BlockState {
+  Bool(bool),
+  Int(i32),
+  String(String),
+}
+
+impl From<bool> for BlockState {
+  fn from(value: bool) -> Self {
+    BlockState::Bool(value)
+  }
+}
+
+impl From<i32> for BlockState {
+  fn from(value: i32) -> Self {
+    BlockState::Int(value)
+  }
+}
+
+impl From<&str> for BlockState {
+  fn from(value: &str) -> Self {
+    BlockState::String(value.to_string())
+  }
+}
+
+impl From<String> for BlockState {
+  fn from(value: String) -> Self {
+    BlockState::String(value)
+  }
+}
+
+impl fmt::Display for BlockState {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      BlockState::Bool(value) => write!(f, "{}", value),
+      BlockState::Int(value) => write!(f, "{}", value),
+      BlockState::String(value) => write!(f, "\"{}\"", value),
+    }
+  }
+}
+
+/**
+ * A block type together with the values of its block states, e.g. a stair facing east.
+ *
+ * ```ignore
+ * let stairs = BlockPermutation::new("minecraft:oak_stairs")
+ *   .with_state("weirdo_direction", 0)
+ *   .with_state("upside_down_bit", false);
+ *
+ * dimension.set_block(position, &stairs)?;
+ * ```
+*/
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BlockPermutation {
+  pub identifier: String,
+  pub states: BTreeMap<String, BlockState>,
+}
+
+impl BlockPermutation {
+  /**
+   * Creates the permutation of the block type, leaving every state at its default.
+  */
+  pub fn new(identifier: &str) -> Self {
+    BlockPermutation { identifier: identifier.to_string(), states: BTreeMap::new() }
+  }
+
+  /**
+   * Gets the permutation with the state set to the value.
+  */
+  pub fn with_state(mut self, name: &str, value: impl Into<BlockState>) -> Self {
+    self.states.insert(name.to_string(), value.into());
+    self
+  }
+
+  /**
+   * Gets the value of the state, if the permutation sets it.
+  */
+  pub fn state(&self, name: &str) -> Option<&BlockState> {
+    self.states.get(name)
+  }
+
+  /**
+   * Reads the permutation from a SerenityJS BlockPermutation object.
+  */
+  pub fn from_js_object(env: napi::Env, object: &JsObject) -> Result<Self, SerenityError> {
+    // Get the identifier from the block type
+    let block_type = get_node_object(object, WRAPPER, "type")?;
+    let identifier = get_node_string(&block_type, WRAPPER, "identifier")?;
+
+    // Get the state object, and convert each of its properties
+    let state = get_node_object(object, WRAPPER, "state")?;
+    let names = state.get_property_names()
+      .map_err(|e| SerenityError::NapiFailure { wrapper: WRAPPER, message: e.to_string() })?;
+    let length = names.get_array_length()
+      .map_err(|e| SerenityError::NapiFailure { wrapper: WRAPPER, message: e.to_string() })?;
+
+    let mut states = BTreeMap::new();
+    for index in 0..length {
+      let name = names.get_element::<JsUnknown>(index)
+        .map_err(|e| SerenityError::NapiFailure { wrapper: WRAPPER, message: e.to_string() })?;
+      let name = String::from_node_value(env, name, WRAPPER, "state")?;
+      let value = get_node_property(&state, WRAPPER, &name)?;
+
+      states.insert(name.clone(), state_from_node_value(env, value, &name)?);
+    }
+
+    Ok(BlockPermutation { identifier, states })
+  }
+
+  /**
+   * Creates a JS object holding the states, as taken by `BlockPermutation.resolve`.
+  */
+  pub fn states_to_js_object(&self, env: napi::Env) -> Result<JsObject, SerenityError> {
+    let mut object = create_node_object(&env, WRAPPER)?;
+
+    for (name, value) in &self.states {
+      let value = match value {
+        BlockState::Bool(value) => value.to_node_value(env, WRAPPER)?,
+        BlockState::Int(value) => value.to_node_value(env, WRAPPER)?,
+        BlockState::String(value) => value.to_node_value(env, WRAPPER)?,
+      };
+
+      set_node_property(&mut object, WRAPPER, name, value)?;
+    }
+
+    Ok(object)
+  }
+}
+
+/**
+ * Formats the permutation like commands do, e.g. `minecraft:oak_stairs["upside_down_bit"=false]`.
+*/
+impl fmt::Display for BlockPermutation {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.identifier)?;
+    if self.states.is_empty() {
+      return Ok(());
+    }
+
+    write!(f, "[")?;
+    for (index, (name, value)) in self.states.iter().enumerate() {
+      if index > 0 {
+        write!(f, ",")?;
+      }
+      write!(f, "\"{}\"={}", name, value)?;
+    }
+    write!(f, "]")
+  }
+}
+
+fn state_from_node_value(env: napi::Env, value: JsUnknown, name: &str) -> Result<BlockState, SerenityError> {
+  let value_type = value.get_type()
+    .map_err(|e| SerenityError::NapiFailure { wrapper: WRAPPER, message: e.to_string() })?;
+
+  match value_type {
+    ValueType::Boolean => Ok(BlockState::Bool(bool::from_node_value(env, value, WRAPPER, name)?)),
+    ValueType::Number => Ok(BlockState::Int(i32::from_node_value(env, value, WRAPPER, name)?)),
+    ValueType::String => Ok(BlockState::String(String::from_node_value(env, value, WRAPPER, name)?)),
+    _ => Err(SerenityError::InvalidType {
+      wrapper: WRAPPER,
+      property: name.to_string(),
+      expected: "boolean, number or string",
+      found: format!("{:?}", value_type).to_lowercase(),
+    })
+  }
+}
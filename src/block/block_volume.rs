@@ -0,0 +1,237 @@
+use std::collections::HashMap;
+use std::ops::Index;
+
+use crate::error::error::SerenityError;
+use crate::protocol::types::block_position::BlockPosition;
+use crate::protocol::types::cuboid::Cuboid;
+
+use super::block_permutation::BlockPermutation;
+
+const WRAPPER: &str = "BlockVolume";
+
+/**
+ * The blocks of a cuboid region, stored as indices into a palette of permutations.
+ * The blocks are ordered like `Cuboid::iter`, by x, then z, then y.
+*/
+#[derive(Debug, Clone)]
+pub struct BlockVolume {
+  region: Cuboid,
+  palette: Vec<BlockPermutation>,
+  indices: Vec<u32>,
+  lookup: HashMap<BlockPermutation, u32>,
+}
+
+impl BlockVolume {
+  /**
+   * Creates the volume of the region, with every block set to the permutation.
+   * Fails when the region has more blocks than can be allocated.
+  */
+  pub fn new(region: Cuboid, fill: BlockPermutation) -> Result<Self, SerenityError> {
+    let overflow = || SerenityError::ConversionOverflow { wrapper: WRAPPER, property: "region".to_string(), target: "an in-memory block volume" };
+
+    // Reserve the indices up front, so a huge region is an error rather than an abort
+    let volume = usize::try_from(region.volume()).map_err(|_| overflow())?;
+    let mut indices = Vec::new();
+    indices.try_reserve_exact(volume).map_err(|_| overflow())?;
+    indices.resize(volume, 0);
+
+    Ok(BlockVolume {
+      region,
+      lookup: HashMap::from([(fill.clone(), 0)]),
+      palette: vec![fill],
+      indices,
+    })
+  }
+
+  /**
+   * Creates a volume of a region with as many blocks as this one, filled with its first permutation.
+   * Unlike `new` this can not fail, as this volume already holds as many blocks.
+  */
+  pub(crate) fn reshaped(&self, region: Cuboid) -> BlockVolume {
+    debug_assert_eq!(region.volume(), self.region.volume());
+    let fill = self.palette[0].clone();
+
+    BlockVolume {
+      region,
+      lookup: HashMap::from([(fill.clone(), 0)]),
+      palette: vec![fill],
+      indices: vec![0; self.indices.len()],
+    }
+  }
+
+  /**
+   * Creates the volume from its palette and the palette index of every block.
+  */
+  pub fn from_palette(region: Cuboid, palette: Vec<BlockPermutation>, indices: Vec<u32>) -> Result<Self, SerenityError> {
+    // Check that there is an index for every block
+    if indices.len() as u64 != region.volume() {
+      return Err(SerenityError::InvalidType {
+        wrapper: WRAPPER,
+        property: "indices".to_string(),
+        expected: "an index for every block of the region",
+        found: format!("{} indices for {} blocks", indices.len(), region.volume()),
+      });
+    }
+
+    // Check that every index points into the palette
+    if let Some(index) = indices.iter().find(|index| **index as usize >= palette.len()) {
+      return Err(SerenityError::InvalidType {
+        wrapper: WRAPPER,
+        property: "indices".to_string(),
+        expected: "indices within the palette",
+        found: format!("index {} for a palette of {}", index, palette.len()),
+      });
+    }
+
+    let mut volume = BlockVolume { region, palette, indices, lookup: HashMap::new() };
+    volume.compact();
+
+    Ok(volume)
+  }
+
+  pub fn region(&self) -> Cuboid {
+    self.region
+  }
+
+  /**
+   * Gets the permutations the blocks refer to, unused ones remain until the volume is compacted.
+  */
+  pub fn palette(&self) -> &[BlockPermutation] {
+    &self.palette
+  }
+
+  /**
+   * Gets the palette index of every block.
+  */
+  pub fn indices(&self) -> &[u32] {
+    &self.indices
+  }
+
+  /**
+   * Gets the number of blocks in the volume.
+  */
+  pub fn len(&self) -> usize {
+    self.indices.len()
+  }
+
+  /**
+   * Checks if the volume has no blocks, which a volume always has.
+  */
+  pub fn is_empty(&self) -> bool {
+    self.indices.is_empty()
+  }
+
+  /**
+   * Gets the index of the position in `indices`, if the position is within the region.
+  */
+  pub fn index_of(&self, position: BlockPosition) -> Option<usize> {
+    if !self.region.contains(position) {
+      return None;
+    }
+
+    let size = self.region.size();
+    let offset = position - self.region.min;
+
+    Some(((offset.x as usize * size.z as usize) + offset.z as usize) * size.y as usize + offset.y as usize)
+  }
+
+  /**
+   * Gets the permutation of the block at the position.
+  */
+  pub fn get(&self, position: BlockPosition) -> Option<&BlockPermutation> {
+    let index = self.index_of(position)?;
+
+    Some(&self.palette[self.indices[index] as usize])
+  }
+
+  /**
+   * Sets the block at the position, returns false when the position is outside the region.
+  */
+  pub fn set(&mut self, position: BlockPosition, permutation: &BlockPermutation) -> bool {
+    let Some(index) = self.index_of(position) else {
+      return false;
+    };
+
+    self.indices[index] = self.palette_index(permutation);
+
+    true
+  }
+
+  /**
+   * Sets every block of the volume to the permutation.
+  */
+  pub fn fill(&mut self, permutation: &BlockPermutation) {
+    let index = self.palette_index(permutation);
+    self.indices.fill(index);
+  }
+
+  /**
+   * Removes the permutations no block refers to, and merges duplicate ones.
+   * The remaining permutations keep the order they first appear in.
+  */
+  pub fn compact(&mut self) {
+    let mut palette = Vec::new();
+    let mut lookup: HashMap<BlockPermutation, u32> = HashMap::new();
+    let mut remap: Vec<Option<u32>> = vec![None; self.palette.len()];
+
+    for index in self.indices.iter_mut() {
+      let old = *index as usize;
+
+      *index = *remap[old].get_or_insert_with(|| {
+        let permutation = &self.palette[old];
+
+        *lookup.entry(permutation.clone()).or_insert_with(|| {
+          palette.push(permutation.clone());
+          palette.len() as u32 - 1
+        })
+      });
+    }
+
+    self.palette = palette;
+    self.lookup = lookup;
+  }
+
+  /**
+   * Iterates over every block and its permutation, ordered like `Cuboid::iter`.
+  */
+  pub fn iter(&self) -> impl Iterator<Item = (BlockPosition, &BlockPermutation)> {
+    self.region.iter()
+      .zip(self.indices.iter())
+      .map(|(position, index)| (position, &self.palette[*index as usize]))
+  }
+
+  fn palette_index(&mut self, permutation: &BlockPermutation) -> u32 {
+    if let Some(index) = self.lookup.get(permutation) {
+      return *index;
+    }
+
+    self.palette.push(permutation.clone());
+    let index = self.palette.len() as u32 - 1;
+    self.lookup.insert(permutation.clone(), index);
+
+    index
+  }
+}
+
+/**
+ * Volumes are equal when they cover the same region with the same blocks,
+ * regardless of the order or unused entries of their palettes.
+*/
+impl PartialEq for BlockVolume {
+  fn eq(&self, other: &BlockVolume) -> bool {
+    self.region == other.region && self.iter().zip(other.iter()).all(|((_, a), (_, b))| a == b)
+  }
+}
+
+impl Eq for BlockVolume {}
+
+impl Index<BlockPosition> for BlockVolume {
+  type Output = BlockPermutation;
+
+  /**
+   * Gets the permutation of the block at the position, panicking when it is outside the region.
+  */
+  fn index(&self, position: BlockPosition) -> &BlockPermutation {
+    self.get(position).expect("the position is outside the block volume")
+  }
+}
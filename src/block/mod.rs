@@ -1 +1,3 @@
-pub mod block_type;
\ No newline at end of file
+pub mod block_type;
+pub mod block_permutation;
+pub mod block_volume;
\ No newline at end of file
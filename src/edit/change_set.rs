@@ -0,0 +1,153 @@
+use std::collections::BTreeMap;
+use std::mem::size_of;
+
+use crate::backend::backend::DimensionBackend;
+use crate::block::block_permutation::{BlockPermutation, BlockState};
+use crate::error::error::SerenityError;
+use crate::protocol::types::block_position::BlockPosition;
+use crate::protocol::types::cuboid::Cuboid;
+use crate::protocol::types::sub_chunk_position::SubChunkPosition;
+
+/** The number of changes within a sub-chunk from which its blocks are read and written at once. */
+const MIN_BULK_CHANGES: usize = 8;
+
+/**
+ * The permutation of a block before and after it was changed.
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlockChange {
+  pub before: BlockPermutation,
+  pub after: BlockPermutation,
+}
+
+/**
+ * The blocks changed by an edit, keeping the permutation each block had before the edit first touched it.
+*/
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ChangeSet {
+  changes: BTreeMap<BlockPosition, BlockChange>,
+}
+
+impl ChangeSet {
+  pub fn new() -> Self {
+    ChangeSet::default()
+  }
+
+  /**
+   * Records a change of the block, a block changed back to its original permutation is forgotten.
+  */
+  pub fn record(&mut self, position: BlockPosition, before: BlockPermutation, after: BlockPermutation) {
+    let before = match self.changes.remove(&position) {
+      Some(change) => change.before,
+      None => before,
+    };
+
+    if before != after {
+      self.changes.insert(position, BlockChange { before, after });
+    }
+  }
+
+  /**
+   * Adds the changes of the later change set on top of this one.
+  */
+  pub fn merge(&mut self, later: ChangeSet) {
+    for (position, change) in later.changes {
+      self.record(position, change.before, change.after);
+    }
+  }
+
+  pub fn get(&self, position: BlockPosition) -> Option<&BlockChange> {
+    self.changes.get(&position)
+  }
+
+  pub fn len(&self) -> usize {
+    self.changes.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.changes.is_empty()
+  }
+
+  pub fn iter(&self) -> impl Iterator<Item = (BlockPosition, &BlockChange)> {
+    self.changes.iter().map(|(position, change)| (*position, change))
+  }
+
+  /**
+   * Gets the smallest region containing every changed block.
+  */
+  pub fn bounds(&self) -> Option<Cuboid> {
+    self.changes.keys()
+      .map(|position| Cuboid::new(*position, *position))
+      .reduce(|bounds, block| bounds.union(&block))
+  }
+
+  /**
+   * Estimates the memory the change set takes up, in bytes.
+  */
+  pub fn memory_size(&self) -> usize {
+    self.changes.values().map(|change| {
+      size_of::<BlockPosition>() + size_of::<BlockChange>() + permutation_size(&change.before) + permutation_size(&change.after)
+    }).sum()
+  }
+
+  /**
+   * Sets every changed block back to the permutation it had before.
+  */
+  pub fn undo<D: DimensionBackend>(&self, dimension: &D) -> Result<(), SerenityError> {
+    self.apply(dimension, |change| &change.before)
+  }
+
+  /**
+   * Sets every changed block to the permutation it was changed to.
+  */
+  pub fn redo<D: DimensionBackend>(&self, dimension: &D) -> Result<(), SerenityError> {
+    self.apply(dimension, |change| &change.after)
+  }
+
+  fn apply<D, F>(&self, dimension: &D, permutation: F) -> Result<(), SerenityError>
+  where
+    D: DimensionBackend,
+    F: Fn(&BlockChange) -> &BlockPermutation
+  {
+    // Group the changes by sub-chunk, so distant changes never read and rewrite the blocks between them
+    let mut sections: BTreeMap<SubChunkPosition, Vec<(BlockPosition, &BlockChange)>> = BTreeMap::new();
+    for (position, change) in &self.changes {
+      sections.entry(SubChunkPosition::from(*position)).or_default().push((*position, change));
+    }
+
+    for changes in sections.values() {
+      // A few changes are cheaper to set one by one
+      if changes.len() < MIN_BULK_CHANGES {
+        for (position, change) in changes {
+          dimension.set_block(*position, permutation(change))?;
+        }
+        continue;
+      }
+
+      // Otherwise patch the changes into the blocks around them, so the section is written at once
+      let (first, _) = changes[0];
+      let bounds = changes.iter().fold(Cuboid::new(first, first), |bounds, (position, _)| bounds.union(&Cuboid::new(*position, *position)));
+      let mut volume = dimension.get_blocks(bounds)?;
+      for (position, change) in changes {
+        volume.set(*position, permutation(change));
+      }
+
+      dimension.set_blocks(&volume)?;
+    }
+
+    Ok(())
+  }
+}
+
+fn permutation_size(permutation: &BlockPermutation) -> usize {
+  let states: usize = permutation.states.iter().map(|(name, value)| {
+    let value = match value {
+      BlockState::String(value) => value.len(),
+      _ => 0,
+    };
+
+    name.len() + size_of::<BlockState>() + value
+  }).sum();
+
+  permutation.identifier.len() + states
+}
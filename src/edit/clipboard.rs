@@ -0,0 +1,179 @@
+use crate::block::block_permutation::{BlockPermutation, BlockState};
+use crate::block::block_volume::BlockVolume;
+use crate::protocol::types::block_position::BlockPosition;
+use crate::protocol::types::cuboid::Cuboid;
+
+/**
+ * The horizontal axis a clipboard is mirrored along.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MirrorAxis {
+  /** Flips the blocks from west to east. */
+  X,
+
+  /** Flips the blocks from north to south. */
+  Z,
+}
+
+// The values of the direction states, indexed north, east, south, west
+const CARDINAL_DIRECTIONS: [&str; 4] = ["north", "east", "south", "west"];
+const FACING_DIRECTIONS: [i32; 4] = [2, 5, 3, 4];
+const WEIRDO_DIRECTIONS: [i32; 4] = [3, 0, 2, 1];
+const DIRECTIONS: [i32; 4] = [2, 3, 0, 1];
+
+/**
+ * Blocks copied out of a dimension, positioned relative to the corner they are pasted at.
+ * Rotating and mirroring move the blocks and turn their direction states along, so stairs, doors and logs keep facing the same way relative to the blocks around them.
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct Clipboard {
+  blocks: BlockVolume,
+}
+
+impl Clipboard {
+  /**
+   * Creates the clipboard from the volume, with its min corner becoming the paste corner.
+  */
+  pub fn new(volume: &BlockVolume) -> Self {
+    let region = volume.region();
+    let relative = Cuboid { min: BlockPosition::ZERO, max: region.max - region.min };
+
+    let mut blocks = volume.reshaped(relative);
+    for (position, permutation) in volume.iter() {
+      blocks.set(position - region.min, permutation);
+    }
+    blocks.compact();
+
+    Clipboard { blocks }
+  }
+
+  /**
+   * Gets the number of blocks along each axis.
+  */
+  pub fn size(&self) -> BlockPosition {
+    self.blocks.region().size()
+  }
+
+  /**
+   * Gets the permutation of the block at the position relative to the paste corner.
+  */
+  pub fn get(&self, position: BlockPosition) -> Option<&BlockPermutation> {
+    self.blocks.get(position)
+  }
+
+  /**
+   * Gets the blocks, relative to the paste corner.
+  */
+  pub fn blocks(&self) -> &BlockVolume {
+    &self.blocks
+  }
+
+  /**
+   * Rotates the blocks around the y axis by the number of quarter turns, clockwise when seen from above.
+  */
+  pub fn rotate(&self, quarter_turns: i32) -> Clipboard {
+    let size = self.size();
+    let transform = Transform::Rotate(quarter_turns.rem_euclid(4) as usize);
+
+    match quarter_turns.rem_euclid(4) {
+      0 => self.clone(),
+      1 => self.remap(BlockPosition { x: size.z, y: size.y, z: size.x }, transform, |p| BlockPosition { x: size.z - 1 - p.z, y: p.y, z: p.x }),
+      2 => self.remap(size, transform, |p| BlockPosition { x: size.x - 1 - p.x, y: p.y, z: size.z - 1 - p.z }),
+      _ => self.remap(BlockPosition { x: size.z, y: size.y, z: size.x }, transform, |p| BlockPosition { x: p.z, y: p.y, z: size.x - 1 - p.x }),
+    }
+  }
+
+  /**
+   * Mirrors the blocks along the axis.
+  */
+  pub fn mirror(&self, axis: MirrorAxis) -> Clipboard {
+    let size = self.size();
+    let transform = Transform::Mirror(axis);
+
+    match axis {
+      MirrorAxis::X => self.remap(size, transform, |p| BlockPosition { x: size.x - 1 - p.x, ..p }),
+      MirrorAxis::Z => self.remap(size, transform, |p| BlockPosition { z: size.z - 1 - p.z, ..p }),
+    }
+  }
+
+  fn remap<F: Fn(BlockPosition) -> BlockPosition>(&self, size: BlockPosition, transform: Transform, map: F) -> Clipboard {
+    let region = Cuboid { min: BlockPosition::ZERO, max: size - BlockPosition { x: 1, y: 1, z: 1 } };
+
+    // Transform the states of each palette entry once, rather than for every block
+    let palette = self.blocks.palette().iter()
+      .map(|permutation| transform.permutation(permutation))
+      .collect::<Vec<_>>();
+
+    let mut blocks = self.blocks.reshaped(region);
+    for (position, index) in self.blocks.region().iter().zip(self.blocks.indices()) {
+      blocks.set(map(position), &palette[*index as usize]);
+    }
+    blocks.compact();
+
+    Clipboard { blocks }
+  }
+}
+
+/**
+ * How the blocks of a clipboard are moved, applied to their direction states.
+*/
+#[derive(Clone, Copy)]
+enum Transform {
+  /** Clockwise quarter turns, from 0 to 3. */
+  Rotate(usize),
+  Mirror(MirrorAxis),
+}
+
+impl Transform {
+  /**
+   * Gets the horizontal direction a direction ends up at, both indexed north, east, south, west.
+  */
+  fn direction(self, index: usize) -> usize {
+    match self {
+      Transform::Rotate(quarter_turns) => (index + quarter_turns) % 4,
+      Transform::Mirror(MirrorAxis::X) => [0, 3, 2, 1][index],
+      Transform::Mirror(MirrorAxis::Z) => [2, 1, 0, 3][index],
+    }
+  }
+
+  /**
+   * Gets the permutation with its direction states transformed, other states are kept as they are.
+  */
+  fn permutation(self, permutation: &BlockPermutation) -> BlockPermutation {
+    let mut transformed = permutation.clone();
+
+    for (name, value) in transformed.states.iter_mut() {
+      let state = match (name.as_str(), &*value) {
+        ("minecraft:cardinal_direction" | "minecraft:facing_direction" | "minecraft:block_face", BlockState::String(direction)) => {
+          self.turn(&CARDINAL_DIRECTIONS, &direction.as_str()).map(BlockState::from)
+        },
+        ("facing_direction", BlockState::Int(direction)) => self.turn(&FACING_DIRECTIONS, direction).map(BlockState::Int),
+        ("weirdo_direction", BlockState::Int(direction)) => self.turn(&WEIRDO_DIRECTIONS, direction).map(BlockState::Int),
+        ("direction", BlockState::Int(direction)) => self.turn(&DIRECTIONS, direction).map(BlockState::Int),
+        ("pillar_axis", BlockState::String(axis)) if matches!(self, Transform::Rotate(1 | 3)) => match axis.as_str() {
+          "x" => Some(BlockState::from("z")),
+          "z" => Some(BlockState::from("x")),
+          _ => None,
+        },
+        // A mirrored door opens the other way
+        ("door_hinge_bit", BlockState::Bool(hinge)) if matches!(self, Transform::Mirror(_)) => Some(BlockState::Bool(!hinge)),
+        _ => None,
+      };
+
+      if let Some(state) = state {
+        *value = state;
+      }
+    }
+
+    transformed
+  }
+
+  /**
+   * Transforms a direction given by its value in the table, values not in the table such as up and down are kept.
+  */
+  fn turn<T: PartialEq + Copy>(self, table: &[T; 4], value: &T) -> Option<T> {
+    let index = table.iter().position(|entry| entry == value)?;
+
+    Some(table[self.direction(index)])
+  }
+}
@@ -0,0 +1,187 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::backend::backend::DimensionBackend;
+use crate::error::error::SerenityError;
+use crate::world::player::player_ref::PlayerRef;
+
+use super::change_set::ChangeSet;
+
+/** The memory a history keeps its change sets in by default, 64 MiB. */
+pub const DEFAULT_MEMORY_CAP: usize = 64 * 1024 * 1024;
+
+/**
+ * The undo and redo stacks of change sets.
+ * The oldest change sets are forgotten once the stacks take up more than the memory cap,
+ * though the latest change set is always kept.
+*/
+#[derive(Debug, Clone)]
+pub struct EditHistory {
+  undo: VecDeque<ChangeSet>,
+  redo: Vec<ChangeSet>,
+  memory_cap: usize,
+  // The memory size of every change set on the stacks, kept to not walk the change sets on every push
+  memory_size: usize,
+}
+
+impl Default for EditHistory {
+  fn default() -> Self {
+    EditHistory::new(DEFAULT_MEMORY_CAP)
+  }
+}
+
+impl EditHistory {
+  pub fn new(memory_cap: usize) -> Self {
+    EditHistory { undo: VecDeque::new(), redo: Vec::new(), memory_cap, memory_size: 0 }
+  }
+
+  pub fn memory_cap(&self) -> usize {
+    self.memory_cap
+  }
+
+  pub fn set_memory_cap(&mut self, memory_cap: usize) {
+    self.memory_cap = memory_cap;
+    self.trim();
+  }
+
+  /**
+   * Estimates the memory the stacks take up, in bytes.
+  */
+  pub fn memory_size(&self) -> usize {
+    self.memory_size
+  }
+
+  pub fn can_undo(&self) -> bool {
+    !self.undo.is_empty()
+  }
+
+  pub fn can_redo(&self) -> bool {
+    !self.redo.is_empty()
+  }
+
+  /**
+   * Pushes the changes of a finished edit, which clears the redo stack.
+  */
+  pub fn push(&mut self, changes: ChangeSet) {
+    if changes.is_empty() {
+      return;
+    }
+
+    self.memory_size += changes.memory_size();
+    for forgotten in self.redo.drain(..) {
+      self.memory_size -= forgotten.memory_size();
+    }
+
+    self.undo.push_back(changes);
+    self.trim();
+  }
+
+  /**
+   * Undoes the latest change set, returns false when there is nothing to undo.
+  */
+  pub fn undo<D: DimensionBackend>(&mut self, dimension: &D) -> Result<bool, SerenityError> {
+    let Some(changes) = self.undo.pop_back() else {
+      return Ok(false);
+    };
+
+    // Keep the change set on its stack when it could not be applied
+    if let Err(e) = changes.undo(dimension) {
+      self.undo.push_back(changes);
+      return Err(e);
+    }
+
+    self.redo.push(changes);
+
+    Ok(true)
+  }
+
+  /**
+   * Redoes the latest undone change set, returns false when there is nothing to redo.
+  */
+  pub fn redo<D: DimensionBackend>(&mut self, dimension: &D) -> Result<bool, SerenityError> {
+    let Some(changes) = self.redo.pop() else {
+      return Ok(false);
+    };
+
+    if let Err(e) = changes.redo(dimension) {
+      self.redo.push(changes);
+      return Err(e);
+    }
+
+    self.undo.push_back(changes);
+
+    Ok(true)
+  }
+
+  /**
+   * Forgets every change set.
+  */
+  pub fn clear(&mut self) {
+    self.undo.clear();
+    self.redo.clear();
+    self.memory_size = 0;
+  }
+
+  fn trim(&mut self) {
+    // Forget redoable change sets first, then the oldest undoable ones
+    while self.memory_size > self.memory_cap && !self.redo.is_empty() {
+      self.memory_size -= self.redo.remove(0).memory_size();
+    }
+
+    while self.memory_size > self.memory_cap && self.undo.len() > 1 {
+      if let Some(forgotten) = self.undo.pop_front() {
+        self.memory_size -= forgotten.memory_size();
+      }
+    }
+  }
+}
+
+/**
+ * An edit history for every player, sharing the same memory cap.
+*/
+#[derive(Debug, Clone)]
+pub struct PlayerHistories {
+  histories: HashMap<PlayerRef, EditHistory>,
+  memory_cap: usize,
+}
+
+impl Default for PlayerHistories {
+  fn default() -> Self {
+    PlayerHistories::new(DEFAULT_MEMORY_CAP)
+  }
+}
+
+impl PlayerHistories {
+  /**
+   * Creates the histories, each capped to the memory cap.
+  */
+  pub fn new(memory_cap: usize) -> Self {
+    PlayerHistories { histories: HashMap::new(), memory_cap }
+  }
+
+  /**
+   * Gets the history of the player, creating it when the player has none yet.
+  */
+  pub fn history(&mut self, player: &PlayerRef) -> &mut EditHistory {
+    let memory_cap = self.memory_cap;
+
+    self.histories.entry(player.clone()).or_insert_with(|| EditHistory::new(memory_cap))
+  }
+
+  /**
+   * Removes the history of the player, e.g. once they leave.
+  */
+  pub fn remove(&mut self, player: &PlayerRef) -> Option<EditHistory> {
+    self.histories.remove(player)
+  }
+
+  /**
+   * Sets the memory cap of every history.
+  */
+  pub fn set_memory_cap(&mut self, memory_cap: usize) {
+    self.memory_cap = memory_cap;
+
+    for history in self.histories.values_mut() {
+      history.set_memory_cap(memory_cap);
+    }
+  }
+}
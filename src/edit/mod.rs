@@ -0,0 +1,4 @@
+pub mod change_set;
+pub mod clipboard;
+pub mod history;
+pub mod session;
@@ -0,0 +1,177 @@
+use crate::backend::backend::DimensionBackend;
+use crate::block::block_permutation::BlockPermutation;
+use crate::block::block_type::BlockType;
+use crate::error::error::SerenityError;
+use crate::protocol::types::block_face::BlockFace;
+use crate::protocol::types::block_position::BlockPosition;
+use crate::protocol::types::cuboid::Cuboid;
+use crate::world::world::dimension::Dimension;
+
+use super::change_set::ChangeSet;
+use super::clipboard::Clipboard;
+
+/**
+ * A batch of edits to a dimension, recording the prior permutation of every block it changes.
+ * Each operation reads and writes its region in one go, and returns the number of blocks it changed.
+ *
+ * ```ignore
+ * let mut session = EditSession::new(&dimension);
+ * session.fill(region, &BlockPermutation::new("minecraft:stone"))?;
+ * session.walls(region, &BlockPermutation::new("minecraft:glass"))?;
+ *
+ * histories.history(&PlayerRef::from(&player)).push(session.finish());
+ * ```
+*/
+pub struct EditSession<'a, D: DimensionBackend> {
+  dimension: &'a D,
+  changes: ChangeSet,
+}
+
+impl<'a, D: DimensionBackend> EditSession<'a, D> {
+  pub fn new(dimension: &'a D) -> Self {
+    EditSession { dimension, changes: ChangeSet::new() }
+  }
+
+  /**
+   * Gets the changes made so far.
+  */
+  pub fn changes(&self) -> &ChangeSet {
+    &self.changes
+  }
+
+  /**
+   * Ends the session, handing back its changes so they can be pushed onto a history.
+  */
+  pub fn finish(self) -> ChangeSet {
+    self.changes
+  }
+
+  /**
+   * Sets every block of the region to the permutation.
+  */
+  pub fn fill(&mut self, region: Cuboid, permutation: &BlockPermutation) -> Result<usize, SerenityError> {
+    self.edit(region, |_, _| Some(permutation.clone()))
+  }
+
+  /**
+   * Sets the blocks of the region the predicate matches to the permutation.
+  */
+  pub fn replace<F>(&mut self, region: Cuboid, mut predicate: F, permutation: &BlockPermutation) -> Result<usize, SerenityError>
+  where
+    F: FnMut(&BlockPermutation) -> bool
+  {
+    self.edit(region, |_, current| predicate(current).then(|| permutation.clone()))
+  }
+
+  /**
+   * Sets the four vertical sides of the region to the permutation.
+  */
+  pub fn walls(&mut self, region: Cuboid, permutation: &BlockPermutation) -> Result<usize, SerenityError> {
+    let mut changed = 0;
+    for face in [BlockFace::North, BlockFace::South, BlockFace::West, BlockFace::East] {
+      changed += self.fill(region.face(face), permutation)?;
+    }
+
+    Ok(changed)
+  }
+
+  /**
+   * Sets the surface of the region to the permutation, and clears everything inside it.
+  */
+  pub fn hollow(&mut self, region: Cuboid, permutation: &BlockPermutation) -> Result<usize, SerenityError> {
+    let air = BlockPermutation::new("minecraft:air");
+    let inner = Cuboid { min: region.min + BlockPosition { x: 1, y: 1, z: 1 }, max: region.max - BlockPosition { x: 1, y: 1, z: 1 } };
+    let has_inside = inner.min.x <= inner.max.x && inner.min.y <= inner.max.y && inner.min.z <= inner.max.z;
+
+    self.edit(region, |position, _| {
+      let inside = has_inside && inner.contains(position);
+
+      Some(if inside { air.clone() } else { permutation.clone() })
+    })
+  }
+
+  /**
+   * Copies the blocks of the region, with the min corner of the region as the paste corner.
+  */
+  pub fn copy(&self, region: Cuboid) -> Result<Clipboard, SerenityError> {
+    Ok(Clipboard::new(&self.dimension.get_blocks(region)?))
+  }
+
+  /**
+   * Pastes the clipboard with its paste corner at the position.
+  */
+  pub fn paste(&mut self, clipboard: &Clipboard, position: BlockPosition) -> Result<usize, SerenityError> {
+    let region = Cuboid { min: position, max: position + clipboard.size() - BlockPosition { x: 1, y: 1, z: 1 } };
+
+    self.edit(region, |block, _| clipboard.get(block - position).cloned())
+  }
+
+  /**
+   * Reads the region, asks the edit for the new permutation of every block, and writes the changed ones.
+  */
+  fn edit<F>(&mut self, region: Cuboid, mut edit: F) -> Result<usize, SerenityError>
+  where
+    F: FnMut(BlockPosition, &BlockPermutation) -> Option<BlockPermutation>
+  {
+    let current = self.dimension.get_blocks(region)?;
+    let mut target = current.clone();
+
+    let mut changes = Vec::new();
+    for (position, permutation) in current.iter() {
+      let Some(next) = edit(position, permutation) else {
+        continue;
+      };
+
+      if next != *permutation {
+        target.set(position, &next);
+        changes.push((position, permutation.clone(), next));
+      }
+    }
+
+    if changes.is_empty() {
+      return Ok(0);
+    }
+
+    // Only record the changes once they are in the dimension, so a failed write can't be undone
+    self.dimension.set_blocks(&target)?;
+
+    let changed = changes.len();
+    for (position, previous, next) in changes {
+      self.changes.record(position, previous, next);
+    }
+
+    Ok(changed)
+  }
+}
+
+impl EditSession<'_, Dimension> {
+  /**
+   * Sets the blocks of the region whose type the predicate matches to the permutation.
+   * Every distinct permutation of the region is looked up once to get its type.
+  */
+  pub fn replace_types<F>(&mut self, region: Cuboid, mut predicate: F, permutation: &BlockPermutation) -> Result<usize, SerenityError>
+  where
+    F: FnMut(&BlockType) -> bool
+  {
+    // Find a block of the region for every permutation in it
+    let current = self.dimension.get_blocks(region)?;
+    let mut positions = vec![None; current.palette().len()];
+    for (position, index) in region.iter().zip(current.indices()) {
+      positions[*index as usize].get_or_insert(position);
+    }
+
+    // Look up the type of each permutation through one of its blocks
+    let mut matching = Vec::new();
+    for (candidate, position) in current.palette().iter().zip(positions) {
+      let Some(position) = position else {
+        continue;
+      };
+
+      if predicate(&self.dimension.get_block(position)?.get_type()?) {
+        matching.push(candidate.clone());
+      }
+    }
+
+    self.replace(region, |block| matching.contains(block), permutation)
+  }
+}
@@ -0,0 +1,77 @@
+use std::fmt;
+
+/**
+ * An error raised while reading from, or calling into, a SerenityJS object.
+ * Every variant carries the name of the wrapper that raised it, so failures
+ * can be traced back to the Rust type that touched the JS object.
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub enum SerenityError {
+  /** The JS object does not have the expected property. */
+  MissingProperty { wrapper: &'static str, property: String },
+
+  /** The property exists, but holds a value of the wrong JS type. */
+  InvalidType { wrapper: &'static str, property: String, expected: &'static str, found: String },
+
+  /** A JS method threw an exception while being called. */
+  JsException { wrapper: &'static str, method: String, message: String },
+
+  /** A JS value does not fit into the Rust type it is being converted to. */
+  ConversionOverflow { wrapper: &'static str, property: String, target: &'static str },
+
+  /** An N-API call failed for a reason other than the ones above. */
+  NapiFailure { wrapper: &'static str, message: String },
+
+  /** A Rust callback panicked, the panic was caught before reaching JS. */
+  Panic { wrapper: &'static str, message: String, backtrace: String },
+}
+
+impl SerenityError {
+  /**
+   * Gets the name of the wrapper type that raised the error.
+  */
+  pub fn wrapper(&self) -> &'static str {
+    match self {
+      SerenityError::MissingProperty { wrapper, .. } => wrapper,
+      SerenityError::InvalidType { wrapper, .. } => wrapper,
+      SerenityError::JsException { wrapper, .. } => wrapper,
+      SerenityError::ConversionOverflow { wrapper, .. } => wrapper,
+      SerenityError::NapiFailure { wrapper, .. } => wrapper,
+      SerenityError::Panic { wrapper, .. } => wrapper,
+    }
+  }
+}
+
+impl fmt::Display for SerenityError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      SerenityError::MissingProperty { wrapper, property } => {
+        write!(f, "{}: missing property \"{}\"", wrapper, property)
+      },
+      SerenityError::InvalidType { wrapper, property, expected, found } => {
+        write!(f, "{}: property \"{}\" expected to be {}, found {}", wrapper, property, expected, found)
+      },
+      SerenityError::JsException { wrapper, method, message } => {
+        write!(f, "{}: method \"{}\" threw an exception: {}", wrapper, method, message)
+      },
+      SerenityError::ConversionOverflow { wrapper, property, target } => {
+        write!(f, "{}: property \"{}\" does not fit into {}", wrapper, property, target)
+      },
+      SerenityError::NapiFailure { wrapper, message } => {
+        write!(f, "{}: napi call failed: {}", wrapper, message)
+      },
+      SerenityError::Panic { wrapper, message, .. } => {
+        write!(f, "{}: callback panicked: {}", wrapper, message)
+      },
+    }
+  }
+}
+
+impl std::error::Error for SerenityError {}
+
+impl From<SerenityError> for napi::Error {
+  fn from(error: SerenityError) -> Self {
+    // Surface the error to JS as a regular exception
+    napi::Error::new(napi::Status::GenericFailure, error.to_string())
+  }
+}
@@ -1,8 +1,26 @@
+// Modules mirror the SerenityJS layout, e.g. `world::world::world::World`
+#![allow(clippy::module_inception)]
+
+// Lets the derive macros refer to `::serenityrs` from inside the crate
+extern crate self as serenityrs;
+
 pub mod serenity;
 pub mod world;
 pub mod protocol;
 pub mod block;
 pub mod logger;
 pub mod plugin;
+pub mod error;
+pub mod backend;
+pub mod edit;
+#[cfg(feature = "replay")]
+pub mod replay;
+
+pub mod utils;
+
+pub use serenityrs_derive::{js_method, BinaryCodec, JsWrapper};
 
-pub mod utils;
\ No newline at end of file
+#[doc(hidden)]
+pub mod __private {
+  pub use napi;
+}
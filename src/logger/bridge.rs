@@ -0,0 +1,341 @@
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::fmt;
+#[cfg(feature = "tracing")]
+use std::fmt::Write;
+use std::mem::ManuallyDrop;
+use std::sync::{Arc, Mutex};
+use std::thread::{self, ThreadId};
+
+use crate::backend::backend::LoggerBackend;
+use crate::error::error::SerenityError;
+use crate::serenity::dispatcher::MainThreadDispatcher;
+use crate::utils::js_reference::JsReference;
+use crate::utils::js_wrapper::JsWrapper;
+
+use super::logger::Logger;
+
+const WRAPPER: &str = "SerenityLogBridge";
+
+/** The number of records kept from other threads until they are flushed. */
+const MAX_BUFFERED_RECORDS: usize = 1024;
+
+type QueueFlush = Box<dyn Fn(Arc<BridgeInner>) + Send>;
+
+thread_local! {
+  // Set while the bridge writes to the JS logger, so records raised meanwhile are buffered
+  static WRITING: Cell<bool> = const { Cell::new(false) };
+}
+
+/**
+ * Routes the records of the `log` and `tracing` crates to the JS logger.
+ * Records raised on the JS thread are written right away, records from other threads are
+ * buffered until `flush` is called on the JS thread, or flushed through a `MainThreadDispatcher`.
+ *
+ * ```ignore
+ * let bridge = SerenityLogBridge::new(&serenity.logger)?.with_dispatcher(dispatcher);
+ *
+ * // With the `log` feature
+ * bridge.clone().install_log(log::LevelFilter::Info)?;
+ *
+ * // With the `tracing` feature
+ * use tracing_subscriber::prelude::*;
+ * tracing_subscriber::registry().with(bridge).init();
+ * ```
+*/
+#[derive(Clone)]
+pub struct SerenityLogBridge {
+  inner: Arc<BridgeInner>,
+}
+
+impl SerenityLogBridge {
+  /**
+   * Creates a bridge writing to the logger, must be called on the JS thread.
+  */
+  pub fn new(logger: &Logger) -> Result<Self, SerenityError> {
+    let reference = JsReference::new(logger.js_env(), logger.js_object(), WRAPPER)?;
+
+    Ok(SerenityLogBridge::from_backend(LoggerReference(reference)))
+  }
+
+  /**
+   * Creates a bridge writing to any logger backend, e.g. a `MockLogger` in tests.
+   * The calling thread becomes the JS thread, the logger is only ever used and dropped on it.
+  */
+  pub fn from_backend<L: LoggerBackend + 'static>(logger: L) -> Self {
+    let inner = BridgeInner {
+      logger: ManuallyDrop::new(Box::new(logger)),
+      main_thread: thread::current().id(),
+      buffer: Mutex::new(RecordBuffer::default()),
+      queue_flush: Mutex::new(None),
+    };
+
+    SerenityLogBridge { inner: Arc::new(inner) }
+  }
+
+  /**
+   * Flushes records from other threads through the dispatcher, instead of waiting for `flush`.
+  */
+  pub fn with_dispatcher(self, dispatcher: MainThreadDispatcher) -> Self {
+    let queue_flush: QueueFlush = Box::new(move |inner| {
+      let _ = dispatcher.run(move |_| {
+        inner.flush();
+        Ok(())
+      });
+    });
+
+    *self.inner.queue_flush.lock().unwrap_or_else(|e| e.into_inner()) = Some(queue_flush);
+    self
+  }
+
+  /**
+   * Writes the buffered records to the JS logger, does nothing when called off the JS thread.
+  */
+  pub fn flush(&self) {
+    self.inner.flush();
+  }
+
+  /**
+   * Installs the bridge as the logger of the `log` crate.
+  */
+  #[cfg(feature = "log")]
+  pub fn install_log(self, level: log::LevelFilter) -> Result<(), log::SetLoggerError> {
+    log::set_boxed_logger(Box::new(self))?;
+    log::set_max_level(level);
+
+    Ok(())
+  }
+}
+
+#[derive(Clone, Copy)]
+enum RecordLevel {
+  Error,
+  Warn,
+  Info,
+  Debug,
+}
+
+impl RecordLevel {
+  fn method(self) -> &'static str {
+    match self {
+      RecordLevel::Error => "error",
+      RecordLevel::Warn => "warn",
+      RecordLevel::Info => "info",
+      RecordLevel::Debug => "debug",
+    }
+  }
+}
+
+struct BufferedRecord {
+  level: RecordLevel,
+  message: String,
+}
+
+#[derive(Default)]
+struct RecordBuffer {
+  records: VecDeque<BufferedRecord>,
+  dropped: usize,
+}
+
+/**
+ * The JS logger, kept alive beyond the callback the bridge was created in.
+*/
+struct LoggerReference(JsReference);
+
+impl LoggerReference {
+  fn logger(&self) -> Result<Logger, SerenityError> {
+    Logger::new(self.0.env(), self.0.object()?)
+  }
+}
+
+impl LoggerBackend for LoggerReference {
+  fn log(&self, message: &str) -> Result<(), SerenityError> {
+    self.logger()?.log(message)
+  }
+
+  fn info(&self, message: &str) -> Result<(), SerenityError> {
+    self.logger()?.info(message)
+  }
+
+  fn warn(&self, message: &str) -> Result<(), SerenityError> {
+    self.logger()?.warn(message)
+  }
+
+  fn error(&self, message: &str) -> Result<(), SerenityError> {
+    self.logger()?.error(message)
+  }
+
+  fn success(&self, message: &str) -> Result<(), SerenityError> {
+    self.logger()?.success(message)
+  }
+
+  fn debug(&self, message: &str) -> Result<(), SerenityError> {
+    self.logger()?.debug(message)
+  }
+}
+
+struct BridgeInner {
+  logger: ManuallyDrop<Box<dyn LoggerBackend>>,
+  main_thread: ThreadId,
+  buffer: Mutex<RecordBuffer>,
+  // Queues a flush on the JS thread, set by `with_dispatcher`
+  queue_flush: Mutex<Option<QueueFlush>>,
+}
+
+// The logger is only ever used and dropped on the JS thread
+unsafe impl Send for BridgeInner {}
+unsafe impl Sync for BridgeInner {}
+
+impl BridgeInner {
+  fn is_main_thread(&self) -> bool {
+    thread::current().id() == self.main_thread
+  }
+
+  fn record(self: &Arc<Self>, level: RecordLevel, message: String) {
+    // Write the record right away when possible, keeping the order with buffered ones
+    if self.is_main_thread() && !WRITING.with(Cell::get) {
+      self.flush();
+      self.write(level, &message);
+      return;
+    }
+
+    // Otherwise buffer the record until the next flush
+    let was_empty = {
+      let mut buffer = self.buffer.lock().unwrap_or_else(|e| e.into_inner());
+      if buffer.records.len() >= MAX_BUFFERED_RECORDS {
+        buffer.records.pop_front();
+        buffer.dropped += 1;
+      }
+      buffer.records.push_back(BufferedRecord { level, message });
+      buffer.records.len() == 1
+    };
+
+    // Only the first buffered record needs to queue a flush
+    if was_empty && !self.is_main_thread() {
+      if let Some(queue_flush) = &*self.queue_flush.lock().unwrap_or_else(|e| e.into_inner()) {
+        queue_flush(self.clone());
+      }
+    }
+  }
+
+  fn flush(&self) {
+    if !self.is_main_thread() || WRITING.with(Cell::get) {
+      return;
+    }
+
+    // Take the records out, so records raised while writing do not deadlock
+    let buffer = std::mem::take(&mut *self.buffer.lock().unwrap_or_else(|e| e.into_inner()));
+
+    if buffer.dropped > 0 {
+      self.write(RecordLevel::Warn, &format!("{} log records from other threads were dropped", buffer.dropped));
+    }
+    for record in buffer.records {
+      self.write(record.level, &record.message);
+    }
+  }
+
+  fn write(&self, level: RecordLevel, message: &str) {
+    WRITING.with(|writing| writing.set(true));
+
+    let result = match level {
+      RecordLevel::Error => self.logger.error(message),
+      RecordLevel::Warn => self.logger.warn(message),
+      RecordLevel::Info => self.logger.info(message),
+      RecordLevel::Debug => self.logger.debug(message),
+    };
+
+    WRITING.with(|writing| writing.set(false));
+
+    // The JS logger is gone, so fall back to stderr
+    if let Err(e) = result {
+      eprintln!("{} ({}: {})", message, level.method(), e);
+    }
+  }
+}
+
+impl Drop for BridgeInner {
+  fn drop(&mut self) {
+    // The logger can only be released on the JS thread, elsewhere it is leaked
+    if self.is_main_thread() {
+      unsafe { ManuallyDrop::drop(&mut self.logger) };
+    }
+  }
+}
+
+fn prefixed(target: &str, message: fmt::Arguments<'_>) -> String {
+  format!("[{}] {}", target, message)
+}
+
+#[cfg(feature = "log")]
+impl log::Log for SerenityLogBridge {
+  fn enabled(&self, _: &log::Metadata<'_>) -> bool {
+    true
+  }
+
+  fn log(&self, record: &log::Record<'_>) {
+    let level = match record.level() {
+      log::Level::Error => RecordLevel::Error,
+      log::Level::Warn => RecordLevel::Warn,
+      log::Level::Info => RecordLevel::Info,
+      log::Level::Debug | log::Level::Trace => RecordLevel::Debug,
+    };
+
+    self.inner.record(level, prefixed(record.target(), *record.args()));
+  }
+
+  fn flush(&self) {
+    self.inner.flush();
+  }
+}
+
+/**
+ * Collects the message and the fields of a tracing event.
+*/
+#[cfg(feature = "tracing")]
+#[derive(Default)]
+struct EventVisitor {
+  message: String,
+  fields: String,
+}
+
+#[cfg(feature = "tracing")]
+impl tracing::field::Visit for EventVisitor {
+  fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+    match field.name() {
+      "message" => self.message.push_str(value),
+      name => {
+        let _ = write!(self.fields, " {}={}", name, value);
+      }
+    }
+  }
+
+  fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn fmt::Debug) {
+    match field.name() {
+      "message" => {
+        let _ = write!(self.message, "{:?}", value);
+      },
+      name => {
+        let _ = write!(self.fields, " {}={:?}", name, value);
+      }
+    }
+  }
+}
+
+#[cfg(feature = "tracing")]
+impl<S: tracing::Subscriber> tracing_subscriber::Layer<S> for SerenityLogBridge {
+  fn on_event(&self, event: &tracing::Event<'_>, _: tracing_subscriber::layer::Context<'_, S>) {
+    let metadata = event.metadata();
+    let level = match *metadata.level() {
+      tracing::Level::ERROR => RecordLevel::Error,
+      tracing::Level::WARN => RecordLevel::Warn,
+      tracing::Level::INFO => RecordLevel::Info,
+      _ => RecordLevel::Debug,
+    };
+
+    // Collect the message, followed by the fields of the event
+    let mut visitor = EventVisitor::default();
+    event.record(&mut visitor);
+
+    self.inner.record(level, prefixed(metadata.target(), format_args!("{}{}", visitor.message, visitor.fields)));
+  }
+}
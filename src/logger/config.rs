@@ -0,0 +1,57 @@
+use std::time::Duration;
+
+/**
+ * The severity of a log message, messages below the minimum level of the logger are dropped.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LogLevel {
+  Debug,
+  /** Used by `log`, `info` and `success`. */
+  Info,
+  Warn,
+  Error,
+}
+
+/**
+ * Limits how often an identical message is written within a time window.
+ * Repeats past the limit are suppressed, and summarized once the window has passed.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+  /** The time window repeats are counted in. */
+  pub window: Duration,
+  /** The number of identical messages written per window. */
+  pub max_repeats: u32,
+}
+
+impl Default for RateLimit {
+  fn default() -> Self {
+    RateLimit { window: Duration::from_secs(5), max_repeats: 3 }
+  }
+}
+
+/**
+ * The configuration of the loggers of a plugin, set with `Logger::set_config`.
+*/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoggerConfig {
+  /** The prefix written in front of every message, e.g. `[Rust]`. */
+  pub prefix: String,
+  /** The colour of the prefix, as rgb. */
+  pub color: (u8, u8, u8),
+  /** The minimum level of the messages that are written. */
+  pub min_level: LogLevel,
+  /** Limits identical messages, or `None` to write every message. */
+  pub rate_limit: Option<RateLimit>,
+}
+
+impl Default for LoggerConfig {
+  fn default() -> Self {
+    LoggerConfig {
+      prefix: "Rust".to_string(),
+      color: (183, 65, 14),
+      min_level: LogLevel::Debug,
+      rate_limit: None,
+    }
+  }
+}
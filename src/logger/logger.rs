@@ -1,184 +1,290 @@
+use std::cell::{Cell, RefCell};
+use std::fmt::{Display, Write};
+use std::time::Instant;
+
 use colored::{Colorize, CustomColor};
-use napi::{bindgen_prelude::FromNapiValue, JsObject, JsString, NapiValue};
+use napi::{CallContext, JsObject, NapiRaw, NapiValue};
 
+use crate::error::error::SerenityError;
+use crate::utils::js_reference::JsReference;
 use crate::utils::node_converter::*;
+use crate::JsWrapper;
+
+use super::config::{LogLevel, LoggerConfig, RateLimit};
+use super::rate_limit::{RateLimiter, SuppressedSummary};
+
+const WRAPPER: &str = "Logger";
 
+thread_local! {
+  // Shared by every logger of the plugin, so the configuration and rate limits outlive the wrappers
+  static CONFIG: RefCell<LoggerConfig> = RefCell::new(LoggerConfig::default());
+  static RATE_LIMITER: RefCell<RateLimiter> = RefCell::new(RateLimiter::default());
+  // Set while a timer is waiting to summarize suppressed messages
+  static FLUSH_SCHEDULED: Cell<bool> = const { Cell::new(false) };
+}
+
+#[derive(JsWrapper)]
 pub struct Logger {
   pub object: JsObject,
   pub env: napi::Env
 }
 
 impl Logger {
-  /** 
-   * Create a new Logger instance
+  /**
+   * Sets the configuration used by every logger of the plugin.
   */
-  pub fn new(env: napi::Env, object: JsObject) -> Self {
-    Logger { object, env }
+  pub fn set_config(config: LoggerConfig) {
+    CONFIG.with(|current| *current.borrow_mut() = config);
   }
 
-  /** 
-   * Logs a message to the console
+  /**
+   * Gets the configuration used by every logger of the plugin.
   */
-  pub fn log(&self, message: &str) {
-    // Get the node function
-    let func = get_node_func(&self.object, "log");
-
-    // Check if the function is an error
-    if func.is_err() {
-      return println!("Failed to get the log function");
-    }
+  pub fn config() -> LoggerConfig {
+    CONFIG.with(|current| current.borrow().clone())
+  }
 
-    // Format the message
-    let message = format!("{}{}{} {}", "[".black(), "Rust".custom_color(CustomColor::new(183, 65, 14)), "]".black(), message);
+  /**
+   * Logs a message to the console
+  */
+  pub fn log(&self, message: &str) -> Result<(), SerenityError> {
+    self.write("log", LogLevel::Info, message)
+  }
 
-    // Create a string for the message
-    let message = convert_to_js_string(&self.env, &message).unwrap();
+  /**
+   * Logs an information message to the console
+  */
+  pub fn info(&self, message: &str) -> Result<(), SerenityError> {
+    self.write("info", LogLevel::Info, message)
+  }
 
-    // Unwrap the function
-    let func = func.unwrap();
+  /**
+   * Logs a warning message to the console
+  */
+  pub fn warn(&self, message: &str) -> Result<(), SerenityError> {
+    self.write("warn", LogLevel::Warn, message)
+  }
 
-    // Call the function
-    func.call::<JsString>(Some(&self.object), &[JsString::from(message)]).unwrap();
+  /**
+   * Logs an error message to the console
+  */
+  pub fn error(&self, message: &str) -> Result<(), SerenityError> {
+    self.write("error", LogLevel::Error, message)
   }
 
-  /** 
-   * Logs an information message to the console
+  /**
+   * Logs a success message to the console
   */
-  pub fn info(&self, message: &str) {
-    // Get the node function
-    let func = get_node_func(&self.object, "info");
+  pub fn success(&self, message: &str) -> Result<(), SerenityError> {
+    self.write("success", LogLevel::Info, message)
+  }
 
-    // Check if the function is an error
-    if func.is_err() {
-      return println!("Failed to get the info function");
-    }
+  /**
+   * Logs a debug message to the console
+  */
+  pub fn debug(&self, message: &str) -> Result<(), SerenityError> {
+    self.write("debug", LogLevel::Debug, message)
+  }
 
-    // Format the message
-    let message = format!("{}{}{} {}", "[".black(), "Rust".custom_color(CustomColor::new(183, 65, 14)), "]".black(), message);
+  /**
+   * Attaches structured fields to the next message, written as `key=value` after it.
+   *
+   * ```ignore
+   * logger.fields(&[("player", &player.username), ("tick", &world.current_tick)]).info("Player joined")?;
+   * ```
+  */
+  pub fn fields<'a>(&'a self, fields: &'a [(&'a str, &'a dyn Display)]) -> FieldLogger<'a> {
+    FieldLogger { logger: self, fields }
+  }
 
-    // Create a string for the message
-    let message = convert_to_js_string(&self.env, &message).unwrap();
+  /**
+   * Writes the summaries of suppressed messages whose window has passed.
+   * This also happens on a timer once a window passes, so a flood that stopped is still summarized.
+  */
+  pub fn flush_suppressed(&self) -> Result<(), SerenityError> {
+    let config = Logger::config();
+    let limit = match config.rate_limit {
+      Some(limit) => limit,
+      None => return Ok(())
+    };
 
-    // Unwrap the function
-    let func = func.unwrap();
+    let summaries = RATE_LIMITER.with(|limiter| limiter.borrow_mut().expire(&limit, Instant::now()));
+    self.write_summaries(&config, summaries)?;
 
-    // Call the function
-    func.call::<JsString>(Some(&self.object), &[JsString::from(message)]).unwrap();
+    // Windows that have not passed yet are summarized by the next timer
+    self.schedule_flush(&limit)
   }
 
-  /** 
-   * Logs a warning message to the console
+  /**
+   * Filters, formats and rate limits the message, then passes it to the given method of the JS logger
   */
-  pub fn warn(&self, message: &str) {
-    // Get the node function
-    let func = get_node_func(&self.object, "warn");
+  fn write(&self, method: &str, level: LogLevel, message: &str) -> Result<(), SerenityError> {
+    let config = Logger::config();
 
-    // Check if the function is an error
-    if func.is_err() {
-      return println!("Failed to get the warn function");
+    // Drop messages below the minimum level
+    if level < config.min_level {
+      return Ok(());
     }
 
-    // Format the message
-    let message = format!("{}{}{} {}", "[".black(), "Rust".custom_color(CustomColor::new(183, 65, 14)), "]".black(), message);
+    // Write the summaries of suppressed messages, and check if the message is suppressed
+    if let Some(limit) = config.rate_limit {
+      let now = Instant::now();
+      let (summaries, allowed) = RATE_LIMITER.with(|limiter| {
+        let mut limiter = limiter.borrow_mut();
+        let summaries = limiter.expire(&limit, now);
 
-    // Create a string for the message
-    let message = convert_to_js_string(&self.env, &message).unwrap();
+        (summaries, limiter.allow(&limit, level, message, now))
+      });
 
-    // Unwrap the function
-    let func = func.unwrap();
+      self.write_summaries(&config, summaries)?;
 
-    // Call the function
-    func.call::<JsString>(Some(&self.object), &[JsString::from(message)]).unwrap();
+      if !allowed {
+        return self.schedule_flush(&limit);
+      }
+    }
+
+    self.call(&config, method, message)
   }
 
-  /** 
-   * Logs an error message to the console
-  */
-  pub fn error(&self, message: &str) {
-    // Get the node function
-    let func = get_node_func(&self.object, "error");
+  fn write_summaries(&self, config: &LoggerConfig, summaries: Vec<SuppressedSummary>) -> Result<(), SerenityError> {
+    for summary in summaries {
+      let summary_message = format!("Suppressed {} repeats of: {}", summary.suppressed, summary.message);
+      self.call(config, level_method(summary.level), &summary_message)?;
+    }
+
+    Ok(())
+  }
 
-    // Check if the function is an error
-    if func.is_err() {
-      return println!("Failed to get the error function");
+  /**
+   * Sets a timer to call `flush_suppressed` once the next window with suppressed messages passes.
+  */
+  fn schedule_flush(&self, limit: &RateLimit) -> Result<(), SerenityError> {
+    if FLUSH_SCHEDULED.with(Cell::get) {
+      return Ok(());
     }
 
+    let expiry = match RATE_LIMITER.with(|limiter| limiter.borrow().next_expiry(limit)) {
+      Some(expiry) => expiry,
+      None => return Ok(())
+    };
+
+    // Keep the logger alive until the timer fires
+    let logger = JsReference::new(self.env, &self.object, WRAPPER)?;
+    let handler = self.env.create_function_from_closure("serenityrsFlushSuppressed", move |_: CallContext| {
+      FLUSH_SCHEDULED.with(|scheduled| scheduled.set(false));
+
+      let flushed = logger.object()
+        .and_then(|object| Logger::new(logger.env(), object))
+        .and_then(|logger| logger.flush_suppressed());
+      if let Err(e) = flushed {
+        eprintln!("{}", e);
+      }
+
+      Ok(())
+    }).map_err(|e| SerenityError::NapiFailure { wrapper: WRAPPER, message: e.to_string() })?;
+
+    // Wait a millisecond past the expiry, so the window has passed when the timer fires
+    let delay = expiry.saturating_duration_since(Instant::now()).as_secs_f64() * 1000.0 + 1.0;
+    let timeout = self.env.get_global()
+      .and_then(|global| global.set_timeout(handler, delay))
+      .map_err(|e| SerenityError::NapiFailure { wrapper: WRAPPER, message: e.to_string() })?;
+
+    // The timer should not keep the server from shutting down
+    let timeout = unsafe { JsObject::from_raw_unchecked(self.env.raw(), timeout.raw()) };
+    call_node_method::<JsObject>(&timeout, WRAPPER, "unref", &[])?;
+    FLUSH_SCHEDULED.with(|scheduled| scheduled.set(true));
+
+    Ok(())
+  }
+
+  fn call(&self, config: &LoggerConfig, method: &str, message: &str) -> Result<(), SerenityError> {
     // Format the message
-    let message = format!("{}{}{} {}", "[".black(), "Rust".custom_color(CustomColor::new(183, 65, 14)), "]".black(), message);
+    let (r, g, b) = config.color;
+    let message = format!("{}{}{} {}", "[".black(), config.prefix.custom_color(CustomColor::new(r, g, b)), "]".black(), message);
 
     // Create a string for the message
-    let message = convert_to_js_string(&self.env, &message).unwrap();
-
-    // Unwrap the function
-    let func = func.unwrap();
+    let message = convert_to_js_string(&self.env, WRAPPER, &message)?;
 
     // Call the function
-    func.call::<JsString>(Some(&self.object), &[JsString::from(message)]).unwrap();
-  }
+    call_node_method(&self.object, WRAPPER, method, &[message])?;
 
-  /** 
-   * Logs a success message to the console
-  */
-  pub fn success(&self, message: &str) {
-    // Get the node function
-    let func = get_node_func(&self.object, "success");
+    Ok(())
+  }
+}
 
-    // Check if the function is an error
-    if func.is_err() {
-      return println!("Failed to get the success function");
-    }
+/**
+ * A logger with structured fields attached, created by `Logger::fields`.
+*/
+pub struct FieldLogger<'a> {
+  logger: &'a Logger,
+  fields: &'a [(&'a str, &'a dyn Display)],
+}
 
-    // Format the message
-    let message = format!("{}{}{} {}", "[".black(), "Rust".custom_color(CustomColor::new(183, 65, 14)), "]".black(), message);
+impl FieldLogger<'_> {
+  /**
+   * Logs a message with the fields to the console
+  */
+  pub fn log(&self, message: &str) -> Result<(), SerenityError> {
+    self.logger.log(&self.format(message))
+  }
 
-    // Create a string for the message
-    let message = convert_to_js_string(&self.env, &message).unwrap();
+  /**
+   * Logs an information message with the fields to the console
+  */
+  pub fn info(&self, message: &str) -> Result<(), SerenityError> {
+    self.logger.info(&self.format(message))
+  }
 
-    // Unwrap the function
-    let func = func.unwrap();
+  /**
+   * Logs a warning message with the fields to the console
+  */
+  pub fn warn(&self, message: &str) -> Result<(), SerenityError> {
+    self.logger.warn(&self.format(message))
+  }
 
-    // Call the function
-    func.call::<JsString>(Some(&self.object), &[JsString::from(message)]).unwrap();
+  /**
+   * Logs an error message with the fields to the console
+  */
+  pub fn error(&self, message: &str) -> Result<(), SerenityError> {
+    self.logger.error(&self.format(message))
   }
 
-  /** 
-   * Logs a debug message to the console
+  /**
+   * Logs a success message with the fields to the console
   */
-  pub fn debug(&self, message: &str) {
-    // Get the node function
-    let func = get_node_func(&self.object, "debug");
+  pub fn success(&self, message: &str) -> Result<(), SerenityError> {
+    self.logger.success(&self.format(message))
+  }
 
-    // Check if the function is an error
-    if func.is_err() {
-      return println!("Failed to get the debug function");
-    }
+  /**
+   * Logs a debug message with the fields to the console
+  */
+  pub fn debug(&self, message: &str) -> Result<(), SerenityError> {
+    self.logger.debug(&self.format(message))
+  }
 
-    // Format the message
-    let message = format!("{}{}{} {}", "[".black(), "Rust".custom_color(CustomColor::new(183, 65, 14)), "]".black(), message);
+  fn format(&self, message: &str) -> String {
+    let mut formatted = message.to_string();
 
-    // Create a string for the message
-    let message = convert_to_js_string(&self.env, &message).unwrap();
+    for (key, value) in self.fields {
+      let value = value.to_string();
 
-    // Unwrap the function
-    let func = func.unwrap();
+      // Quote values that would otherwise be ambiguous
+      let _ = match value.is_empty() || value.contains(char::is_whitespace) || value.contains(['=', '"']) {
+        true => write!(formatted, " {}={:?}", key, value),
+        false => write!(formatted, " {}={}", key, value)
+      };
+    }
 
-    // Call the function
-    func.call::<JsString>(Some(&self.object), &[JsString::from(message)]).unwrap();
+    formatted
   }
 }
 
-impl FromNapiValue for Logger {
-  unsafe fn from_napi_value(env: napi::sys::napi_env, value: napi::sys::napi_value) -> napi::Result<Self> {
-    // Create the JsObject from the napi_value
-    let object = match JsObject::from_raw(env.clone(), value.clone()) {
-      Ok(obj) => obj,
-      Err(e) => return Err(napi::Error::new(
-        napi::Status::GenericFailure,
-        e.to_string()
-      ))
-    };
-
-    // Return the Logger instance
-    return Ok(Logger::new(env.into(), object));
+fn level_method(level: LogLevel) -> &'static str {
+  match level {
+    LogLevel::Debug => "debug",
+    LogLevel::Info => "info",
+    LogLevel::Warn => "warn",
+    LogLevel::Error => "error",
   }
-}
\ No newline at end of file
+}
@@ -1 +1,5 @@
-pub mod logger;
\ No newline at end of file
+pub mod logger;
+pub mod config;
+pub mod rate_limit;
+#[cfg(any(feature = "log", feature = "tracing"))]
+pub mod bridge;
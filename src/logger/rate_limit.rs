@@ -0,0 +1,89 @@
+use std::collections::HashMap;
+use std::time::Instant;
+
+use super::config::{LogLevel, RateLimit};
+
+/** The number of distinct messages tracked before expired ones are forgotten. */
+const MAX_TRACKED_MESSAGES: usize = 256;
+
+struct MessageWindow {
+  started: Instant,
+  count: u32,
+  suppressed: u32,
+}
+
+/**
+ * Counts identical messages per time window, deciding which ones are written.
+*/
+#[derive(Default)]
+pub struct RateLimiter {
+  windows: HashMap<(LogLevel, String), MessageWindow>,
+}
+
+/**
+ * A message that was suppressed, to be summarized once its window has passed.
+*/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SuppressedSummary {
+  pub level: LogLevel,
+  pub message: String,
+  /** The number of repeats that were not written. */
+  pub suppressed: u32,
+}
+
+impl RateLimiter {
+  /**
+   * Checks if the message should be written, counting it towards its window.
+  */
+  pub fn allow(&mut self, limit: &RateLimit, level: LogLevel, message: &str, now: Instant) -> bool {
+    let key = (level, message.to_string());
+
+    match self.windows.get_mut(&key) {
+      Some(window) if now.duration_since(window.started) < limit.window => {
+        window.count += 1;
+        if window.count > limit.max_repeats {
+          window.suppressed += 1;
+          return false;
+        }
+
+        true
+      },
+      _ => {
+        self.windows.insert(key, MessageWindow { started: now, count: 1, suppressed: 0 });
+
+        true
+      }
+    }
+  }
+
+  /**
+   * Takes the summaries of the windows that have passed, and forgets old messages.
+  */
+  pub fn expire(&mut self, limit: &RateLimit, now: Instant) -> Vec<SuppressedSummary> {
+    let mut summaries = Vec::new();
+
+    for ((level, message), window) in self.windows.iter_mut() {
+      if window.suppressed > 0 && now.duration_since(window.started) >= limit.window {
+        summaries.push(SuppressedSummary { level: *level, message: message.clone(), suppressed: window.suppressed });
+        window.suppressed = 0;
+      }
+    }
+
+    // Forget expired windows once too many messages are tracked
+    if self.windows.len() > MAX_TRACKED_MESSAGES {
+      self.windows.retain(|_, window| window.suppressed > 0 || now.duration_since(window.started) < limit.window);
+    }
+
+    summaries
+  }
+
+  /**
+   * Gets when the first window with suppressed messages passes, if any message is suppressed.
+  */
+  pub fn next_expiry(&self, limit: &RateLimit) -> Option<Instant> {
+    self.windows.values()
+      .filter(|window| window.suppressed > 0)
+      .map(|window| window.started + limit.window)
+      .min()
+  }
+}
@@ -1,54 +1,192 @@
-use napi::bindgen_prelude::FromNapiValue;
-use napi::NapiValue;
+use std::any::Any;
+use std::cell::RefCell;
 
-use crate::utils::node_converter::*;
+use napi::{CallContext, JsUnknown, NapiRaw, ValueType};
+
+use crate::error::error::SerenityError;
+use crate::logger::config::LoggerConfig;
 use crate::logger::logger::Logger;
+use crate::serenity::serenity::Serenity;
+use crate::utils::js_wrapper::JsWrapper;
+use crate::utils::node_converter::*;
+use crate::utils::panic::catch_panic;
+use crate::JsWrapper;
 
+#[derive(JsWrapper)]
 pub struct Plugin {
   pub object: napi::JsObject,
   pub env: napi::Env,
+  #[js(nested)]
   pub logger: Logger,
   pub path: String,
 }
 
-impl Plugin {
-  pub fn new(env: napi::Env, object: napi::JsObject) -> napi::Result<Self> {
-    // Create the logger instance
-    let logger_object = get_node_object(&object, "logger");
-    let logger = match logger_object {
-      Ok(logger) => Logger::new(env.clone(), logger),
-      Err(e) => return Err(napi::Error::new(
-        napi::Status::GenericFailure,
-        e.to_string()
-      ))
-    };
+/**
+ * The lifecycle of a Rust plugin, exported to SerenityJS with `serenity_plugin!`.
+ * The plugin is created with `Default` before the first hook runs, and dropped after `on_shutdown`.
+*/
+pub trait SerenityPlugin: Default + 'static {
+  /**
+   * The configuration of the plugin's loggers, applied before `on_initialize` is called.
+  */
+  fn logger_config(&self) -> LoggerConfig {
+    LoggerConfig::default()
+  }
 
-    // Get the path object
-    let path_object = get_node_string(&object, "path");
-    let path = match path_object {
-      Ok(path) => path.into_utf8().unwrap().into_owned().unwrap(),
-      Err(e) => return Err(napi::Error::new(
-        napi::Status::GenericFailure,
-        e.to_string()
-      ))
-    };
+  /**
+   * Called when SerenityJS initializes the plugin, before the server starts.
+  */
+  fn on_initialize(&mut self, _plugin: &Plugin, _serenity: &Serenity) -> Result<(), SerenityError> {
+    Ok(())
+  }
+
+  /**
+   * Called once the server has started.
+  */
+  fn on_startup(&mut self, _plugin: &Plugin, _serenity: &Serenity) -> Result<(), SerenityError> {
+    Ok(())
+  }
 
-    Ok(Plugin { env, object, logger, path })
+  /**
+   * Called when the server shuts down.
+  */
+  fn on_shutdown(&mut self, _plugin: &Plugin, _serenity: &Serenity) -> Result<(), SerenityError> {
+    Ok(())
   }
 }
 
-impl FromNapiValue for Plugin {
-  unsafe fn from_napi_value(env: napi::sys::napi_env, value: napi::sys::napi_value) -> napi::Result<Self> {
-    // Create the JsObject from the napi_value
-    let object = match napi::JsObject::from_raw(env.clone(), value.clone()) {
-      Ok(obj) => obj,
-      Err(e) => return Err(napi::Error::new(
-        napi::Status::GenericFailure,
-        e.to_string()
-      ))
+/**
+ * Exports the `onInitialize`, `onStartup` and `onShutdown` hooks SerenityJS calls on a native plugin,
+ * and forwards them to the given `SerenityPlugin` type.
+ *
+ * ```ignore
+ * #[derive(Default)]
+ * struct MyPlugin;
+ *
+ * impl SerenityPlugin for MyPlugin {}
+ *
+ * serenityrs::serenity_plugin!(MyPlugin);
+ * ```
+*/
+#[macro_export]
+macro_rules! serenity_plugin {
+  ($plugin:ty) => {
+    const _: () = {
+      #[$crate::__private::napi::bindgen_prelude::ctor]
+      fn __serenityrs_register_plugin() {
+        $crate::plugin::register_plugin::<$plugin>();
+      }
     };
+  };
+}
 
-    // Return the Plugin instance
-    Ok(Plugin::new(env.into(), object)?)
+#[derive(Clone, Copy)]
+enum LifecycleHook {
+  Initialize,
+  Startup,
+  Shutdown,
+}
+
+impl LifecycleHook {
+  fn js_name(self) -> &'static str {
+    match self {
+      LifecycleHook::Initialize => "onInitialize",
+      LifecycleHook::Startup => "onStartup",
+      LifecycleHook::Shutdown => "onShutdown",
+    }
   }
-}
\ No newline at end of file
+}
+
+thread_local! {
+  // The plugin instance, only ever touched from the JS thread
+  static PLUGIN: RefCell<Option<Box<dyn Any>>> = RefCell::new(None);
+}
+
+/**
+ * Registers the lifecycle hooks of the plugin as module exports.
+ * Used by `serenity_plugin!`, which calls it when the native module is loaded.
+*/
+#[doc(hidden)]
+pub fn register_plugin<P: SerenityPlugin>() {
+  use napi::bindgen_prelude::register_module_export;
+
+  register_module_export(None, "onInitialize\0", export_on_initialize::<P>);
+  register_module_export(None, "onStartup\0", export_on_startup::<P>);
+  register_module_export(None, "onShutdown\0", export_on_shutdown::<P>);
+}
+
+unsafe fn export_on_initialize<P: SerenityPlugin>(env: napi::sys::napi_env) -> napi::Result<napi::sys::napi_value> {
+  create_hook_function::<P>(env, LifecycleHook::Initialize)
+}
+
+unsafe fn export_on_startup<P: SerenityPlugin>(env: napi::sys::napi_env) -> napi::Result<napi::sys::napi_value> {
+  create_hook_function::<P>(env, LifecycleHook::Startup)
+}
+
+unsafe fn export_on_shutdown<P: SerenityPlugin>(env: napi::sys::napi_env) -> napi::Result<napi::sys::napi_value> {
+  create_hook_function::<P>(env, LifecycleHook::Shutdown)
+}
+
+unsafe fn create_hook_function<P: SerenityPlugin>(env: napi::sys::napi_env, hook: LifecycleHook) -> napi::Result<napi::sys::napi_value> {
+  let env = napi::Env::from_raw(env);
+
+  // Create the JS function SerenityJS calls for the hook
+  let function = env.create_function_from_closure(hook.js_name(), move |ctx: CallContext| -> napi::Result<()> {
+    let (plugin, serenity) = hook_arguments(&ctx)?;
+
+    Ok(run_hook::<P>(hook, &plugin, &serenity)?)
+  })?;
+
+  Ok(function.raw())
+}
+
+fn hook_arguments(ctx: &CallContext) -> Result<(Plugin, Serenity), SerenityError> {
+  // The plugin is always the first argument
+  let plugin_object = ctx.get::<JsUnknown>(0)
+    .map_err(|e| SerenityError::NapiFailure { wrapper: Plugin::WRAPPER, message: e.to_string() })?;
+  let plugin = Plugin::new(*ctx.env, node_value_to_object(plugin_object, Plugin::WRAPPER, "plugin")?)?;
+
+  // The serenity instance is either passed along, or read from the plugin
+  let serenity_object = match ctx.length > 1 {
+    true => ctx.get::<JsUnknown>(1).ok().filter(|value| matches!(value.get_type(), Ok(ValueType::Object))),
+    false => None
+  };
+  let serenity_object = match serenity_object {
+    Some(value) => node_value_to_object(value, Plugin::WRAPPER, "serenity")?,
+    None => get_node_object(&plugin.object, Plugin::WRAPPER, "serenity")?
+  };
+  let serenity = Serenity::new(*ctx.env, serenity_object)?;
+
+  Ok((plugin, serenity))
+}
+
+fn run_hook<P: SerenityPlugin>(hook: LifecycleHook, plugin: &Plugin, serenity: &Serenity) -> Result<(), SerenityError> {
+  // Take the plugin out of its slot, so a hook can never observe it borrowed
+  let mut instance = PLUGIN.with(|slot| slot.borrow_mut().take())
+    .and_then(|instance| instance.downcast::<P>().ok())
+    .unwrap_or_default();
+
+  // Catch panics, so they never unwind into SerenityJS
+  let result = catch_panic(Plugin::WRAPPER, || match hook {
+    LifecycleHook::Initialize => {
+      Logger::set_config(instance.logger_config());
+      instance.on_initialize(plugin, serenity)
+    },
+    LifecycleHook::Startup => instance.on_startup(plugin, serenity),
+    LifecycleHook::Shutdown => instance.on_shutdown(plugin, serenity),
+  });
+
+  if let Err(SerenityError::Panic { message, backtrace, .. }) = &result {
+    let report = format!("Plugin hook {} panicked: {}\n{}", hook.js_name(), message, backtrace);
+    if plugin.logger.error(&report).is_err() {
+      eprintln!("{}", report);
+    }
+  }
+
+  // Keep the plugin around until the server shuts down
+  if !matches!(hook, LifecycleHook::Shutdown) {
+    PLUGIN.with(|slot| *slot.borrow_mut() = Some(instance));
+  }
+
+  result
+}
@@ -0,0 +1,225 @@
+use uuid::Uuid;
+
+use crate::protocol::types::block_position::BlockPosition;
+use crate::protocol::types::vector3f::Vector3f;
+
+use super::error::BinaryError;
+use super::stream::{BinaryStream, Endianness};
+
+/**
+ * A value that can be written to and read from a `BinaryStream`.
+ * Usually derived with `#[derive(BinaryCodec)]`, which encodes the fields in declaration order.
+ *
+ * Fixed width numbers are little-endian, strings are VarInt length prefixed,
+ * `Vec<T>` is prefixed with its length as an unsigned VarInt, and `Option<T>` with a bool.
+ * Fields using another encoding are marked with the module of that encoding, e.g. `#[codec(var_int)]`.
+*/
+pub trait BinaryCodec: Sized {
+  fn encode(&self, stream: &mut BinaryStream) -> Result<(), BinaryError>;
+
+  fn decode(stream: &mut BinaryStream) -> Result<Self, BinaryError>;
+}
+
+impl BinaryCodec for u8 {
+  fn encode(&self, stream: &mut BinaryStream) -> Result<(), BinaryError> {
+    stream.write_u8(*self);
+
+    Ok(())
+  }
+
+  fn decode(stream: &mut BinaryStream) -> Result<Self, BinaryError> {
+    stream.read_u8()
+  }
+}
+
+impl BinaryCodec for i8 {
+  fn encode(&self, stream: &mut BinaryStream) -> Result<(), BinaryError> {
+    stream.write_i8(*self);
+
+    Ok(())
+  }
+
+  fn decode(stream: &mut BinaryStream) -> Result<Self, BinaryError> {
+    stream.read_i8()
+  }
+}
+
+impl BinaryCodec for bool {
+  fn encode(&self, stream: &mut BinaryStream) -> Result<(), BinaryError> {
+    stream.write_bool(*self);
+
+    Ok(())
+  }
+
+  fn decode(stream: &mut BinaryStream) -> Result<Self, BinaryError> {
+    stream.read_bool()
+  }
+}
+
+macro_rules! little_endian {
+  ($($type:ty => $read:ident, $write:ident;)*) => {
+    $(
+      impl BinaryCodec for $type {
+        fn encode(&self, stream: &mut BinaryStream) -> Result<(), BinaryError> {
+          stream.$write(*self, Endianness::Little);
+
+          Ok(())
+        }
+
+        fn decode(stream: &mut BinaryStream) -> Result<Self, BinaryError> {
+          stream.$read(Endianness::Little)
+        }
+      }
+    )*
+  };
+}
+
+little_endian! {
+  u16 => read_u16, write_u16;
+  i16 => read_i16, write_i16;
+  u32 => read_u32, write_u32;
+  i32 => read_i32, write_i32;
+  u64 => read_u64, write_u64;
+  i64 => read_i64, write_i64;
+  f32 => read_f32, write_f32;
+  f64 => read_f64, write_f64;
+}
+
+impl BinaryCodec for String {
+  fn encode(&self, stream: &mut BinaryStream) -> Result<(), BinaryError> {
+    stream.write_var_string(self)
+  }
+
+  fn decode(stream: &mut BinaryStream) -> Result<Self, BinaryError> {
+    stream.read_var_string()
+  }
+}
+
+impl BinaryCodec for Uuid {
+  fn encode(&self, stream: &mut BinaryStream) -> Result<(), BinaryError> {
+    stream.write_uuid(self);
+
+    Ok(())
+  }
+
+  fn decode(stream: &mut BinaryStream) -> Result<Self, BinaryError> {
+    stream.read_uuid()
+  }
+}
+
+impl BinaryCodec for Vector3f {
+  fn encode(&self, stream: &mut BinaryStream) -> Result<(), BinaryError> {
+    stream.write_vector3f(self);
+
+    Ok(())
+  }
+
+  fn decode(stream: &mut BinaryStream) -> Result<Self, BinaryError> {
+    stream.read_vector3f()
+  }
+}
+
+impl BinaryCodec for BlockPosition {
+  fn encode(&self, stream: &mut BinaryStream) -> Result<(), BinaryError> {
+    stream.write_block_position(self);
+
+    Ok(())
+  }
+
+  fn decode(stream: &mut BinaryStream) -> Result<Self, BinaryError> {
+    stream.read_block_position()
+  }
+}
+
+impl<T: BinaryCodec> BinaryCodec for Vec<T> {
+  fn encode(&self, stream: &mut BinaryStream) -> Result<(), BinaryError> {
+    stream.write_var_u32(self.len() as u32);
+    for value in self {
+      value.encode(stream)?;
+    }
+
+    Ok(())
+  }
+
+  fn decode(stream: &mut BinaryStream) -> Result<Self, BinaryError> {
+    let length = stream.read_var_u32()? as usize;
+
+    // Every value takes at least a byte, so a bad length cannot allocate more than the stream holds
+    let mut values = Vec::with_capacity(length.min(stream.remaining().len()));
+    for _ in 0..length {
+      values.push(T::decode(stream)?);
+    }
+
+    Ok(values)
+  }
+}
+
+impl<T: BinaryCodec> BinaryCodec for Option<T> {
+  fn encode(&self, stream: &mut BinaryStream) -> Result<(), BinaryError> {
+    stream.write_bool(self.is_some());
+    if let Some(value) = self {
+      value.encode(stream)?;
+    }
+
+    Ok(())
+  }
+
+  fn decode(stream: &mut BinaryStream) -> Result<Self, BinaryError> {
+    match stream.read_bool()? {
+      true => Ok(Some(T::decode(stream)?)),
+      false => Ok(None)
+    }
+  }
+}
+
+macro_rules! encoding {
+  ($($(#[$meta:meta])* $name:ident($type:ty) => $read:ident, $write:ident;)*) => {
+    $(
+      $(#[$meta])*
+      pub mod $name {
+        use super::*;
+
+        pub fn encode(value: &$type, stream: &mut BinaryStream) -> Result<(), BinaryError> {
+          stream.$write(*value);
+
+          Ok(())
+        }
+
+        pub fn decode(stream: &mut BinaryStream) -> Result<$type, BinaryError> {
+          stream.$read()
+        }
+      }
+    )*
+  };
+}
+
+encoding! {
+  /** Encodes an `i32` as a zigzag VarInt, used with `#[codec(var_int)]`. */
+  var_int(i32) => read_var_int, write_var_int;
+
+  /** Encodes an `u32` as an unsigned VarInt, used with `#[codec(var_u32)]`. */
+  var_u32(u32) => read_var_u32, write_var_u32;
+
+  /** Encodes an `i64` as a zigzag VarLong, used with `#[codec(var_long)]`. */
+  var_long(i64) => read_var_long, write_var_long;
+
+  /** Encodes an `u64` as an unsigned VarLong, used with `#[codec(var_u64)]`. */
+  var_u64(u64) => read_var_u64, write_var_u64;
+}
+
+/**
+ * Encodes a `BlockPosition` with an unsigned y, used with `#[codec(network_block_position)]`.
+*/
+pub mod network_block_position {
+  use super::*;
+
+  pub fn encode(value: &BlockPosition, stream: &mut BinaryStream) -> Result<(), BinaryError> {
+    stream.write_network_block_position(value);
+
+    Ok(())
+  }
+
+  pub fn decode(stream: &mut BinaryStream) -> Result<BlockPosition, BinaryError> {
+    stream.read_network_block_position()
+  }
+}
@@ -0,0 +1,54 @@
+use std::fmt;
+
+use crate::protocol::nbt::error::NbtError;
+
+/**
+ * An error raised while reading from or writing to a `BinaryStream`.
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub enum BinaryError {
+  /** A read needed more bytes than are left in the stream. */
+  UnexpectedEof { offset: usize, needed: usize, remaining: usize },
+
+  /** A VarInt or VarLong is longer, or holds more bits, than its type allows. */
+  VarIntTooLong { offset: usize },
+
+  /** A string is not valid UTF-8. */
+  InvalidString { offset: usize },
+
+  /** A string is too long for its length prefix to be written. */
+  StringTooLong { length: usize, max: usize },
+
+  /** A value that does not map to any variant of the enum. */
+  UnknownVariant { name: &'static str, value: i64 },
+
+  /** The packet id does not match the packet being decoded. */
+  UnexpectedPacket { expected: u32, found: u32 },
+
+  /** NBT could not be read from or written to the stream. */
+  Nbt(NbtError),
+}
+
+impl fmt::Display for BinaryError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      BinaryError::UnexpectedEof { offset, needed, remaining } => {
+        write!(f, "binary: needed {} bytes at offset {}, but only {} remain", needed, offset, remaining)
+      },
+      BinaryError::VarIntTooLong { offset } => write!(f, "binary: varint at offset {} is too long", offset),
+      BinaryError::InvalidString { offset } => write!(f, "binary: string at offset {} is not valid utf-8", offset),
+      BinaryError::StringTooLong { length, max } => write!(f, "binary: string of {} bytes is longer than the maximum of {}", length, max),
+      BinaryError::UnknownVariant { name, value } => write!(f, "binary: {} has no variant {}", name, value),
+      BinaryError::UnexpectedPacket { expected, found } => write!(f, "binary: expected packet {}, found packet {}", expected, found),
+      BinaryError::Nbt(e) => e.fmt(f),
+    }
+  }
+}
+
+impl std::error::Error for BinaryError {}
+
+impl From<NbtError> for BinaryError {
+  fn from(e: NbtError) -> Self {
+    BinaryError::Nbt(e)
+  }
+}
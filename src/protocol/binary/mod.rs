@@ -0,0 +1,3 @@
+pub mod codec;
+pub mod error;
+pub mod stream;
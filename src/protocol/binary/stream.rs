@@ -0,0 +1,464 @@
+use uuid::Uuid;
+
+use crate::protocol::nbt::codec::NbtEncoding;
+use crate::protocol::nbt::tag::NamedTag;
+use crate::protocol::types::block_position::BlockPosition;
+use crate::protocol::types::vector3f::Vector3f;
+
+use super::error::BinaryError;
+
+/**
+ * The byte order of a fixed width number.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Endianness {
+  Big,
+  Little,
+}
+
+/**
+ * A buffer read from and written to in the encodings Bedrock uses on the wire.
+ * Reads start at the offset and advance it, a read that fails leaves the offset where it started.
+ * Writes always append to the end of the buffer.
+ *
+ * ```ignore
+ * let mut stream = BinaryStream::new();
+ * stream.write_var_int(300);
+ * stream.write_var_string("hello")?;
+ *
+ * let mut stream = BinaryStream::from_bytes(stream.into_bytes());
+ * assert_eq!(stream.read_var_int()?, 300);
+ * ```
+*/
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BinaryStream {
+  buffer: Vec<u8>,
+  offset: usize,
+}
+
+macro_rules! fixed_width {
+  ($($type:ty => $read:ident, $write:ident;)*) => {
+    $(
+      pub fn $read(&mut self, endianness: Endianness) -> Result<$type, BinaryError> {
+        let bytes = self.read_array()?;
+
+        Ok(match endianness {
+          Endianness::Big => <$type>::from_be_bytes(bytes),
+          Endianness::Little => <$type>::from_le_bytes(bytes),
+        })
+      }
+
+      pub fn $write(&mut self, value: $type, endianness: Endianness) {
+        match endianness {
+          Endianness::Big => self.buffer.extend_from_slice(&value.to_be_bytes()),
+          Endianness::Little => self.buffer.extend_from_slice(&value.to_le_bytes()),
+        }
+      }
+    )*
+  };
+}
+
+impl BinaryStream {
+  pub fn new() -> Self {
+    BinaryStream::default()
+  }
+
+  /**
+   * Creates a stream reading the bytes from the start.
+  */
+  pub fn from_bytes(bytes: impl Into<Vec<u8>>) -> Self {
+    BinaryStream { buffer: bytes.into(), offset: 0 }
+  }
+
+  pub fn as_bytes(&self) -> &[u8] {
+    &self.buffer
+  }
+
+  pub fn into_bytes(self) -> Vec<u8> {
+    self.buffer
+  }
+
+  /**
+   * Gets the offset the next read starts at.
+  */
+  pub fn offset(&self) -> usize {
+    self.offset
+  }
+
+  /**
+   * Moves the read offset, clamped to the end of the buffer.
+  */
+  pub fn set_offset(&mut self, offset: usize) {
+    self.offset = offset.min(self.buffer.len());
+  }
+
+  /**
+   * Gets the bytes that have not been read yet.
+  */
+  pub fn remaining(&self) -> &[u8] {
+    &self.buffer[self.offset..]
+  }
+
+  pub fn is_eof(&self) -> bool {
+    self.offset >= self.buffer.len()
+  }
+
+  /**
+   * Reads the next bytes, failing without advancing if fewer are left.
+  */
+  pub fn read_bytes(&mut self, length: usize) -> Result<&[u8], BinaryError> {
+    let remaining = self.buffer.len() - self.offset;
+    if length > remaining {
+      return Err(BinaryError::UnexpectedEof { offset: self.offset, needed: length, remaining });
+    }
+
+    let start = self.offset;
+    self.offset += length;
+
+    Ok(&self.buffer[start..self.offset])
+  }
+
+  /**
+   * Reads every byte left in the stream.
+  */
+  pub fn read_remaining(&mut self) -> &[u8] {
+    let start = self.offset;
+    self.offset = self.buffer.len();
+
+    &self.buffer[start..]
+  }
+
+  pub fn write_bytes(&mut self, bytes: &[u8]) {
+    self.buffer.extend_from_slice(bytes);
+  }
+
+  /**
+   * Runs the reads, moving the offset back to where they started if any of them fails.
+  */
+  fn rewind_on_error<T, F>(&mut self, read: F) -> Result<T, BinaryError>
+  where
+    F: FnOnce(&mut Self) -> Result<T, BinaryError>
+  {
+    let offset = self.offset;
+    let result = read(self);
+    if result.is_err() {
+      self.offset = offset;
+    }
+
+    result
+  }
+
+  fn read_array<const N: usize>(&mut self) -> Result<[u8; N], BinaryError> {
+    Ok(self.read_bytes(N)?.try_into().unwrap())
+  }
+
+  pub fn read_u8(&mut self) -> Result<u8, BinaryError> {
+    Ok(self.read_array::<1>()?[0])
+  }
+
+  pub fn write_u8(&mut self, value: u8) {
+    self.buffer.push(value);
+  }
+
+  pub fn read_i8(&mut self) -> Result<i8, BinaryError> {
+    Ok(self.read_u8()? as i8)
+  }
+
+  pub fn write_i8(&mut self, value: i8) {
+    self.write_u8(value as u8);
+  }
+
+  pub fn read_bool(&mut self) -> Result<bool, BinaryError> {
+    Ok(self.read_u8()? != 0)
+  }
+
+  pub fn write_bool(&mut self, value: bool) {
+    self.write_u8(value as u8);
+  }
+
+  fixed_width! {
+    u16 => read_u16, write_u16;
+    i16 => read_i16, write_i16;
+    u32 => read_u32, write_u32;
+    i32 => read_i32, write_i32;
+    u64 => read_u64, write_u64;
+    i64 => read_i64, write_i64;
+    f32 => read_f32, write_f32;
+    f64 => read_f64, write_f64;
+  }
+
+  /**
+   * Reads a VarInt of at most the bits, failing on a longer one or one with bits set above them.
+  */
+  fn read_var(&mut self, bits: u32) -> Result<u64, BinaryError> {
+    let max_bytes = bits.div_ceil(7);
+
+    self.rewind_on_error(|stream| {
+      let offset = stream.offset;
+
+      let mut value = 0u64;
+      for index in 0..max_bytes {
+        let byte = stream.read_u8()?;
+        let shift = index * 7;
+
+        // The last byte may only hold the bits that are left, e.g. 4 for the 5th byte of a VarInt
+        if index == max_bytes - 1 && (byte as u32) >> (bits - shift) != 0 {
+          return Err(BinaryError::VarIntTooLong { offset });
+        }
+
+        value |= ((byte & 0x7f) as u64) << shift;
+
+        if byte & 0x80 == 0 {
+          return Ok(value);
+        }
+      }
+
+      Err(BinaryError::VarIntTooLong { offset })
+    })
+  }
+
+  fn write_var(&mut self, mut value: u64) {
+    while value >= 0x80 {
+      self.buffer.push(value as u8 | 0x80);
+      value >>= 7;
+    }
+
+    self.buffer.push(value as u8);
+  }
+
+  /**
+   * Reads an unsigned VarInt, 7 bits per byte with the high bit marking that more follow.
+  */
+  pub fn read_var_u32(&mut self) -> Result<u32, BinaryError> {
+    Ok(self.read_var(32)? as u32)
+  }
+
+  pub fn write_var_u32(&mut self, value: u32) {
+    self.write_var(value as u64);
+  }
+
+  /**
+   * Reads a zigzag encoded VarInt, which keeps small negative numbers short.
+  */
+  pub fn read_var_int(&mut self) -> Result<i32, BinaryError> {
+    let value = self.read_var_u32()?;
+
+    Ok((value >> 1) as i32 ^ -((value & 1) as i32))
+  }
+
+  pub fn write_var_int(&mut self, value: i32) {
+    self.write_var_u32(((value << 1) ^ (value >> 31)) as u32);
+  }
+
+  /**
+   * Reads an unsigned VarLong.
+  */
+  pub fn read_var_u64(&mut self) -> Result<u64, BinaryError> {
+    self.read_var(64)
+  }
+
+  pub fn write_var_u64(&mut self, value: u64) {
+    self.write_var(value);
+  }
+
+  /**
+   * Reads a zigzag encoded VarLong.
+  */
+  pub fn read_var_long(&mut self) -> Result<i64, BinaryError> {
+    let value = self.read_var_u64()?;
+
+    Ok((value >> 1) as i64 ^ -((value & 1) as i64))
+  }
+
+  pub fn write_var_long(&mut self, value: i64) {
+    self.write_var_u64(((value << 1) ^ (value >> 63)) as u64);
+  }
+
+  fn read_string(&mut self, length: usize) -> Result<String, BinaryError> {
+    let offset = self.offset;
+    let bytes = self.read_bytes(length)?;
+
+    String::from_utf8(bytes.to_vec()).map_err(|_| BinaryError::InvalidString { offset })
+  }
+
+  /**
+   * Reads a string prefixed with its length as an unsigned VarInt, the usual string in packets.
+  */
+  pub fn read_var_string(&mut self) -> Result<String, BinaryError> {
+    self.rewind_on_error(|stream| {
+      let length = stream.read_var_u32()? as usize;
+
+      stream.read_string(length)
+    })
+  }
+
+  /**
+   * Writes the string prefixed with its length, failing if it is longer than `u32::MAX` bytes.
+  */
+  pub fn write_var_string(&mut self, value: &str) -> Result<(), BinaryError> {
+    let length = u32::try_from(value.len())
+      .map_err(|_| BinaryError::StringTooLong { length: value.len(), max: u32::MAX as usize })?;
+
+    self.write_var_u32(length);
+    self.write_bytes(value.as_bytes());
+
+    Ok(())
+  }
+
+  /**
+   * Reads a string prefixed with its length as an u16.
+  */
+  pub fn read_string16(&mut self, endianness: Endianness) -> Result<String, BinaryError> {
+    self.rewind_on_error(|stream| {
+      let length = stream.read_u16(endianness)? as usize;
+
+      stream.read_string(length)
+    })
+  }
+
+  /**
+   * Writes the string prefixed with its length, failing if it is longer than `u16::MAX` bytes.
+  */
+  pub fn write_string16(&mut self, value: &str, endianness: Endianness) -> Result<(), BinaryError> {
+    let length = u16::try_from(value.len())
+      .map_err(|_| BinaryError::StringTooLong { length: value.len(), max: u16::MAX as usize })?;
+
+    self.write_u16(length, endianness);
+    self.write_bytes(value.as_bytes());
+
+    Ok(())
+  }
+
+  /**
+   * Reads a string prefixed with its length as an u32.
+  */
+  pub fn read_string32(&mut self, endianness: Endianness) -> Result<String, BinaryError> {
+    self.rewind_on_error(|stream| {
+      let length = stream.read_u32(endianness)? as usize;
+
+      stream.read_string(length)
+    })
+  }
+
+  /**
+   * Writes the string prefixed with its length, failing if it is longer than `u32::MAX` bytes.
+  */
+  pub fn write_string32(&mut self, value: &str, endianness: Endianness) -> Result<(), BinaryError> {
+    let length = u32::try_from(value.len())
+      .map_err(|_| BinaryError::StringTooLong { length: value.len(), max: u32::MAX as usize })?;
+
+    self.write_u32(length, endianness);
+    self.write_bytes(value.as_bytes());
+
+    Ok(())
+  }
+
+  /**
+   * Reads a UUID, written as its two halves in little-endian with the most significant half first.
+  */
+  pub fn read_uuid(&mut self) -> Result<Uuid, BinaryError> {
+    self.rewind_on_error(|stream| {
+      let most = stream.read_u64(Endianness::Little)?;
+      let least = stream.read_u64(Endianness::Little)?;
+
+      Ok(Uuid::from_u64_pair(most, least))
+    })
+  }
+
+  pub fn write_uuid(&mut self, value: &Uuid) {
+    let (most, least) = value.as_u64_pair();
+
+    self.write_u64(most, Endianness::Little);
+    self.write_u64(least, Endianness::Little);
+  }
+
+  /**
+   * Reads a vector of three little-endian f32s.
+  */
+  pub fn read_vector3f(&mut self) -> Result<Vector3f, BinaryError> {
+    self.rewind_on_error(|stream| {
+      let x = stream.read_f32(Endianness::Little)?;
+      let y = stream.read_f32(Endianness::Little)?;
+      let z = stream.read_f32(Endianness::Little)?;
+
+      Ok(Vector3f { x: x as f64, y: y as f64, z: z as f64 })
+    })
+  }
+
+  /**
+   * Writes the vector, its components are narrowed to f32.
+  */
+  pub fn write_vector3f(&mut self, value: &Vector3f) {
+    self.write_f32(value.x as f32, Endianness::Little);
+    self.write_f32(value.y as f32, Endianness::Little);
+    self.write_f32(value.z as f32, Endianness::Little);
+  }
+
+  /**
+   * Reads a block position of three zigzag VarInts.
+  */
+  pub fn read_block_position(&mut self) -> Result<BlockPosition, BinaryError> {
+    self.rewind_on_error(|stream| {
+      let x = stream.read_var_int()?;
+      let y = stream.read_var_int()?;
+      let z = stream.read_var_int()?;
+
+      Ok(BlockPosition { x, y, z })
+    })
+  }
+
+  pub fn write_block_position(&mut self, value: &BlockPosition) {
+    self.write_var_int(value.x);
+    self.write_var_int(value.y);
+    self.write_var_int(value.z);
+  }
+
+  /**
+   * Reads a block position whose y is an unsigned VarInt, as used by most block packets.
+  */
+  pub fn read_network_block_position(&mut self) -> Result<BlockPosition, BinaryError> {
+    self.rewind_on_error(|stream| {
+      let x = stream.read_var_int()?;
+      let y = stream.read_var_u32()? as i32;
+      let z = stream.read_var_int()?;
+
+      Ok(BlockPosition { x, y, z })
+    })
+  }
+
+  pub fn write_network_block_position(&mut self, value: &BlockPosition) {
+    self.write_var_int(value.x);
+    self.write_var_u32(value.y as u32);
+    self.write_var_int(value.z);
+  }
+
+  /**
+   * Reads a root NBT tag in the encoding.
+  */
+  pub fn read_nbt(&mut self, encoding: NbtEncoding) -> Result<NamedTag, BinaryError> {
+    let mut remaining = self.remaining();
+    let length = remaining.len();
+
+    let named = NamedTag::read(&mut remaining, encoding)?;
+    self.offset += length - remaining.len();
+
+    Ok(named)
+  }
+
+  /**
+   * Writes the root tag in the encoding, failing when a string or a list is too long to be written.
+  */
+  pub fn write_nbt(&mut self, value: &NamedTag, encoding: NbtEncoding) -> Result<(), BinaryError> {
+    // Write into a separate buffer, so a failed write leaves the stream unchanged
+    let bytes = value.to_bytes(encoding)?;
+    self.buffer.extend_from_slice(&bytes);
+
+    Ok(())
+  }
+}
+
+impl From<Vec<u8>> for BinaryStream {
+  fn from(bytes: Vec<u8>) -> Self {
+    BinaryStream::from_bytes(bytes)
+  }
+}
@@ -1 +1,4 @@
-pub mod types;
\ No newline at end of file
+pub mod binary;
+pub mod nbt;
+pub mod packets;
+pub mod types;
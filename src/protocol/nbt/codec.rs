@@ -0,0 +1,338 @@
+use super::error::NbtError;
+use super::tag::{Compound, List, NamedTag, Tag, TagType};
+
+/**
+ * How deep compounds and lists may be nested before reading fails.
+*/
+pub const MAX_DEPTH: usize = 512;
+
+/**
+ * The two ways Bedrock writes NBT.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NbtEncoding {
+  /** Fixed width little-endian values, used by level.dat and structure files. */
+  LittleEndian,
+
+  /**
+   * Used in packets, ints, longs and lengths are written as zigzag VarInts,
+   * and string lengths as unsigned VarInts.
+  */
+  Network,
+}
+
+impl NamedTag {
+  /**
+   * Reads a root tag from the start of the bytes, advancing them past it.
+  */
+  pub fn read(bytes: &mut &[u8], encoding: NbtEncoding) -> Result<NamedTag, NbtError> {
+    let mut reader = Reader { bytes, encoding };
+
+    // Get the type and name of the root tag
+    let tag_type = TagType::from_id(reader.u8()?)?;
+    let name = reader.string()?;
+    let tag = reader.payload(tag_type, 0)?;
+
+    Ok(NamedTag { name, tag })
+  }
+
+  /**
+   * Reads the root tag the bytes consist of, failing if any bytes follow it.
+  */
+  pub fn from_bytes(mut bytes: &[u8], encoding: NbtEncoding) -> Result<NamedTag, NbtError> {
+    let named = NamedTag::read(&mut bytes, encoding)?;
+
+    match bytes.is_empty() {
+      true => Ok(named),
+      false => Err(NbtError::TrailingBytes(bytes.len()))
+    }
+  }
+
+  /**
+   * Writes the root tag to the end of the buffer.
+   * Fails when a string or a list is too long for its length prefix, the buffer may then hold part of the tag.
+  */
+  pub fn write(&self, buffer: &mut Vec<u8>, encoding: NbtEncoding) -> Result<(), NbtError> {
+    let mut writer = Writer { buffer, encoding };
+
+    writer.u8(self.tag.tag_type().id());
+    writer.string(&self.name)?;
+    writer.payload(&self.tag)
+  }
+
+  pub fn to_bytes(&self, encoding: NbtEncoding) -> Result<Vec<u8>, NbtError> {
+    let mut buffer = Vec::new();
+    self.write(&mut buffer, encoding)?;
+
+    Ok(buffer)
+  }
+}
+
+struct Reader<'a, 'b> {
+  bytes: &'a mut &'b [u8],
+  encoding: NbtEncoding,
+}
+
+impl Reader<'_, '_> {
+  fn take<const N: usize>(&mut self) -> Result<[u8; N], NbtError> {
+    if self.bytes.len() < N {
+      return Err(NbtError::UnexpectedEof);
+    }
+
+    let (head, tail) = self.bytes.split_at(N);
+    *self.bytes = tail;
+
+    Ok(head.try_into().unwrap())
+  }
+
+  fn u8(&mut self) -> Result<u8, NbtError> {
+    Ok(self.take::<1>()?[0])
+  }
+
+  fn var_u64(&mut self, max_bytes: u32) -> Result<u64, NbtError> {
+    let mut value = 0u64;
+    for index in 0..max_bytes {
+      let byte = self.u8()?;
+      value |= ((byte & 0x7f) as u64) << (index * 7);
+
+      if byte & 0x80 == 0 {
+        return Ok(value);
+      }
+    }
+
+    Err(NbtError::VarIntTooLong)
+  }
+
+  fn zigzag_i32(&mut self) -> Result<i32, NbtError> {
+    let value = self.var_u64(5)? as u32;
+
+    Ok((value >> 1) as i32 ^ -((value & 1) as i32))
+  }
+
+  fn zigzag_i64(&mut self) -> Result<i64, NbtError> {
+    let value = self.var_u64(10)?;
+
+    Ok((value >> 1) as i64 ^ -((value & 1) as i64))
+  }
+
+  fn i16(&mut self) -> Result<i16, NbtError> {
+    Ok(i16::from_le_bytes(self.take()?))
+  }
+
+  fn i32(&mut self) -> Result<i32, NbtError> {
+    match self.encoding {
+      NbtEncoding::LittleEndian => Ok(i32::from_le_bytes(self.take()?)),
+      NbtEncoding::Network => self.zigzag_i32()
+    }
+  }
+
+  fn i64(&mut self) -> Result<i64, NbtError> {
+    match self.encoding {
+      NbtEncoding::LittleEndian => Ok(i64::from_le_bytes(self.take()?)),
+      NbtEncoding::Network => self.zigzag_i64()
+    }
+  }
+
+  fn length(&mut self) -> Result<usize, NbtError> {
+    let length = self.i32()?;
+    if length < 0 {
+      return Err(NbtError::NegativeLength(length));
+    }
+
+    // A length can never be larger than the data left, which keeps bad input from allocating
+    match (length as usize) <= self.bytes.len() {
+      true => Ok(length as usize),
+      false => Err(NbtError::UnexpectedEof)
+    }
+  }
+
+  fn string(&mut self) -> Result<String, NbtError> {
+    let length = match self.encoding {
+      NbtEncoding::LittleEndian => u16::from_le_bytes(self.take()?) as usize,
+      NbtEncoding::Network => self.var_u64(5)? as u32 as usize
+    };
+
+    if self.bytes.len() < length {
+      return Err(NbtError::UnexpectedEof);
+    }
+
+    let (head, tail) = self.bytes.split_at(length);
+    *self.bytes = tail;
+
+    String::from_utf8(head.to_vec()).map_err(|_| NbtError::InvalidString)
+  }
+
+  fn payload(&mut self, tag_type: TagType, depth: usize) -> Result<Tag, NbtError> {
+    if depth > MAX_DEPTH {
+      return Err(NbtError::DepthLimit);
+    }
+
+    let tag = match tag_type {
+      TagType::End => return Err(NbtError::InvalidTagType(0)),
+      TagType::Byte => Tag::Byte(self.u8()? as i8),
+      TagType::Short => Tag::Short(self.i16()?),
+      TagType::Int => Tag::Int(self.i32()?),
+      TagType::Long => Tag::Long(self.i64()?),
+      TagType::Float => Tag::Float(f32::from_le_bytes(self.take()?)),
+      TagType::Double => Tag::Double(f64::from_le_bytes(self.take()?)),
+      TagType::ByteArray => {
+        let length = self.length()?;
+        let bytes = (0..length).map(|_| self.u8().map(|byte| byte as i8)).collect::<Result<_, _>>()?;
+
+        Tag::ByteArray(bytes)
+      },
+      TagType::String => Tag::String(self.string()?),
+      TagType::List => Tag::List(self.list(depth)?),
+      TagType::Compound => Tag::Compound(self.compound(depth)?),
+      TagType::IntArray => {
+        let length = self.length()?;
+
+        Tag::IntArray((0..length).map(|_| self.i32()).collect::<Result<_, _>>()?)
+      },
+      TagType::LongArray => {
+        let length = self.length()?;
+
+        Tag::LongArray((0..length).map(|_| self.i64()).collect::<Result<_, _>>()?)
+      },
+    };
+
+    Ok(tag)
+  }
+
+  // Lists and compounds are read apart from `payload`, which keeps its frame small for deeply nested tags
+  fn list(&mut self, depth: usize) -> Result<List, NbtError> {
+    // Get the element type and length of the list
+    let element_type = TagType::from_id(self.u8()?)?;
+    let length = self.length()?;
+
+    let mut list = List::empty(element_type);
+    for _ in 0..length {
+      list.push(self.payload(element_type, depth + 1)?)?;
+    }
+
+    Ok(list)
+  }
+
+  fn compound(&mut self, depth: usize) -> Result<Compound, NbtError> {
+    let mut compound = Compound::new();
+    loop {
+      // A compound ends with an end tag, which has no name
+      let tag_type = TagType::from_id(self.u8()?)?;
+      if tag_type == TagType::End {
+        break;
+      }
+
+      let name = self.string()?;
+      let tag = self.payload(tag_type, depth + 1)?;
+      compound.insert(&name, tag);
+    }
+
+    Ok(compound)
+  }
+}
+
+struct Writer<'a> {
+  buffer: &'a mut Vec<u8>,
+  encoding: NbtEncoding,
+}
+
+impl Writer<'_> {
+  fn u8(&mut self, value: u8) {
+    self.buffer.push(value);
+  }
+
+  fn var_u64(&mut self, mut value: u64) {
+    while value >= 0x80 {
+      self.buffer.push(value as u8 | 0x80);
+      value >>= 7;
+    }
+
+    self.buffer.push(value as u8);
+  }
+
+  fn i32(&mut self, value: i32) {
+    match self.encoding {
+      NbtEncoding::LittleEndian => self.buffer.extend_from_slice(&value.to_le_bytes()),
+      NbtEncoding::Network => self.var_u64(((value << 1) ^ (value >> 31)) as u32 as u64)
+    }
+  }
+
+  fn i64(&mut self, value: i64) {
+    match self.encoding {
+      NbtEncoding::LittleEndian => self.buffer.extend_from_slice(&value.to_le_bytes()),
+      NbtEncoding::Network => self.var_u64(((value << 1) ^ (value >> 63)) as u64)
+    }
+  }
+
+  fn length(&mut self, length: usize) -> Result<(), NbtError> {
+    let length = i32::try_from(length).map_err(|_| NbtError::LengthTooLarge(length))?;
+    self.i32(length);
+
+    Ok(())
+  }
+
+  fn string(&mut self, value: &str) -> Result<(), NbtError> {
+    let too_long = || NbtError::StringTooLong(value.len());
+
+    match self.encoding {
+      NbtEncoding::LittleEndian => {
+        let length = u16::try_from(value.len()).map_err(|_| too_long())?;
+        self.buffer.extend_from_slice(&length.to_le_bytes());
+      },
+      NbtEncoding::Network => {
+        let length = u32::try_from(value.len()).map_err(|_| too_long())?;
+        self.var_u64(length as u64);
+      }
+    }
+
+    self.buffer.extend_from_slice(value.as_bytes());
+
+    Ok(())
+  }
+
+  fn payload(&mut self, tag: &Tag) -> Result<(), NbtError> {
+    match tag {
+      Tag::Byte(value) => self.u8(*value as u8),
+      Tag::Short(value) => self.buffer.extend_from_slice(&value.to_le_bytes()),
+      Tag::Int(value) => self.i32(*value),
+      Tag::Long(value) => self.i64(*value),
+      Tag::Float(value) => self.buffer.extend_from_slice(&value.to_le_bytes()),
+      Tag::Double(value) => self.buffer.extend_from_slice(&value.to_le_bytes()),
+      Tag::ByteArray(values) => {
+        self.length(values.len())?;
+        self.buffer.extend(values.iter().map(|value| *value as u8));
+      },
+      Tag::String(value) => self.string(value)?,
+      Tag::List(list) => {
+        self.u8(list.element_type().id());
+        self.length(list.len())?;
+        for tag in list.iter() {
+          self.payload(tag)?;
+        }
+      },
+      Tag::Compound(compound) => {
+        for (name, tag) in compound.iter() {
+          self.u8(tag.tag_type().id());
+          self.string(name)?;
+          self.payload(tag)?;
+        }
+
+        self.u8(TagType::End.id());
+      },
+      Tag::IntArray(values) => {
+        self.length(values.len())?;
+        for value in values {
+          self.i32(*value);
+        }
+      },
+      Tag::LongArray(values) => {
+        self.length(values.len())?;
+        for value in values {
+          self.i64(*value);
+        }
+      },
+    }
+
+    Ok(())
+  }
+}
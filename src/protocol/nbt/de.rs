@@ -0,0 +1,156 @@
+use serde::de::{self, DeserializeOwned, IntoDeserializer, Visitor};
+use serde::forward_to_deserialize_any;
+
+use super::error::NbtError;
+use super::tag::{Compound, Tag};
+
+impl de::Error for NbtError {
+  fn custom<T: std::fmt::Display>(message: T) -> Self {
+    NbtError::Serde(message.to_string())
+  }
+}
+
+/**
+ * Converts the tag to a value, the reverse of `ser::to_tag`.
+ * Byte tags are accepted for `bool` fields, and missing compound entries for `Option` fields.
+*/
+pub fn from_tag<T: DeserializeOwned>(tag: Tag) -> Result<T, NbtError> {
+  T::deserialize(tag)
+}
+
+/**
+ * Converts the compound to a value, the reverse of `ser::to_compound`.
+*/
+pub fn from_compound<T: DeserializeOwned>(compound: &Compound) -> Result<T, NbtError> {
+  from_tag(Tag::Compound(compound.clone()))
+}
+
+impl<'de> IntoDeserializer<'de, NbtError> for Tag {
+  type Deserializer = Tag;
+
+  fn into_deserializer(self) -> Self::Deserializer {
+    self
+  }
+}
+
+impl<'de> de::Deserializer<'de> for Tag {
+  type Error = NbtError;
+
+  fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+    match self {
+      Tag::Byte(value) => visitor.visit_i8(value),
+      Tag::Short(value) => visitor.visit_i16(value),
+      Tag::Int(value) => visitor.visit_i32(value),
+      Tag::Long(value) => visitor.visit_i64(value),
+      Tag::Float(value) => visitor.visit_f32(value),
+      Tag::Double(value) => visitor.visit_f64(value),
+      Tag::String(value) => visitor.visit_string(value),
+      Tag::ByteArray(values) => visitor.visit_seq(de::value::SeqDeserializer::new(values.into_iter())),
+      Tag::IntArray(values) => visitor.visit_seq(de::value::SeqDeserializer::new(values.into_iter())),
+      Tag::LongArray(values) => visitor.visit_seq(de::value::SeqDeserializer::new(values.into_iter())),
+      Tag::List(list) => visitor.visit_seq(de::value::SeqDeserializer::new(list.into_tags().into_iter())),
+      Tag::Compound(compound) => visitor.visit_map(de::value::MapDeserializer::new(compound.into_iter())),
+    }
+  }
+
+  fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+    match self {
+      Tag::Byte(value) => visitor.visit_bool(value != 0),
+      tag => tag.deserialize_any(visitor)
+    }
+  }
+
+  fn deserialize_u8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+    match self {
+      Tag::Byte(value) => visitor.visit_u8(value as u8),
+      tag => tag.deserialize_any(visitor)
+    }
+  }
+
+  fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+    match self {
+      Tag::ByteArray(values) => visitor.visit_byte_buf(values.into_iter().map(|value| value as u8).collect()),
+      tag => tag.deserialize_any(visitor)
+    }
+  }
+
+  fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+    self.deserialize_bytes(visitor)
+  }
+
+  fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+    // A tag that exists is always a value, missing entries are handled by serde
+    visitor.visit_some(self)
+  }
+
+  fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+    visitor.visit_unit()
+  }
+
+  fn deserialize_unit_struct<V: Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value, Self::Error> {
+    visitor.visit_unit()
+  }
+
+  fn deserialize_newtype_struct<V: Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value, Self::Error> {
+    visitor.visit_newtype_struct(self)
+  }
+
+  fn deserialize_enum<V: Visitor<'de>>(self, _name: &'static str, _variants: &'static [&'static str], visitor: V) -> Result<V::Value, Self::Error> {
+    match self {
+      // Unit variants are stored as their name
+      Tag::String(variant) => visitor.visit_enum(variant.into_deserializer()),
+
+      // Other variants are stored as a compound holding the value under the variant name
+      Tag::Compound(compound) if compound.len() == 1 => {
+        let (variant, value) = compound.into_iter().next().unwrap();
+        visitor.visit_enum(VariantAccess { variant, value })
+      },
+      tag => Err(NbtError::Serde(format!("expected an enum, found {:?}", tag.tag_type())))
+    }
+  }
+
+  fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+    visitor.visit_unit()
+  }
+
+  forward_to_deserialize_any! {
+    i8 i16 i32 i64 i128 u16 u32 u64 u128 f32 f64 char str string
+    seq tuple tuple_struct map struct identifier
+  }
+}
+
+struct VariantAccess {
+  variant: String,
+  value: Tag,
+}
+
+impl<'de> de::EnumAccess<'de> for VariantAccess {
+  type Error = NbtError;
+  type Variant = Tag;
+
+  fn variant_seed<S: de::DeserializeSeed<'de>>(self, seed: S) -> Result<(S::Value, Self::Variant), Self::Error> {
+    let variant = seed.deserialize(self.variant.into_deserializer())?;
+
+    Ok((variant, self.value))
+  }
+}
+
+impl<'de> de::VariantAccess<'de> for Tag {
+  type Error = NbtError;
+
+  fn unit_variant(self) -> Result<(), Self::Error> {
+    Ok(())
+  }
+
+  fn newtype_variant_seed<S: de::DeserializeSeed<'de>>(self, seed: S) -> Result<S::Value, Self::Error> {
+    seed.deserialize(self)
+  }
+
+  fn tuple_variant<V: Visitor<'de>>(self, _length: usize, visitor: V) -> Result<V::Value, Self::Error> {
+    de::Deserializer::deserialize_seq(self, visitor)
+  }
+
+  fn struct_variant<V: Visitor<'de>>(self, _fields: &'static [&'static str], visitor: V) -> Result<V::Value, Self::Error> {
+    de::Deserializer::deserialize_map(self, visitor)
+  }
+}
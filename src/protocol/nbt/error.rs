@@ -0,0 +1,64 @@
+use std::fmt;
+
+/**
+ * An error raised while reading, writing or converting NBT.
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub enum NbtError {
+  /** The data ended in the middle of a tag. */
+  UnexpectedEof,
+
+  /** A tag type id that does not exist. */
+  InvalidTagType(u8),
+
+  /** A string is not valid UTF-8. */
+  InvalidString,
+
+  /** A VarInt or VarLong is longer than its type allows. */
+  VarIntTooLong,
+
+  /** A list or array has a negative length. */
+  NegativeLength(i32),
+
+  /** The tags are nested deeper than `MAX_DEPTH`. */
+  DepthLimit,
+
+  /** The root tag is followed by more data. */
+  TrailingBytes(usize),
+
+  /** A string is longer than its length prefix can hold, in bytes. */
+  StringTooLong(usize),
+
+  /** A list or array has more elements than its length prefix can hold. */
+  LengthTooLarge(usize),
+
+  /** A list holds tags of different types. */
+  MixedList,
+
+  /** SNBT text could not be parsed, at the character offset. */
+  Snbt { position: usize, message: String },
+
+  /** A value could not be converted to or from NBT through serde. */
+  Serde(String),
+}
+
+impl fmt::Display for NbtError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      NbtError::UnexpectedEof => write!(f, "nbt: unexpected end of data"),
+      NbtError::InvalidTagType(id) => write!(f, "nbt: invalid tag type {}", id),
+      NbtError::InvalidString => write!(f, "nbt: string is not valid utf-8"),
+      NbtError::VarIntTooLong => write!(f, "nbt: varint is too long"),
+      NbtError::NegativeLength(length) => write!(f, "nbt: negative length {}", length),
+      NbtError::DepthLimit => write!(f, "nbt: tags are nested too deeply"),
+      NbtError::TrailingBytes(count) => write!(f, "nbt: {} bytes follow the root tag", count),
+      NbtError::StringTooLong(length) => write!(f, "nbt: string of {} bytes is too long", length),
+      NbtError::LengthTooLarge(length) => write!(f, "nbt: length {} is too large", length),
+      NbtError::MixedList => write!(f, "nbt: list holds tags of different types"),
+      NbtError::Snbt { position, message } => write!(f, "nbt: invalid snbt at {}: {}", position, message),
+      NbtError::Serde(message) => write!(f, "nbt: {}", message),
+    }
+  }
+}
+
+impl std::error::Error for NbtError {}
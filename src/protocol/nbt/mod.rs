@@ -0,0 +1,9 @@
+pub mod codec;
+pub mod error;
+pub mod snbt;
+pub mod tag;
+
+#[cfg(feature = "serde")]
+pub mod de;
+#[cfg(feature = "serde")]
+pub mod ser;
@@ -0,0 +1,317 @@
+use serde::ser::{self, Serialize};
+
+use super::error::NbtError;
+use super::tag::{Compound, List, Tag};
+
+impl ser::Error for NbtError {
+  fn custom<T: std::fmt::Display>(message: T) -> Self {
+    NbtError::Serde(message.to_string())
+  }
+}
+
+/**
+ * Converts the value to a tag.
+ * Structs and maps become compounds, sequences become lists, and `None` fields are left out.
+ * Unsigned integers are stored in the next wider signed tag, except `u8` which is stored in a byte tag.
+*/
+pub fn to_tag<T: Serialize + ?Sized>(value: &T) -> Result<Tag, NbtError> {
+  value.serialize(TagSerializer)?
+    .ok_or_else(|| NbtError::Serde("cannot store None outside of a struct field".to_string()))
+}
+
+/**
+ * Converts the value to a compound, failing if it is not a struct or map.
+*/
+pub fn to_compound<T: Serialize + ?Sized>(value: &T) -> Result<Compound, NbtError> {
+  match to_tag(value)? {
+    Tag::Compound(compound) => Ok(compound),
+    tag => Err(NbtError::Serde(format!("expected a compound, found {:?}", tag.tag_type())))
+  }
+}
+
+/**
+ * Serializes values to tags, `None` produces no tag at all.
+*/
+struct TagSerializer;
+
+fn some(tag: Tag) -> Result<Option<Tag>, NbtError> {
+  Ok(Some(tag))
+}
+
+fn required(tag: Option<Tag>) -> Result<Tag, NbtError> {
+  tag.ok_or_else(|| NbtError::Serde("cannot store None in a list".to_string()))
+}
+
+impl ser::Serializer for TagSerializer {
+  type Ok = Option<Tag>;
+  type Error = NbtError;
+  type SerializeSeq = SerializeList;
+  type SerializeTuple = SerializeList;
+  type SerializeTupleStruct = SerializeList;
+  type SerializeTupleVariant = SerializeVariant<SerializeList>;
+  type SerializeMap = SerializeCompound;
+  type SerializeStruct = SerializeCompound;
+  type SerializeStructVariant = SerializeVariant<SerializeCompound>;
+
+  fn serialize_bool(self, value: bool) -> Result<Self::Ok, Self::Error> {
+    some(Tag::Byte(value as i8))
+  }
+
+  fn serialize_i8(self, value: i8) -> Result<Self::Ok, Self::Error> {
+    some(Tag::Byte(value))
+  }
+
+  fn serialize_i16(self, value: i16) -> Result<Self::Ok, Self::Error> {
+    some(Tag::Short(value))
+  }
+
+  fn serialize_i32(self, value: i32) -> Result<Self::Ok, Self::Error> {
+    some(Tag::Int(value))
+  }
+
+  fn serialize_i64(self, value: i64) -> Result<Self::Ok, Self::Error> {
+    some(Tag::Long(value))
+  }
+
+  fn serialize_u8(self, value: u8) -> Result<Self::Ok, Self::Error> {
+    some(Tag::Byte(value as i8))
+  }
+
+  fn serialize_u16(self, value: u16) -> Result<Self::Ok, Self::Error> {
+    some(Tag::Int(value as i32))
+  }
+
+  fn serialize_u32(self, value: u32) -> Result<Self::Ok, Self::Error> {
+    some(Tag::Long(value as i64))
+  }
+
+  fn serialize_u64(self, value: u64) -> Result<Self::Ok, Self::Error> {
+    let value = i64::try_from(value).map_err(|_| NbtError::Serde(format!("{} does not fit in a long", value)))?;
+
+    some(Tag::Long(value))
+  }
+
+  fn serialize_f32(self, value: f32) -> Result<Self::Ok, Self::Error> {
+    some(Tag::Float(value))
+  }
+
+  fn serialize_f64(self, value: f64) -> Result<Self::Ok, Self::Error> {
+    some(Tag::Double(value))
+  }
+
+  fn serialize_char(self, value: char) -> Result<Self::Ok, Self::Error> {
+    some(Tag::String(value.to_string()))
+  }
+
+  fn serialize_str(self, value: &str) -> Result<Self::Ok, Self::Error> {
+    some(Tag::String(value.to_string()))
+  }
+
+  fn serialize_bytes(self, value: &[u8]) -> Result<Self::Ok, Self::Error> {
+    some(Tag::ByteArray(value.iter().map(|byte| *byte as i8).collect()))
+  }
+
+  fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+    Ok(None)
+  }
+
+  fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Self::Ok, Self::Error> {
+    value.serialize(self)
+  }
+
+  fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+    some(Tag::Compound(Compound::new()))
+  }
+
+  fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> {
+    self.serialize_unit()
+  }
+
+  fn serialize_unit_variant(self, _name: &'static str, _index: u32, variant: &'static str) -> Result<Self::Ok, Self::Error> {
+    self.serialize_str(variant)
+  }
+
+  fn serialize_newtype_struct<T: Serialize + ?Sized>(self, _name: &'static str, value: &T) -> Result<Self::Ok, Self::Error> {
+    value.serialize(self)
+  }
+
+  fn serialize_newtype_variant<T: Serialize + ?Sized>(self, _name: &'static str, _index: u32, variant: &'static str, value: &T) -> Result<Self::Ok, Self::Error> {
+    let mut compound = Compound::new();
+    if let Some(tag) = value.serialize(TagSerializer)? {
+      compound.insert(variant, tag);
+    }
+
+    some(Tag::Compound(compound))
+  }
+
+  fn serialize_seq(self, _length: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+    Ok(SerializeList { list: List::default() })
+  }
+
+  fn serialize_tuple(self, length: usize) -> Result<Self::SerializeTuple, Self::Error> {
+    self.serialize_seq(Some(length))
+  }
+
+  fn serialize_tuple_struct(self, _name: &'static str, length: usize) -> Result<Self::SerializeTupleStruct, Self::Error> {
+    self.serialize_seq(Some(length))
+  }
+
+  fn serialize_tuple_variant(self, _name: &'static str, _index: u32, variant: &'static str, length: usize) -> Result<Self::SerializeTupleVariant, Self::Error> {
+    Ok(SerializeVariant { variant, inner: self.serialize_seq(Some(length))? })
+  }
+
+  fn serialize_map(self, _length: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+    Ok(SerializeCompound { compound: Compound::new(), key: None })
+  }
+
+  fn serialize_struct(self, _name: &'static str, length: usize) -> Result<Self::SerializeStruct, Self::Error> {
+    self.serialize_map(Some(length))
+  }
+
+  fn serialize_struct_variant(self, _name: &'static str, _index: u32, variant: &'static str, length: usize) -> Result<Self::SerializeStructVariant, Self::Error> {
+    Ok(SerializeVariant { variant, inner: self.serialize_map(Some(length))? })
+  }
+}
+
+struct SerializeList {
+  list: List,
+}
+
+impl SerializeList {
+  fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), NbtError> {
+    self.list.push(required(value.serialize(TagSerializer)?)?)
+  }
+}
+
+impl ser::SerializeSeq for SerializeList {
+  type Ok = Option<Tag>;
+  type Error = NbtError;
+
+  fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+    self.push(value)
+  }
+
+  fn end(self) -> Result<Self::Ok, Self::Error> {
+    some(Tag::List(self.list))
+  }
+}
+
+impl ser::SerializeTuple for SerializeList {
+  type Ok = Option<Tag>;
+  type Error = NbtError;
+
+  fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+    self.push(value)
+  }
+
+  fn end(self) -> Result<Self::Ok, Self::Error> {
+    some(Tag::List(self.list))
+  }
+}
+
+impl ser::SerializeTupleStruct for SerializeList {
+  type Ok = Option<Tag>;
+  type Error = NbtError;
+
+  fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+    self.push(value)
+  }
+
+  fn end(self) -> Result<Self::Ok, Self::Error> {
+    some(Tag::List(self.list))
+  }
+}
+
+struct SerializeCompound {
+  compound: Compound,
+  key: Option<String>,
+}
+
+impl SerializeCompound {
+  fn insert<T: Serialize + ?Sized>(&mut self, key: &str, value: &T) -> Result<(), NbtError> {
+    // Fields set to None are left out of the compound
+    if let Some(tag) = value.serialize(TagSerializer)? {
+      self.compound.insert(key, tag);
+    }
+
+    Ok(())
+  }
+}
+
+impl ser::SerializeMap for SerializeCompound {
+  type Ok = Option<Tag>;
+  type Error = NbtError;
+
+  fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Self::Error> {
+    match key.serialize(TagSerializer)? {
+      Some(Tag::String(key)) => {
+        self.key = Some(key);
+        Ok(())
+      },
+      _ => Err(NbtError::Serde("compound names have to be strings".to_string()))
+    }
+  }
+
+  fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+    let key = self.key.take().ok_or_else(|| NbtError::Serde("value serialized before its key".to_string()))?;
+
+    self.insert(&key, value)
+  }
+
+  fn end(self) -> Result<Self::Ok, Self::Error> {
+    some(Tag::Compound(self.compound))
+  }
+}
+
+impl ser::SerializeStruct for SerializeCompound {
+  type Ok = Option<Tag>;
+  type Error = NbtError;
+
+  fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), Self::Error> {
+    self.insert(key, value)
+  }
+
+  fn end(self) -> Result<Self::Ok, Self::Error> {
+    some(Tag::Compound(self.compound))
+  }
+}
+
+/**
+ * Wraps the value of an enum variant in a compound holding it under the variant name.
+*/
+struct SerializeVariant<S> {
+  variant: &'static str,
+  inner: S,
+}
+
+impl<S> SerializeVariant<S> {
+  fn end(variant: &'static str, tag: Option<Tag>) -> Result<Option<Tag>, NbtError> {
+    some(Tag::Compound(Compound::new().with(variant, required(tag)?)))
+  }
+}
+
+impl ser::SerializeTupleVariant for SerializeVariant<SerializeList> {
+  type Ok = Option<Tag>;
+  type Error = NbtError;
+
+  fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+    self.inner.push(value)
+  }
+
+  fn end(self) -> Result<Self::Ok, Self::Error> {
+    Self::end(self.variant, ser::SerializeSeq::end(self.inner)?)
+  }
+}
+
+impl ser::SerializeStructVariant for SerializeVariant<SerializeCompound> {
+  type Ok = Option<Tag>;
+  type Error = NbtError;
+
+  fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), Self::Error> {
+    self.inner.insert(key, value)
+  }
+
+  fn end(self) -> Result<Self::Ok, Self::Error> {
+    Self::end(self.variant, ser::SerializeStruct::end(self.inner)?)
+  }
+}
@@ -0,0 +1,380 @@
+use std::fmt::{self, Write};
+
+use super::error::NbtError;
+use super::tag::{Compound, List, Tag};
+
+/**
+ * Prints the tag as SNBT, the text format used by commands, e.g. `{name:"Steve",health:20.0f}`.
+ * SNBT has no syntax for non-finite numbers, so NaN and infinite floats and doubles print as e.g. `NaNf` and don't round-trip.
+*/
+impl fmt::Display for Tag {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Tag::Byte(value) => write!(f, "{}b", value),
+      Tag::Short(value) => write!(f, "{}s", value),
+      Tag::Int(value) => write!(f, "{}", value),
+      Tag::Long(value) => write!(f, "{}L", value),
+      Tag::Float(value) => write!(f, "{:?}f", value),
+      Tag::Double(value) => write!(f, "{:?}d", value),
+      Tag::ByteArray(values) => write_array(f, "B", values.iter().map(|value| format!("{}b", value))),
+      Tag::String(value) => write_string(f, value),
+      Tag::List(list) => list.fmt(f),
+      Tag::Compound(compound) => compound.fmt(f),
+      Tag::IntArray(values) => write_array(f, "I", values.iter().map(|value| value.to_string())),
+      Tag::LongArray(values) => write_array(f, "L", values.iter().map(|value| format!("{}L", value))),
+    }
+  }
+}
+
+impl fmt::Display for List {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_char('[')?;
+    for (index, tag) in self.iter().enumerate() {
+      if index > 0 {
+        f.write_char(',')?;
+      }
+
+      write!(f, "{}", tag)?;
+    }
+
+    f.write_char(']')
+  }
+}
+
+impl fmt::Display for Compound {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_char('{')?;
+    for (index, (name, tag)) in self.iter().enumerate() {
+      if index > 0 {
+        f.write_char(',')?;
+      }
+
+      // Names are only quoted when they have to be
+      match !name.is_empty() && name.chars().all(is_unquoted_char) {
+        true => f.write_str(name)?,
+        false => write_string(f, name)?
+      }
+
+      write!(f, ":{}", tag)?;
+    }
+
+    f.write_char('}')
+  }
+}
+
+fn write_array(f: &mut fmt::Formatter<'_>, prefix: &str, values: impl Iterator<Item = String>) -> fmt::Result {
+  write!(f, "[{};", prefix)?;
+  for (index, value) in values.enumerate() {
+    if index > 0 {
+      f.write_char(',')?;
+    }
+
+    f.write_str(&value)?;
+  }
+
+  f.write_char(']')
+}
+
+fn write_string(f: &mut fmt::Formatter<'_>, value: &str) -> fmt::Result {
+  f.write_char('"')?;
+  for char in value.chars() {
+    match char {
+      '"' => f.write_str("\\\"")?,
+      '\\' => f.write_str("\\\\")?,
+      '\n' => f.write_str("\\n")?,
+      '\t' => f.write_str("\\t")?,
+      '\r' => f.write_str("\\r")?,
+      char => f.write_char(char)?
+    }
+  }
+
+  f.write_char('"')
+}
+
+fn is_unquoted_char(char: char) -> bool {
+  char.is_ascii_alphanumeric() || matches!(char, '_' | '-' | '.' | '+')
+}
+
+impl Tag {
+  /**
+   * Parses SNBT text, as printed by the `Display` implementation of `Tag`.
+   * Numbers without a suffix are ints, or doubles when they have a fraction or exponent.
+  */
+  pub fn from_snbt(text: &str) -> Result<Tag, NbtError> {
+    let mut parser = Parser { chars: text.char_indices().collect(), index: 0, length: text.len() };
+
+    let tag = parser.tag(0)?;
+    parser.skip_whitespace();
+
+    match parser.peek() {
+      None => Ok(tag),
+      Some(_) => Err(parser.error("expected the end of the text"))
+    }
+  }
+}
+
+impl std::str::FromStr for Tag {
+  type Err = NbtError;
+
+  fn from_str(text: &str) -> Result<Self, Self::Err> {
+    Tag::from_snbt(text)
+  }
+}
+
+struct Parser {
+  chars: Vec<(usize, char)>,
+  index: usize,
+  length: usize,
+}
+
+impl Parser {
+  fn position(&self) -> usize {
+    self.chars.get(self.index).map(|(position, _)| *position).unwrap_or(self.length)
+  }
+
+  fn error(&self, message: &str) -> NbtError {
+    NbtError::Snbt { position: self.position(), message: message.to_string() }
+  }
+
+  fn peek(&self) -> Option<char> {
+    self.chars.get(self.index).map(|(_, char)| *char)
+  }
+
+  fn next(&mut self) -> Option<char> {
+    let char = self.peek()?;
+    self.index += 1;
+
+    Some(char)
+  }
+
+  fn skip_whitespace(&mut self) {
+    while self.peek().is_some_and(char::is_whitespace) {
+      self.index += 1;
+    }
+  }
+
+  fn expect(&mut self, expected: char) -> Result<(), NbtError> {
+    self.skip_whitespace();
+
+    match self.peek() == Some(expected) {
+      true => {
+        self.index += 1;
+        Ok(())
+      },
+      false => Err(self.error(&format!("expected '{}'", expected)))
+    }
+  }
+
+  fn tag(&mut self, depth: usize) -> Result<Tag, NbtError> {
+    if depth > super::codec::MAX_DEPTH {
+      return Err(self.error("tags are nested too deeply"));
+    }
+
+    self.skip_whitespace();
+
+    match self.peek() {
+      Some('{') => self.compound(depth).map(Tag::Compound),
+      Some('[') => self.list(depth),
+      Some('"') | Some('\'') => self.quoted().map(Tag::String),
+      Some(_) => self.value(),
+      None => Err(self.error("expected a tag"))
+    }
+  }
+
+  fn compound(&mut self, depth: usize) -> Result<Compound, NbtError> {
+    self.expect('{')?;
+
+    let mut compound = Compound::new();
+    self.skip_whitespace();
+    if self.peek() == Some('}') {
+      self.index += 1;
+      return Ok(compound);
+    }
+
+    loop {
+      // Get the name, which may be quoted
+      self.skip_whitespace();
+      let name = match self.peek() {
+        Some('"') | Some('\'') => self.quoted()?,
+        _ => self.unquoted()?
+      };
+
+      self.expect(':')?;
+      let tag = self.tag(depth + 1)?;
+      compound.insert(&name, tag);
+
+      self.skip_whitespace();
+      match self.next() {
+        Some(',') => continue,
+        Some('}') => return Ok(compound),
+        _ => {
+          self.index -= 1;
+          return Err(self.error("expected ',' or '}'"));
+        }
+      }
+    }
+  }
+
+  fn list(&mut self, depth: usize) -> Result<Tag, NbtError> {
+    self.expect('[')?;
+
+    // Check for the prefix of a typed array
+    let prefix = match (self.chars.get(self.index), self.chars.get(self.index + 1)) {
+      (Some((_, prefix @ ('B' | 'I' | 'L'))), Some((_, ';'))) => Some(*prefix),
+      _ => None
+    };
+
+    if let Some(prefix) = prefix {
+      self.index += 2;
+      return self.array(prefix);
+    }
+
+    let mut list = List::default();
+    self.skip_whitespace();
+    if self.peek() == Some(']') {
+      self.index += 1;
+      return Ok(Tag::List(list));
+    }
+
+    loop {
+      let position = self.position();
+      let tag = self.tag(depth + 1)?;
+      list.push(tag).map_err(|_| NbtError::Snbt { position, message: "list holds tags of different types".to_string() })?;
+
+      self.skip_whitespace();
+      match self.next() {
+        Some(',') => continue,
+        Some(']') => return Ok(Tag::List(list)),
+        _ => {
+          self.index -= 1;
+          return Err(self.error("expected ',' or ']'"));
+        }
+      }
+    }
+  }
+
+  fn array(&mut self, prefix: char) -> Result<Tag, NbtError> {
+    let mut values = Vec::new();
+
+    self.skip_whitespace();
+    if self.peek() == Some(']') {
+      self.index += 1;
+    } else {
+      loop {
+        self.skip_whitespace();
+        let position = self.position();
+        let value = self.value()?.as_i64()
+          .ok_or_else(|| NbtError::Snbt { position, message: "expected an integer".to_string() })?;
+        values.push((position, value));
+
+        self.skip_whitespace();
+        match self.next() {
+          Some(',') => continue,
+          Some(']') => break,
+          _ => {
+            self.index -= 1;
+            return Err(self.error("expected ',' or ']'"));
+          }
+        }
+      }
+    }
+
+    let tag = match prefix {
+      'B' => Tag::ByteArray(narrow(values)?),
+      'I' => Tag::IntArray(narrow(values)?),
+      _ => Tag::LongArray(values.into_iter().map(|(_, value)| value).collect()),
+    };
+
+    Ok(tag)
+  }
+
+  fn quoted(&mut self) -> Result<String, NbtError> {
+    let quote = self.next().unwrap();
+
+    let mut value = String::new();
+    loop {
+      match self.next() {
+        Some('\\') => match self.next() {
+          Some('n') => value.push('\n'),
+          Some('t') => value.push('\t'),
+          Some('r') => value.push('\r'),
+          Some(char @ ('"' | '\'' | '\\')) => value.push(char),
+          _ => {
+            self.index -= 1;
+            return Err(self.error("invalid escape"));
+          }
+        },
+        Some(char) if char == quote => return Ok(value),
+        Some(char) => value.push(char),
+        None => return Err(self.error("unterminated string"))
+      }
+    }
+  }
+
+  fn unquoted(&mut self) -> Result<String, NbtError> {
+    self.skip_whitespace();
+
+    let start = self.index;
+    while self.peek().is_some_and(is_unquoted_char) {
+      self.index += 1;
+    }
+
+    match self.index > start {
+      true => Ok(self.chars[start..self.index].iter().map(|(_, char)| *char).collect()),
+      false => Err(self.error("expected a name or value"))
+    }
+  }
+
+  /**
+   * Parses an unquoted value, which is a number when it looks like one and a string otherwise.
+  */
+  fn value(&mut self) -> Result<Tag, NbtError> {
+    let position = self.position();
+    let token = self.unquoted()?;
+
+    match token.as_str() {
+      "true" => return Ok(Tag::Byte(1)),
+      "false" => return Ok(Tag::Byte(0)),
+      _ => {}
+    }
+
+    let out_of_range = || NbtError::Snbt { position, message: format!("number out of range: {}", token) };
+
+    // Split off the type suffix
+    let (number, suffix) = match token.char_indices().last() {
+      Some((index, suffix)) if suffix.is_ascii_alphabetic() && index > 0 => (&token[..index], Some(suffix.to_ascii_lowercase())),
+      _ => (token.as_str(), None)
+    };
+
+    let is_integer = is_integer(number);
+    let is_float = is_integer || number.parse::<f64>().is_ok_and(|_| number.bytes().any(|byte| byte.is_ascii_digit()));
+
+    let tag = match suffix {
+      Some('b') if is_integer => Tag::Byte(number.parse().map_err(|_| out_of_range())?),
+      Some('s') if is_integer => Tag::Short(number.parse().map_err(|_| out_of_range())?),
+      Some('l') if is_integer => Tag::Long(number.parse().map_err(|_| out_of_range())?),
+      Some('f') if is_float => Tag::Float(number.parse().unwrap()),
+      Some('d') if is_float => Tag::Double(number.parse().unwrap()),
+      None if is_integer => Tag::Int(number.parse().map_err(|_| out_of_range())?),
+      None if is_float => Tag::Double(number.parse().unwrap()),
+      _ => Tag::String(token.clone()),
+    };
+
+    Ok(tag)
+  }
+}
+
+/**
+ * Narrows the values of a typed array to its element type, failing on the first value that doesn't fit.
+*/
+fn narrow<T: TryFrom<i64>>(values: Vec<(usize, i64)>) -> Result<Vec<T>, NbtError> {
+  values.into_iter()
+    .map(|(position, value)| T::try_from(value).map_err(|_| NbtError::Snbt { position, message: format!("number out of range: {}", value) }))
+    .collect()
+}
+
+fn is_integer(text: &str) -> bool {
+  let digits = text.strip_prefix(['-', '+']).unwrap_or(text);
+
+  !digits.is_empty() && digits.bytes().all(|byte| byte.is_ascii_digit())
+}
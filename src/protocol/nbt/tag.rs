@@ -0,0 +1,363 @@
+use std::collections::HashMap;
+
+use super::error::NbtError;
+
+/**
+ * The type of a tag, with the id it is written with.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TagType {
+  End = 0,
+  Byte = 1,
+  Short = 2,
+  Int = 3,
+  Long = 4,
+  Float = 5,
+  Double = 6,
+  ByteArray = 7,
+  String = 8,
+  List = 9,
+  Compound = 10,
+  IntArray = 11,
+  LongArray = 12,
+}
+
+impl TagType {
+  pub fn id(&self) -> u8 {
+    *self as u8
+  }
+
+  pub fn from_id(id: u8) -> Result<TagType, NbtError> {
+    let tag_type = match id {
+      0 => TagType::End,
+      1 => TagType::Byte,
+      2 => TagType::Short,
+      3 => TagType::Int,
+      4 => TagType::Long,
+      5 => TagType::Float,
+      6 => TagType::Double,
+      7 => TagType::ByteArray,
+      8 => TagType::String,
+      9 => TagType::List,
+      10 => TagType::Compound,
+      11 => TagType::IntArray,
+      12 => TagType::LongArray,
+      _ => return Err(NbtError::InvalidTagType(id))
+    };
+
+    Ok(tag_type)
+  }
+}
+
+/**
+ * A single NBT value.
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub enum Tag {
+  Byte(i8),
+  Short(i16),
+  Int(i32),
+  Long(i64),
+  Float(f32),
+  Double(f64),
+  ByteArray(Vec<i8>),
+  String(String),
+  List(List),
+  Compound(Compound),
+  IntArray(Vec<i32>),
+  LongArray(Vec<i64>),
+}
+
+impl Tag {
+  pub fn tag_type(&self) -> TagType {
+    match self {
+      Tag::Byte(_) => TagType::Byte,
+      Tag::Short(_) => TagType::Short,
+      Tag::Int(_) => TagType::Int,
+      Tag::Long(_) => TagType::Long,
+      Tag::Float(_) => TagType::Float,
+      Tag::Double(_) => TagType::Double,
+      Tag::ByteArray(_) => TagType::ByteArray,
+      Tag::String(_) => TagType::String,
+      Tag::List(_) => TagType::List,
+      Tag::Compound(_) => TagType::Compound,
+      Tag::IntArray(_) => TagType::IntArray,
+      Tag::LongArray(_) => TagType::LongArray,
+    }
+  }
+
+  pub fn as_compound(&self) -> Option<&Compound> {
+    match self {
+      Tag::Compound(compound) => Some(compound),
+      _ => None
+    }
+  }
+
+  pub fn as_list(&self) -> Option<&List> {
+    match self {
+      Tag::List(list) => Some(list),
+      _ => None
+    }
+  }
+
+  pub fn as_str(&self) -> Option<&str> {
+    match self {
+      Tag::String(value) => Some(value),
+      _ => None
+    }
+  }
+
+  /**
+   * Gets the value of an integer tag of any width.
+  */
+  pub fn as_i64(&self) -> Option<i64> {
+    match self {
+      Tag::Byte(value) => Some(*value as i64),
+      Tag::Short(value) => Some(*value as i64),
+      Tag::Int(value) => Some(*value as i64),
+      Tag::Long(value) => Some(*value),
+      _ => None
+    }
+  }
+
+  /**
+   * Gets the value of a float or double tag.
+  */
+  pub fn as_f64(&self) -> Option<f64> {
+    match self {
+      Tag::Float(value) => Some(*value as f64),
+      Tag::Double(value) => Some(*value),
+      _ => None
+    }
+  }
+}
+
+macro_rules! impl_from {
+  ($($type:ty => $variant:ident),*) => {
+    $(
+      impl From<$type> for Tag {
+        fn from(value: $type) -> Self {
+          Tag::$variant(value)
+        }
+      }
+    )*
+  };
+}
+
+impl_from!(i8 => Byte, i16 => Short, i32 => Int, i64 => Long, f32 => Float, f64 => Double, String => String, List => List, Compound => Compound);
+
+impl From<bool> for Tag {
+  fn from(value: bool) -> Self {
+    Tag::Byte(value as i8)
+  }
+}
+
+impl From<&str> for Tag {
+  fn from(value: &str) -> Self {
+    Tag::String(value.to_string())
+  }
+}
+
+/**
+ * A list of tags that all have the same type.
+ * The element type is kept even when the list is empty, so lists are written back unchanged.
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct List {
+  element_type: TagType,
+  tags: Vec<Tag>,
+}
+
+impl Default for List {
+  fn default() -> Self {
+    List::empty(TagType::End)
+  }
+}
+
+impl List {
+  /**
+   * Creates an empty list of the element type.
+  */
+  pub fn empty(element_type: TagType) -> Self {
+    List { element_type, tags: Vec::new() }
+  }
+
+  /**
+   * Creates the list of the tags, which have to share the same type.
+  */
+  pub fn new(tags: Vec<Tag>) -> Result<Self, NbtError> {
+    let element_type = tags.first().map(Tag::tag_type).unwrap_or(TagType::End);
+    if tags.iter().any(|tag| tag.tag_type() != element_type) {
+      return Err(NbtError::MixedList);
+    }
+
+    Ok(List { element_type, tags })
+  }
+
+  pub fn element_type(&self) -> TagType {
+    self.element_type
+  }
+
+  pub fn tags(&self) -> &[Tag] {
+    &self.tags
+  }
+
+  pub fn into_tags(self) -> Vec<Tag> {
+    self.tags
+  }
+
+  pub fn len(&self) -> usize {
+    self.tags.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.tags.is_empty()
+  }
+
+  pub fn get(&self, index: usize) -> Option<&Tag> {
+    self.tags.get(index)
+  }
+
+  /**
+   * Adds the tag to the end of the list, the first tag of an empty list sets its element type.
+  */
+  pub fn push(&mut self, tag: Tag) -> Result<(), NbtError> {
+    if self.tags.is_empty() {
+      self.element_type = tag.tag_type();
+    } else if tag.tag_type() != self.element_type {
+      return Err(NbtError::MixedList);
+    }
+
+    self.tags.push(tag);
+
+    Ok(())
+  }
+
+  pub fn iter(&self) -> std::slice::Iter<'_, Tag> {
+    self.tags.iter()
+  }
+}
+
+/**
+ * Named tags, kept in the order they were inserted so compounds are written back unchanged.
+*/
+#[derive(Debug, Clone, Default)]
+pub struct Compound {
+  entries: Vec<(String, Tag)>,
+  // The position of each entry by name, so lookups stay fast in large compounds
+  index: HashMap<String, usize>,
+}
+
+impl Compound {
+  pub fn new() -> Self {
+    Compound::default()
+  }
+
+  /**
+   * Gets the compound with the tag added, see `insert`.
+  */
+  pub fn with(mut self, name: &str, tag: impl Into<Tag>) -> Self {
+    self.insert(name, tag);
+    self
+  }
+
+  /**
+   * Sets the tag with the name, returning the tag it replaced.
+  */
+  pub fn insert(&mut self, name: &str, tag: impl Into<Tag>) -> Option<Tag> {
+    let tag = tag.into();
+
+    match self.index.get(name) {
+      Some(&index) => Some(std::mem::replace(&mut self.entries[index].1, tag)),
+      None => {
+        self.index.insert(name.to_string(), self.entries.len());
+        self.entries.push((name.to_string(), tag));
+        None
+      }
+    }
+  }
+
+  pub fn get(&self, name: &str) -> Option<&Tag> {
+    self.index.get(name).map(|&index| &self.entries[index].1)
+  }
+
+  pub fn get_mut(&mut self, name: &str) -> Option<&mut Tag> {
+    self.index.get(name).map(|&index| &mut self.entries[index].1)
+  }
+
+  /**
+   * Removes the tag with the name, the entries after it keep their order.
+  */
+  pub fn remove(&mut self, name: &str) -> Option<Tag> {
+    let index = self.index.remove(name)?;
+
+    // Move the entries after the removed one up
+    for position in self.index.values_mut() {
+      if *position > index {
+        *position -= 1;
+      }
+    }
+
+    Some(self.entries.remove(index).1)
+  }
+
+  pub fn contains(&self, name: &str) -> bool {
+    self.index.contains_key(name)
+  }
+
+  pub fn len(&self) -> usize {
+    self.entries.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.entries.is_empty()
+  }
+
+  pub fn iter(&self) -> impl Iterator<Item = (&str, &Tag)> {
+    self.entries.iter().map(|(name, tag)| (name.as_str(), tag))
+  }
+}
+
+/**
+ * Compares the entries in order, the index only mirrors them.
+*/
+impl PartialEq for Compound {
+  fn eq(&self, other: &Compound) -> bool {
+    self.entries == other.entries
+  }
+}
+
+impl IntoIterator for Compound {
+  type Item = (String, Tag);
+  type IntoIter = std::vec::IntoIter<(String, Tag)>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.entries.into_iter()
+  }
+}
+
+impl FromIterator<(String, Tag)> for Compound {
+  fn from_iter<I: IntoIterator<Item = (String, Tag)>>(iter: I) -> Self {
+    let mut compound = Compound::new();
+    for (name, tag) in iter {
+      compound.insert(&name, tag);
+    }
+
+    compound
+  }
+}
+
+/**
+ * The root tag of NBT data, together with its name.
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct NamedTag {
+  pub name: String,
+  pub tag: Tag,
+}
+
+impl NamedTag {
+  pub fn new(name: &str, tag: impl Into<Tag>) -> Self {
+    NamedTag { name: name.to_string(), tag: tag.into() }
+  }
+}
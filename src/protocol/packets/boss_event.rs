@@ -0,0 +1,63 @@
+use crate::BinaryCodec;
+
+/**
+ * What a `BossEventPacket` does to the boss bar, with the data of that event.
+*/
+#[derive(Debug, Clone, PartialEq, BinaryCodec)]
+#[codec(tag = var_u32)]
+pub enum BossEvent {
+  Show {
+    title: String,
+    filtered_title: String,
+    health_percent: f32,
+    screen_darkening: i16,
+    #[codec(var_u32)]
+    color: u32,
+    #[codec(var_u32)]
+    overlay: u32,
+  },
+  RegisterPlayer {
+    #[codec(var_long)]
+    player_unique_id: i64,
+  },
+  Hide,
+  UnregisterPlayer {
+    #[codec(var_long)]
+    player_unique_id: i64,
+  },
+  HealthPercent {
+    health_percent: f32,
+  },
+  Title {
+    title: String,
+    filtered_title: String,
+  },
+  AppearanceProperties {
+    screen_darkening: i16,
+    #[codec(var_u32)]
+    color: u32,
+    #[codec(var_u32)]
+    overlay: u32,
+  },
+  Texture {
+    #[codec(var_u32)]
+    color: u32,
+    #[codec(var_u32)]
+    overlay: u32,
+  },
+  Query {
+    #[codec(var_long)]
+    player_unique_id: i64,
+  },
+}
+
+/**
+ * Shows, updates or hides the boss bar of an actor.
+*/
+#[derive(Debug, Clone, PartialEq, BinaryCodec)]
+pub struct BossEventPacket {
+  #[codec(var_long)]
+  pub boss_unique_id: i64,
+
+  pub event: BossEvent,
+}
@@ -0,0 +1,42 @@
+use crate::protocol::binary::codec::{self, BinaryCodec};
+use crate::protocol::binary::error::BinaryError;
+use crate::protocol::binary::stream::BinaryStream;
+
+/**
+ * Disconnects the player from the server.
+ * The messages are only encoded when the disconnect screen is shown.
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct DisconnectPacket {
+  /** The reason code of the disconnect, shown in telemetry. */
+  pub reason: i32,
+  pub hide_disconnect_screen: bool,
+  pub message: String,
+  pub filtered_message: String,
+}
+
+impl BinaryCodec for DisconnectPacket {
+  fn encode(&self, stream: &mut BinaryStream) -> Result<(), BinaryError> {
+    codec::var_int::encode(&self.reason, stream)?;
+    self.hide_disconnect_screen.encode(stream)?;
+
+    if !self.hide_disconnect_screen {
+      self.message.encode(stream)?;
+      self.filtered_message.encode(stream)?;
+    }
+
+    Ok(())
+  }
+
+  fn decode(stream: &mut BinaryStream) -> Result<Self, BinaryError> {
+    let reason = codec::var_int::decode(stream)?;
+    let hide_disconnect_screen = bool::decode(stream)?;
+
+    let (message, filtered_message) = match hide_disconnect_screen {
+      true => (String::new(), String::new()),
+      false => (String::decode(stream)?, String::decode(stream)?)
+    };
+
+    Ok(DisconnectPacket { reason, hide_disconnect_screen, message, filtered_message })
+  }
+}
@@ -0,0 +1,103 @@
+use crate::protocol::binary::codec::BinaryCodec;
+use crate::protocol::binary::error::BinaryError;
+use crate::protocol::binary::stream::BinaryStream;
+
+use super::{NetworkPacket, PacketHeader};
+
+/**
+ * The direction a packet travels in.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PacketDirection {
+  /** Sent by the client to the server. */
+  Serverbound,
+
+  /** Sent by the server to the client. */
+  Clientbound,
+}
+
+impl PacketDirection {
+  /**
+   * Gets the `NetworkBound` SerenityJS uses for the direction.
+  */
+  pub fn bound(&self) -> &'static str {
+    match self {
+      PacketDirection::Serverbound => "server",
+      PacketDirection::Clientbound => "client",
+    }
+  }
+}
+
+/**
+ * What a packet handler wants to happen to the packet.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PacketAction {
+  /** Lets the packet through, with any changes the handler made to it. */
+  Pass,
+
+  /** Drops the packet. */
+  Cancel,
+}
+
+/**
+ * The outcome of running a handler on an encoded packet.
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub enum Interception {
+  /** The packet was passed on without changes, so the original bytes can be kept. */
+  Unchanged,
+
+  /**
+   * The packet was passed on after the handler changed it, re-encoded with its original header.
+   * Any bytes following the fields the definition knows of are kept at the end.
+  */
+  Modified(Vec<u8>),
+
+  Cancelled,
+}
+
+/**
+ * Decodes the packet with its header, runs the handler on it, and re-encodes it if the handler changed it.
+ * Changes are detected on the encoded packet, so fields the definition does not know of never count as changes.
+*/
+pub fn intercept<P, F>(bytes: &[u8], handler: F) -> Result<Interception, BinaryError>
+where
+  P: NetworkPacket,
+  F: FnOnce(&mut P) -> PacketAction
+{
+  let mut stream = BinaryStream::from_bytes(bytes);
+
+  // Decode the packet, keeping the header and the bytes its definition does not cover
+  let header = PacketHeader::decode(&mut stream)?;
+  if header.id != P::ID {
+    return Err(BinaryError::UnexpectedPacket { expected: P::ID, found: header.id });
+  }
+  let mut packet = P::decode(&mut stream)?;
+  let trailing = stream.remaining();
+  let before = encode_payload(&packet)?;
+
+  if handler(&mut packet) == PacketAction::Cancel {
+    return Ok(Interception::Cancelled);
+  }
+
+  let after = encode_payload(&packet)?;
+  if after == before {
+    return Ok(Interception::Unchanged);
+  }
+
+  // Re-encode the packet between its original header and trailing bytes
+  let mut stream = BinaryStream::new();
+  header.encode(&mut stream)?;
+  stream.write_bytes(&after);
+  stream.write_bytes(trailing);
+
+  Ok(Interception::Modified(stream.into_bytes()))
+}
+
+fn encode_payload<P: BinaryCodec>(packet: &P) -> Result<Vec<u8>, BinaryError> {
+  let mut stream = BinaryStream::new();
+  packet.encode(&mut stream)?;
+
+  Ok(stream.into_bytes())
+}
@@ -0,0 +1,250 @@
+use crate::protocol::binary::codec::BinaryCodec;
+use crate::protocol::binary::error::BinaryError;
+use crate::protocol::binary::stream::BinaryStream;
+
+pub mod boss_event;
+pub mod disconnect;
+pub mod intercept;
+pub mod modal_form_request;
+pub mod modal_form_response;
+pub mod move_player;
+pub mod play_sound;
+pub mod set_score;
+pub mod set_title;
+pub mod spawn_particle_effect;
+pub mod text;
+pub mod transfer;
+pub mod update_block;
+
+use boss_event::BossEventPacket;
+use disconnect::DisconnectPacket;
+use modal_form_request::ModalFormRequestPacket;
+use modal_form_response::ModalFormResponsePacket;
+use move_player::MovePlayerPacket;
+use play_sound::PlaySoundPacket;
+use set_score::SetScorePacket;
+use set_title::SetTitlePacket;
+use spawn_particle_effect::SpawnParticleEffectPacket;
+use text::TextPacket;
+use transfer::TransferPacket;
+use update_block::UpdateBlockPacket;
+
+/**
+ * The packet id is kept in the low 10 bits of the header, the rest holds the split screen sub client ids.
+*/
+const PACKET_ID_MASK: u32 = 0x3ff;
+const SENDER_SUB_CLIENT_SHIFT: u32 = 10;
+const TARGET_SUB_CLIENT_SHIFT: u32 = 12;
+const SUB_CLIENT_MASK: u32 = 0x3;
+
+/**
+ * The VarInt header every game packet starts with.
+*/
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PacketHeader {
+  pub id: u32,
+
+  /** The split screen sub client that sent the packet, 0 for the main client. */
+  pub sender_sub_client: u8,
+
+  /** The split screen sub client the packet is sent to, 0 for the main client. */
+  pub target_sub_client: u8,
+}
+
+impl BinaryCodec for PacketHeader {
+  fn encode(&self, stream: &mut BinaryStream) -> Result<(), BinaryError> {
+    let sender = (self.sender_sub_client as u32 & SUB_CLIENT_MASK) << SENDER_SUB_CLIENT_SHIFT;
+    let target = (self.target_sub_client as u32 & SUB_CLIENT_MASK) << TARGET_SUB_CLIENT_SHIFT;
+
+    stream.write_var_u32((self.id & PACKET_ID_MASK) | sender | target);
+
+    Ok(())
+  }
+
+  fn decode(stream: &mut BinaryStream) -> Result<Self, BinaryError> {
+    let header = stream.read_var_u32()?;
+
+    Ok(PacketHeader {
+      id: header & PACKET_ID_MASK,
+      sender_sub_client: ((header >> SENDER_SUB_CLIENT_SHIFT) & SUB_CLIENT_MASK) as u8,
+      target_sub_client: ((header >> TARGET_SUB_CLIENT_SHIFT) & SUB_CLIENT_MASK) as u8,
+    })
+  }
+}
+
+/**
+ * A Bedrock game packet with a known id.
+ * The encoded packet starts with a VarInt header holding the id, followed by the fields.
+*/
+pub trait NetworkPacket: BinaryCodec {
+  const ID: u32;
+
+  /**
+   * Encodes the packet with its header, failing if a field is too long to be written.
+  */
+  fn to_bytes(&self) -> Result<Vec<u8>, BinaryError> {
+    let mut stream = BinaryStream::new();
+    PacketHeader { id: Self::ID, ..PacketHeader::default() }.encode(&mut stream)?;
+    self.encode(&mut stream)?;
+
+    Ok(stream.into_bytes())
+  }
+
+  /**
+   * Decodes the packet with its header, failing if the header holds a different id.
+  */
+  fn from_bytes(bytes: &[u8]) -> Result<Self, BinaryError> {
+    let mut stream = BinaryStream::from_bytes(bytes);
+
+    let found = PacketHeader::decode(&mut stream)?.id;
+    match found == Self::ID {
+      true => Self::decode(&mut stream),
+      false => Err(BinaryError::UnexpectedPacket { expected: Self::ID, found })
+    }
+  }
+}
+
+macro_rules! packets {
+  ($($id:literal => $variant:ident($packet:ty),)*) => {
+    $(
+      impl NetworkPacket for $packet {
+        const ID: u32 = $id;
+      }
+
+      impl From<$packet> for PacketBody {
+        fn from(packet: $packet) -> Self {
+          PacketBody::$variant(packet)
+        }
+      }
+
+      impl From<$packet> for Packet {
+        fn from(packet: $packet) -> Self {
+          Packet::new(PacketBody::$variant(packet))
+        }
+      }
+    )*
+
+    /**
+     * The fields of any game packet, dispatched by the id in its header.
+     * Packets without a definition are kept as their undecoded payload.
+    */
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum PacketBody {
+      $($variant($packet),)*
+      Unknown { id: u32, payload: Vec<u8> },
+    }
+
+    impl PacketBody {
+      pub fn id(&self) -> u32 {
+        match self {
+          $(PacketBody::$variant(_) => $id,)*
+          PacketBody::Unknown { id, .. } => *id,
+        }
+      }
+
+      /**
+       * Decodes the payload of the packet with the id.
+      */
+      pub fn decode_payload(id: u32, stream: &mut BinaryStream) -> Result<PacketBody, BinaryError> {
+        match id {
+          $($id => Ok(PacketBody::$variant(<$packet>::decode(stream)?)),)*
+          id => Ok(PacketBody::Unknown { id, payload: stream.read_remaining().to_vec() }),
+        }
+      }
+
+      /**
+       * Encodes the payload of the packet, without its header.
+      */
+      pub fn encode_payload(&self, stream: &mut BinaryStream) -> Result<(), BinaryError> {
+        match self {
+          $(PacketBody::$variant(packet) => packet.encode(stream),)*
+          PacketBody::Unknown { payload, .. } => {
+            stream.write_bytes(payload);
+
+            Ok(())
+          },
+        }
+      }
+    }
+  };
+}
+
+packets! {
+  5 => Disconnect(DisconnectPacket),
+  9 => Text(TextPacket),
+  19 => MovePlayer(MovePlayerPacket),
+  21 => UpdateBlock(UpdateBlockPacket),
+  74 => BossEvent(BossEventPacket),
+  85 => Transfer(TransferPacket),
+  86 => PlaySound(PlaySoundPacket),
+  88 => SetTitle(SetTitlePacket),
+  100 => ModalFormRequest(ModalFormRequestPacket),
+  101 => ModalFormResponse(ModalFormResponsePacket),
+  108 => SetScore(SetScorePacket),
+  118 => SpawnParticleEffect(SpawnParticleEffectPacket),
+}
+
+/**
+ * Any game packet, together with the split screen sub clients from its header.
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct Packet {
+  /** The sub client that sent the packet, 0 for the main client. */
+  pub sender_sub_client: u8,
+
+  /** The sub client the packet is sent to, 0 for the main client. */
+  pub target_sub_client: u8,
+
+  pub body: PacketBody,
+}
+
+impl Packet {
+  /**
+   * Creates the packet for the main client.
+  */
+  pub fn new(body: PacketBody) -> Self {
+    Packet { sender_sub_client: 0, target_sub_client: 0, body }
+  }
+
+  pub fn id(&self) -> u32 {
+    self.body.id()
+  }
+
+  /**
+   * Gets the header the packet is encoded with.
+  */
+  pub fn header(&self) -> PacketHeader {
+    PacketHeader { id: self.id(), sender_sub_client: self.sender_sub_client, target_sub_client: self.target_sub_client }
+  }
+
+  /**
+   * Decodes a packet with its header, packets without a definition become `PacketBody::Unknown`.
+  */
+  pub fn from_bytes(bytes: &[u8]) -> Result<Packet, BinaryError> {
+    Packet::decode(&mut BinaryStream::from_bytes(bytes))
+  }
+
+  /**
+   * Encodes the packet with its header, failing if a field is too long to be written.
+  */
+  pub fn to_bytes(&self) -> Result<Vec<u8>, BinaryError> {
+    let mut stream = BinaryStream::new();
+    self.encode(&mut stream)?;
+
+    Ok(stream.into_bytes())
+  }
+}
+
+impl BinaryCodec for Packet {
+  fn encode(&self, stream: &mut BinaryStream) -> Result<(), BinaryError> {
+    self.header().encode(stream)?;
+    self.body.encode_payload(stream)
+  }
+
+  fn decode(stream: &mut BinaryStream) -> Result<Self, BinaryError> {
+    let header = PacketHeader::decode(stream)?;
+    let body = PacketBody::decode_payload(header.id, stream)?;
+
+    Ok(Packet { sender_sub_client: header.sender_sub_client, target_sub_client: header.target_sub_client, body })
+  }
+}
@@ -0,0 +1,13 @@
+use crate::BinaryCodec;
+
+/**
+ * Shows a form to the player.
+*/
+#[derive(Debug, Clone, PartialEq, BinaryCodec)]
+pub struct ModalFormRequestPacket {
+  #[codec(var_u32)]
+  pub form_id: u32,
+
+  /** The form, as JSON. */
+  pub form_data: String,
+}
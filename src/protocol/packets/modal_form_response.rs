@@ -0,0 +1,24 @@
+use crate::BinaryCodec;
+
+/**
+ * Why a form was closed without a response.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, BinaryCodec)]
+pub enum FormCancelReason {
+  UserClosed = 0,
+  UserBusy = 1,
+}
+
+/**
+ * The answer of the player to a form.
+*/
+#[derive(Debug, Clone, PartialEq, BinaryCodec)]
+pub struct ModalFormResponsePacket {
+  #[codec(var_u32)]
+  pub form_id: u32,
+
+  /** The response, as JSON, `None` when the form was cancelled. */
+  pub response_data: Option<String>,
+
+  pub cancel_reason: Option<FormCancelReason>,
+}
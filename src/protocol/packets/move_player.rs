@@ -0,0 +1,91 @@
+use crate::protocol::binary::codec::{self, BinaryCodec};
+use crate::protocol::binary::error::BinaryError;
+use crate::protocol::binary::stream::BinaryStream;
+use crate::protocol::types::vector3f::Vector3f;
+use crate::BinaryCodec;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, BinaryCodec)]
+pub enum MoveMode {
+  Normal = 0,
+  Reset = 1,
+  Teleport = 2,
+  Rotation = 3,
+}
+
+/**
+ * Moves a player, sent by the client for its own movement and by the server for teleports.
+ * The teleport cause and source are only encoded in the teleport mode.
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct MovePlayerPacket {
+  /** The runtime id of the player. */
+  pub runtime_id: u64,
+
+  /** The position of the eyes of the player. */
+  pub position: Vector3f,
+
+  pub pitch: f32,
+  pub yaw: f32,
+  pub head_yaw: f32,
+  pub mode: MoveMode,
+  pub on_ground: bool,
+
+  /** The runtime id of the entity the player rides, or 0. */
+  pub riding_runtime_id: u64,
+
+  pub teleport_cause: i32,
+  pub teleport_source_type: i32,
+  pub tick: u64,
+}
+
+impl BinaryCodec for MovePlayerPacket {
+  fn encode(&self, stream: &mut BinaryStream) -> Result<(), BinaryError> {
+    codec::var_u64::encode(&self.runtime_id, stream)?;
+    self.position.encode(stream)?;
+    self.pitch.encode(stream)?;
+    self.yaw.encode(stream)?;
+    self.head_yaw.encode(stream)?;
+    self.mode.encode(stream)?;
+    self.on_ground.encode(stream)?;
+    codec::var_u64::encode(&self.riding_runtime_id, stream)?;
+
+    if self.mode == MoveMode::Teleport {
+      self.teleport_cause.encode(stream)?;
+      self.teleport_source_type.encode(stream)?;
+    }
+
+    codec::var_u64::encode(&self.tick, stream)?;
+
+    Ok(())
+  }
+
+  fn decode(stream: &mut BinaryStream) -> Result<Self, BinaryError> {
+    let runtime_id = codec::var_u64::decode(stream)?;
+    let position = Vector3f::decode(stream)?;
+    let pitch = f32::decode(stream)?;
+    let yaw = f32::decode(stream)?;
+    let head_yaw = f32::decode(stream)?;
+    let mode = MoveMode::decode(stream)?;
+    let on_ground = bool::decode(stream)?;
+    let riding_runtime_id = codec::var_u64::decode(stream)?;
+
+    let (teleport_cause, teleport_source_type) = match mode {
+      MoveMode::Teleport => (i32::decode(stream)?, i32::decode(stream)?),
+      _ => (0, 0)
+    };
+
+    Ok(MovePlayerPacket {
+      runtime_id,
+      position,
+      pitch,
+      yaw,
+      head_yaw,
+      mode,
+      on_ground,
+      riding_runtime_id,
+      teleport_cause,
+      teleport_source_type,
+      tick: codec::var_u64::decode(stream)?,
+    })
+  }
+}
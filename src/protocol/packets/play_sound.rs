@@ -0,0 +1,37 @@
+use crate::protocol::types::block_position::BlockPosition;
+use crate::protocol::types::vector3f::Vector3f;
+use crate::BinaryCodec;
+
+/**
+ * Plays a sound at a position.
+*/
+#[derive(Debug, Clone, PartialEq, BinaryCodec)]
+pub struct PlaySoundPacket {
+  pub sound_name: String,
+
+  /** The position of the sound in eighths of a block, see `location`. */
+  #[codec(network_block_position)]
+  pub position: BlockPosition,
+
+  pub volume: f32,
+  pub pitch: f32,
+}
+
+impl PlaySoundPacket {
+  pub fn new(sound_name: &str, location: Vector3f, volume: f32, pitch: f32) -> Self {
+    let position = BlockPosition {
+      x: (location.x * 8.0) as i32,
+      y: (location.y * 8.0) as i32,
+      z: (location.z * 8.0) as i32,
+    };
+
+    PlaySoundPacket { sound_name: sound_name.to_string(), position, volume, pitch }
+  }
+
+  /**
+   * Gets the position of the sound in blocks.
+  */
+  pub fn location(&self) -> Vector3f {
+    Vector3f::from(self.position) / 8.0
+  }
+}
@@ -0,0 +1,53 @@
+use crate::BinaryCodec;
+
+/**
+ * Who a score belongs to.
+*/
+#[derive(Debug, Clone, PartialEq, BinaryCodec)]
+pub enum ScoreIdentity {
+  #[codec(id = 1)]
+  Player {
+    #[codec(var_long)]
+    unique_id: i64,
+  },
+  Entity {
+    #[codec(var_long)]
+    unique_id: i64,
+  },
+  FakePlayer {
+    name: String,
+  },
+}
+
+#[derive(Debug, Clone, PartialEq, BinaryCodec)]
+pub struct ScoreEntry {
+  #[codec(var_long)]
+  pub scoreboard_id: i64,
+  pub objective_name: String,
+  pub score: i32,
+  pub identity: ScoreIdentity,
+}
+
+/**
+ * A score to remove, which does not repeat who it belonged to.
+*/
+#[derive(Debug, Clone, PartialEq, BinaryCodec)]
+pub struct ScoreRemoval {
+  #[codec(var_long)]
+  pub scoreboard_id: i64,
+  pub objective_name: String,
+  pub score: i32,
+}
+
+/**
+ * Changes or removes scores shown on the scoreboard.
+*/
+#[derive(Debug, Clone, PartialEq, BinaryCodec)]
+pub enum SetScorePacket {
+  Change {
+    entries: Vec<ScoreEntry>,
+  },
+  Remove {
+    entries: Vec<ScoreRemoval>,
+  },
+}
@@ -0,0 +1,36 @@
+use crate::BinaryCodec;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, BinaryCodec)]
+#[codec(tag = var_int)]
+pub enum TitleType {
+  Clear = 0,
+  Reset = 1,
+  Title = 2,
+  Subtitle = 3,
+  ActionBar = 4,
+  Durations = 5,
+  TitleJson = 6,
+  SubtitleJson = 7,
+  ActionBarJson = 8,
+}
+
+/**
+ * Shows, clears or times the title, subtitle and action bar of the player.
+*/
+#[derive(Debug, Clone, PartialEq, BinaryCodec)]
+pub struct SetTitlePacket {
+  pub title_type: TitleType,
+  pub text: String,
+
+  /** The durations of the title, in ticks. */
+  #[codec(var_int)]
+  pub fade_in: i32,
+  #[codec(var_int)]
+  pub stay: i32,
+  #[codec(var_int)]
+  pub fade_out: i32,
+
+  pub xuid: String,
+  pub platform_online_id: String,
+  pub filtered_text: String,
+}
@@ -0,0 +1,21 @@
+use crate::protocol::types::vector3f::Vector3f;
+use crate::BinaryCodec;
+
+/**
+ * Spawns a particle effect, optionally attached to an actor.
+*/
+#[derive(Debug, Clone, PartialEq, BinaryCodec)]
+pub struct SpawnParticleEffectPacket {
+  /** The dimension id, 0 for the overworld, 1 for the nether and 2 for the end. */
+  pub dimension: u8,
+
+  /** The unique id of the actor to attach the effect to, or -1. */
+  #[codec(var_long)]
+  pub actor_unique_id: i64,
+
+  pub position: Vector3f,
+  pub particle_name: String,
+
+  /** The Molang variables of the effect, as JSON. */
+  pub molang_variables: Option<String>,
+}
@@ -0,0 +1,104 @@
+use crate::protocol::binary::codec::BinaryCodec;
+use crate::protocol::binary::error::BinaryError;
+use crate::protocol::binary::stream::BinaryStream;
+use crate::BinaryCodec;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, BinaryCodec)]
+pub enum TextType {
+  Raw = 0,
+  Chat = 1,
+  Translation = 2,
+  Popup = 3,
+  JukeboxPopup = 4,
+  Tip = 5,
+  System = 6,
+  Whisper = 7,
+  Announcement = 8,
+  JsonWhisper = 9,
+  Json = 10,
+  JsonAnnouncement = 11,
+}
+
+impl TextType {
+  /**
+   * Checks if messages of the type are sent with the name of their source.
+  */
+  pub fn has_source(&self) -> bool {
+    matches!(self, TextType::Chat | TextType::Whisper | TextType::Announcement)
+  }
+
+  /**
+   * Checks if messages of the type are sent with translation parameters.
+  */
+  pub fn has_parameters(&self) -> bool {
+    matches!(self, TextType::Translation | TextType::Popup | TextType::JukeboxPopup)
+  }
+}
+
+/**
+ * A chat message, or any other text shown to the player.
+ * The source name is only encoded for chat, whisper and announcement messages,
+ * and the parameters only for translation, popup and jukebox popup messages.
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextPacket {
+  pub text_type: TextType,
+  pub needs_translation: bool,
+  pub source_name: String,
+  pub message: String,
+  pub parameters: Vec<String>,
+  pub xuid: String,
+  pub platform_chat_id: String,
+  pub filtered_message: String,
+}
+
+impl BinaryCodec for TextPacket {
+  fn encode(&self, stream: &mut BinaryStream) -> Result<(), BinaryError> {
+    self.text_type.encode(stream)?;
+    self.needs_translation.encode(stream)?;
+
+    if self.text_type.has_source() {
+      self.source_name.encode(stream)?;
+    }
+
+    self.message.encode(stream)?;
+
+    if self.text_type.has_parameters() {
+      self.parameters.encode(stream)?;
+    }
+
+    self.xuid.encode(stream)?;
+    self.platform_chat_id.encode(stream)?;
+    self.filtered_message.encode(stream)?;
+
+    Ok(())
+  }
+
+  fn decode(stream: &mut BinaryStream) -> Result<Self, BinaryError> {
+    let text_type = TextType::decode(stream)?;
+    let needs_translation = bool::decode(stream)?;
+
+    let source_name = match text_type.has_source() {
+      true => String::decode(stream)?,
+      false => String::new()
+    };
+
+    let message = String::decode(stream)?;
+
+    let parameters = match text_type.has_parameters() {
+      true => Vec::decode(stream)?,
+      false => Vec::new()
+    };
+
+    Ok(TextPacket {
+      text_type,
+      needs_translation,
+      source_name,
+      message,
+      parameters,
+      xuid: String::decode(stream)?,
+      platform_chat_id: String::decode(stream)?,
+      filtered_message: String::decode(stream)?,
+    })
+  }
+}
@@ -0,0 +1,11 @@
+use crate::BinaryCodec;
+
+/**
+ * Transfers the player to another server.
+*/
+#[derive(Debug, Clone, PartialEq, BinaryCodec)]
+pub struct TransferPacket {
+  pub address: String,
+  pub port: u16,
+  pub reload_world: bool,
+}
@@ -0,0 +1,31 @@
+use crate::protocol::types::block_position::BlockPosition;
+use crate::BinaryCodec;
+
+/** Updates the neighbors of the block, a flag of `UpdateBlockPacket`. */
+pub const UPDATE_BLOCK_NEIGHBORS: u32 = 0b001;
+
+/** Sends the update to the other players, a flag of `UpdateBlockPacket`. */
+pub const UPDATE_BLOCK_NETWORK: u32 = 0b010;
+
+/** Skips rerendering the block, a flag of `UpdateBlockPacket`. */
+pub const UPDATE_BLOCK_NO_GRAPHICS: u32 = 0b100;
+
+/**
+ * Sets a single block on the client.
+*/
+#[derive(Debug, Clone, PartialEq, BinaryCodec)]
+pub struct UpdateBlockPacket {
+  #[codec(network_block_position)]
+  pub position: BlockPosition,
+
+  /** The runtime id of the block permutation. */
+  #[codec(var_u32)]
+  pub block_runtime_id: u32,
+
+  #[codec(var_u32)]
+  pub flags: u32,
+
+  /** The layer of the block, 0 for the block itself and 1 for the liquid in it. */
+  #[codec(var_u32)]
+  pub layer: u32,
+}
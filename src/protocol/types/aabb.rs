@@ -0,0 +1,132 @@
+use super::vector3f::Vector3f;
+
+/**
+ * An axis-aligned bounding box, spanning from the min to the max corner.
+*/
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Aabb {
+  pub min: Vector3f,
+  pub max: Vector3f,
+}
+
+impl Aabb {
+  /**
+   * Creates the box spanning between the two corners, in any order.
+  */
+  pub fn new(a: Vector3f, b: Vector3f) -> Self {
+    Aabb {
+      min: Vector3f { x: a.x.min(b.x), y: a.y.min(b.y), z: a.z.min(b.z) },
+      max: Vector3f { x: a.x.max(b.x), y: a.y.max(b.y), z: a.z.max(b.z) },
+    }
+  }
+
+  /**
+   * Creates the box of the given size around the center.
+  */
+  pub fn from_center(center: Vector3f, size: Vector3f) -> Self {
+    Aabb::new(center - size / 2.0, center + size / 2.0)
+  }
+
+  pub fn size(&self) -> Vector3f {
+    self.max - self.min
+  }
+
+  pub fn center(&self) -> Vector3f {
+    self.min.lerp(self.max, 0.5)
+  }
+
+  pub fn volume(&self) -> f64 {
+    let size = self.size();
+
+    size.x * size.y * size.z
+  }
+
+  /**
+   * Checks if the point lies within the box, points on the surface included.
+  */
+  pub fn contains(&self, point: Vector3f) -> bool {
+    point.x >= self.min.x && point.x <= self.max.x
+      && point.y >= self.min.y && point.y <= self.max.y
+      && point.z >= self.min.z && point.z <= self.max.z
+  }
+
+  /**
+   * Checks if the two boxes overlap, boxes that only touch do not.
+  */
+  pub fn intersects(&self, other: &Aabb) -> bool {
+    self.min.x < other.max.x && self.max.x > other.min.x
+      && self.min.y < other.max.y && self.max.y > other.min.y
+      && self.min.z < other.max.z && self.max.z > other.min.z
+  }
+
+  /**
+   * Grows the box by the amount on every side, a negative amount shrinks it.
+  */
+  pub fn expand(&self, amount: f64) -> Aabb {
+    let amount = Vector3f { x: amount, y: amount, z: amount };
+
+    Aabb::new(self.min - amount, self.max + amount)
+  }
+
+  /**
+   * Gets the smallest box containing both boxes.
+  */
+  pub fn union(&self, other: &Aabb) -> Aabb {
+    Aabb {
+      min: Vector3f { x: self.min.x.min(other.min.x), y: self.min.y.min(other.min.y), z: self.min.z.min(other.min.z) },
+      max: Vector3f { x: self.max.x.max(other.max.x), y: self.max.y.max(other.max.y), z: self.max.z.max(other.max.z) },
+    }
+  }
+
+  /**
+   * Gets the overlap of the two boxes, if they intersect.
+  */
+  pub fn intersection(&self, other: &Aabb) -> Option<Aabb> {
+    if !self.intersects(other) {
+      return None;
+    }
+
+    Some(Aabb {
+      min: Vector3f { x: self.min.x.max(other.min.x), y: self.min.y.max(other.min.y), z: self.min.z.max(other.min.z) },
+      max: Vector3f { x: self.max.x.min(other.max.x), y: self.max.y.min(other.max.y), z: self.max.z.min(other.max.z) },
+    })
+  }
+
+  /**
+   * Gets where the ray enters the box, as the multiple of the direction travelled from the origin.
+   * A ray starting inside the box hits it at 0, `None` is returned when the ray misses the box.
+  */
+  pub fn intersect_ray(&self, origin: Vector3f, direction: Vector3f) -> Option<f64> {
+    let mut enter = 0.0_f64;
+    let mut exit = f64::INFINITY;
+
+    // Clip the ray against the slab of every axis
+    let axes = [
+      (origin.x, direction.x, self.min.x, self.max.x),
+      (origin.y, direction.y, self.min.y, self.max.y),
+      (origin.z, direction.z, self.min.z, self.max.z),
+    ];
+
+    for (origin, direction, min, max) in axes {
+      if direction == 0.0 {
+        // A ray parallel to the slab has to start within it
+        if origin < min || origin > max {
+          return None;
+        }
+
+        continue;
+      }
+
+      let a = (min - origin) / direction;
+      let b = (max - origin) / direction;
+
+      enter = enter.max(a.min(b));
+      exit = exit.min(a.max(b));
+      if enter > exit {
+        return None;
+      }
+    }
+
+    Some(enter)
+  }
+}
@@ -0,0 +1,67 @@
+use super::block_position::BlockPosition;
+
+/**
+ * A face of a block, using the ids the protocol uses for faces.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BlockFace {
+  Down = 0,
+  Up = 1,
+  North = 2,
+  South = 3,
+  West = 4,
+  East = 5,
+}
+
+impl BlockFace {
+  pub const ALL: [BlockFace; 6] = [
+    BlockFace::Down,
+    BlockFace::Up,
+    BlockFace::North,
+    BlockFace::South,
+    BlockFace::West,
+    BlockFace::East,
+  ];
+
+  /**
+   * Gets the face from its protocol id.
+  */
+  pub fn from_id(id: i32) -> Option<BlockFace> {
+    BlockFace::ALL.into_iter().find(|face| face.id() == id)
+  }
+
+  /**
+   * Gets the protocol id of the face.
+  */
+  pub fn id(&self) -> i32 {
+    *self as i32
+  }
+
+  /**
+   * Gets the face on the other side of the block.
+  */
+  pub fn opposite(&self) -> BlockFace {
+    match self {
+      BlockFace::Down => BlockFace::Up,
+      BlockFace::Up => BlockFace::Down,
+      BlockFace::North => BlockFace::South,
+      BlockFace::South => BlockFace::North,
+      BlockFace::West => BlockFace::East,
+      BlockFace::East => BlockFace::West,
+    }
+  }
+
+  /**
+   * Gets the unit offset pointing out of the face.
+  */
+  pub fn direction(&self) -> BlockPosition {
+    match self {
+      BlockFace::Down => BlockPosition::DOWN,
+      BlockFace::Up => BlockPosition::UP,
+      BlockFace::North => BlockPosition::NORTH,
+      BlockFace::South => BlockPosition::SOUTH,
+      BlockFace::West => BlockPosition::WEST,
+      BlockFace::East => BlockPosition::EAST,
+    }
+  }
+}
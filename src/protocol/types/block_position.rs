@@ -1,10 +1,18 @@
-use napi::{bindgen_prelude::FromNapiValue, NapiValue};
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use napi::bindgen_prelude::FromNapiValue;
 use napi_derive::napi;
 
+use crate::error::error::SerenityError;
 use crate::utils::node_converter::*;
 
+use super::block_face::BlockFace;
+use super::vector3f::Vector3f;
+
+const WRAPPER: &str = "BlockPosition";
+
 #[napi]
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct BlockPosition {
   pub x: i32,
   pub y: i32,
@@ -12,51 +20,154 @@ pub struct BlockPosition {
 }
 
 impl BlockPosition {
-  pub fn new(object: napi::JsObject) -> Self {
-    // Get the x object
-    let x_object = get_node_number(&object, "x").unwrap();
-    let x = x_object.get_int32().unwrap();
+  pub const ZERO: BlockPosition = BlockPosition { x: 0, y: 0, z: 0 };
+  pub const UP: BlockPosition = BlockPosition { x: 0, y: 1, z: 0 };
+  pub const DOWN: BlockPosition = BlockPosition { x: 0, y: -1, z: 0 };
+  pub const NORTH: BlockPosition = BlockPosition { x: 0, y: 0, z: -1 };
+  pub const SOUTH: BlockPosition = BlockPosition { x: 0, y: 0, z: 1 };
+  pub const WEST: BlockPosition = BlockPosition { x: -1, y: 0, z: 0 };
+  pub const EAST: BlockPosition = BlockPosition { x: 1, y: 0, z: 0 };
 
-    // Get the y object
-    let y_object = get_node_number(&object, "y").unwrap();
-    let y = y_object.get_int32().unwrap();
+  /**
+   * Creates the position from its coordinates.
+  */
+  pub const fn new(x: i32, y: i32, z: i32) -> Self {
+    BlockPosition { x, y, z }
+  }
 
-    // Get the z object
-    let z_object = get_node_number(&object, "z").unwrap();
-    let z = z_object.get_int32().unwrap();
+  /**
+   * Reads the position from a JS object with x, y and z properties.
+  */
+  pub fn from_js_object(object: napi::JsObject) -> Result<Self, SerenityError> {
+    // Get the x value
+    let x = get_node_number(&object, WRAPPER, "x")?;
 
-    BlockPosition { x, y, z }
+    // Get the y value
+    let y = get_node_number(&object, WRAPPER, "y")?;
+
+    // Get the z value
+    let z = get_node_number(&object, WRAPPER, "z")?;
+
+    Ok(BlockPosition { x, y, z })
   }
-}
 
-impl BlockPosition {
-  pub fn to_js_object(&self, env: napi::Env) -> napi::JsObject {
-    let mut object = create_node_object(&env).unwrap();
+  /**
+   * Creates a JS object with the x, y and z properties of the position.
+  */
+  pub fn to_js_object(&self, env: napi::Env) -> Result<napi::JsObject, SerenityError> {
+    let mut object = create_node_object(&env, WRAPPER)?;
+
+    let x = create_node_number(&env, WRAPPER, self.x)?;
+    let y = create_node_number(&env, WRAPPER, self.y)?;
+    let z = create_node_number(&env, WRAPPER, self.z)?;
+
+    set_node_property(&mut object, WRAPPER, "x", x)?;
+    set_node_property(&mut object, WRAPPER, "y", y)?;
+    set_node_property(&mut object, WRAPPER, "z", z)?;
 
-    let x = create_node_number(&env, self.x).unwrap();
-    let y = create_node_number(&env, self.y).unwrap();
-    let z = create_node_number(&env, self.z).unwrap();
+    Ok(object)
+  }
+
+  /**
+   * Gets the position of the block adjacent to the face of this block.
+  */
+  pub fn offset(&self, face: BlockFace) -> BlockPosition {
+    *self + face.direction()
+  }
+
+  /**
+   * Gets the six blocks sharing a face with this block, in the order of `BlockFace::ALL`.
+  */
+  pub fn neighbors(&self) -> [BlockPosition; 6] {
+    BlockFace::ALL.map(|face| self.offset(face))
+  }
+
+  /**
+   * Gets the sum of the distances along each axis.
+  */
+  pub fn manhattan_distance(&self, other: BlockPosition) -> u32 {
+    self.x.abs_diff(other.x) + self.y.abs_diff(other.y) + self.z.abs_diff(other.z)
+  }
 
-    object.set_named_property("x", x).unwrap();
-    object.set_named_property("y", y).unwrap();
-    object.set_named_property("z", z).unwrap();
+  /**
+   * Gets the center of the block.
+  */
+  pub fn center(&self) -> Vector3f {
+    Vector3f::from(*self) + Vector3f { x: 0.5, y: 0.5, z: 0.5 }
+  }
 
-    object
+  /**
+   * Divides every axis, rounding towards negative infinity so negative positions map to the right cell,
+   * e.g. the block at x = -1 lies in the chunk at x = -1 rather than 0.
+   *
+   * # Panics
+   * Panics when the divisor is zero.
+  */
+  pub fn div_floor(&self, divisor: i32) -> BlockPosition {
+    BlockPosition { x: div_floor(self.x, divisor), y: div_floor(self.y, divisor), z: div_floor(self.z, divisor) }
   }
 }
 
 impl FromNapiValue for BlockPosition {
   unsafe fn from_napi_value(env: napi::sys::napi_env, value: napi::sys::napi_value) -> napi::Result<Self> {
     // Create the JsObject from the napi_value
-    let object = match napi::JsObject::from_raw(env.clone(), value.clone()) {
-      Ok(obj) => obj,
-      Err(e) => return Err(napi::Error::new(
-        napi::Status::GenericFailure,
-        e.to_string()
-      ))
-    };
+    let object = object_from_raw(env, value, WRAPPER)?;
 
     // Return the BlockPosition instance
-    Ok(BlockPosition::new(object))
+    Ok(BlockPosition::from_js_object(object)?)
+  }
+}
+
+fn div_floor(value: i32, divisor: i32) -> i32 {
+  let quotient = value / divisor;
+
+  // Truncating division rounds towards zero, which is up when the signs differ
+  match value % divisor != 0 && (value < 0) != (divisor < 0) {
+    true => quotient - 1,
+    false => quotient
   }
-}
\ No newline at end of file
+}
+
+impl Add for BlockPosition {
+  type Output = BlockPosition;
+
+  fn add(self, other: BlockPosition) -> BlockPosition {
+    BlockPosition { x: self.x + other.x, y: self.y + other.y, z: self.z + other.z }
+  }
+}
+
+impl Sub for BlockPosition {
+  type Output = BlockPosition;
+
+  fn sub(self, other: BlockPosition) -> BlockPosition {
+    BlockPosition { x: self.x - other.x, y: self.y - other.y, z: self.z - other.z }
+  }
+}
+
+impl Mul<i32> for BlockPosition {
+  type Output = BlockPosition;
+
+  fn mul(self, scalar: i32) -> BlockPosition {
+    BlockPosition { x: self.x * scalar, y: self.y * scalar, z: self.z * scalar }
+  }
+}
+
+impl Neg for BlockPosition {
+  type Output = BlockPosition;
+
+  fn neg(self) -> BlockPosition {
+    BlockPosition { x: -self.x, y: -self.y, z: -self.z }
+  }
+}
+
+impl AddAssign for BlockPosition {
+  fn add_assign(&mut self, other: BlockPosition) {
+    *self = *self + other;
+  }
+}
+
+impl SubAssign for BlockPosition {
+  fn sub_assign(&mut self, other: BlockPosition) {
+    *self = *self - other;
+  }
+}
@@ -0,0 +1,53 @@
+use super::block_position::BlockPosition;
+
+/**
+ * The position of a block within its chunk column, x and z range from 0 to 15.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ChunkLocalPosition {
+  pub x: u8,
+  pub y: i32,
+  pub z: u8,
+}
+
+impl From<BlockPosition> for ChunkLocalPosition {
+  fn from(position: BlockPosition) -> Self {
+    ChunkLocalPosition { x: (position.x & 15) as u8, y: position.y, z: (position.z & 15) as u8 }
+  }
+}
+
+/**
+ * The position of a block within its sub-chunk, every axis ranges from 0 to 15.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SubChunkLocalPosition {
+  pub x: u8,
+  pub y: u8,
+  pub z: u8,
+}
+
+impl SubChunkLocalPosition {
+  /**
+   * Gets the index of the block in the storage of a sub-chunk, which is ordered x, z, then y.
+  */
+  pub fn index(&self) -> usize {
+    ((self.x as usize) << 8) | ((self.z as usize) << 4) | self.y as usize
+  }
+
+  /**
+   * Gets the position from its index in the storage of a sub-chunk, `None` when out of range.
+  */
+  pub fn from_index(index: usize) -> Option<SubChunkLocalPosition> {
+    if index >= 4096 {
+      return None;
+    }
+
+    Some(SubChunkLocalPosition { x: (index >> 8) as u8, y: (index & 15) as u8, z: ((index >> 4) & 15) as u8 })
+  }
+}
+
+impl From<BlockPosition> for SubChunkLocalPosition {
+  fn from(position: BlockPosition) -> Self {
+    SubChunkLocalPosition { x: (position.x & 15) as u8, y: (position.y & 15) as u8, z: (position.z & 15) as u8 }
+  }
+}
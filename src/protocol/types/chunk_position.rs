@@ -0,0 +1,136 @@
+use super::block_position::BlockPosition;
+use super::chunk_local_position::ChunkLocalPosition;
+use super::vector3f::Vector3f;
+
+/**
+ * The position of a chunk column, every chunk spans 16 by 16 blocks.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ChunkPosition {
+  pub x: i32,
+  pub z: i32,
+}
+
+impl ChunkPosition {
+  /** The number of blocks along the x and z axis of a chunk. */
+  pub const SIZE: i32 = 16;
+
+  pub const ZERO: ChunkPosition = ChunkPosition { x: 0, z: 0 };
+
+  /**
+   * Gets the block at the local position within the chunk, the inverse of `ChunkLocalPosition::from`.
+  */
+  pub fn block_at(&self, local: ChunkLocalPosition) -> BlockPosition {
+    BlockPosition {
+      x: self.x * Self::SIZE + local.x as i32,
+      y: local.y,
+      z: self.z * Self::SIZE + local.z as i32,
+    }
+  }
+
+  /**
+   * Gets the block with the lowest x and z of the chunk at the given height.
+  */
+  pub fn min_block(&self, y: i32) -> BlockPosition {
+    BlockPosition { x: self.x * Self::SIZE, y, z: self.z * Self::SIZE }
+  }
+
+  /**
+   * Checks if the block lies within the chunk.
+  */
+  pub fn contains(&self, position: BlockPosition) -> bool {
+    ChunkPosition::from(position) == *self
+  }
+
+  /**
+   * Gets the squared distance to the other chunk, in chunks.
+  */
+  pub fn distance_squared(&self, other: ChunkPosition) -> i64 {
+    let dx = (self.x - other.x) as i64;
+    let dz = (self.z - other.z) as i64;
+
+    dx * dx + dz * dz
+  }
+
+  /**
+   * Gets the distance to the other chunk, in chunks.
+  */
+  pub fn distance(&self, other: ChunkPosition) -> f64 {
+    (self.distance_squared(other) as f64).sqrt()
+  }
+
+  /**
+   * Checks if the other chunk is within the radius, using the circular view distance Bedrock uses.
+  */
+  pub fn is_within_radius(&self, other: ChunkPosition, radius: i32) -> bool {
+    radius >= 0 && self.distance_squared(other) <= (radius as i64) * (radius as i64)
+  }
+
+  /**
+   * Iterates over the chunks within the radius, row by row from the lowest x and z.
+  */
+  pub fn within_radius(&self, radius: i32) -> ChunksInRadius {
+    ChunksInRadius { center: *self, radius, dx: -radius, dz: -radius }
+  }
+
+  /**
+   * Packs the position into a single key, unique for every chunk.
+  */
+  pub fn key(&self) -> i64 {
+    ((self.x as i64) << 32) | (self.z as u32 as i64)
+  }
+
+  /**
+   * Unpacks a position packed by `key`.
+  */
+  pub fn from_key(key: i64) -> ChunkPosition {
+    ChunkPosition { x: (key >> 32) as i32, z: key as i32 }
+  }
+}
+
+impl From<BlockPosition> for ChunkPosition {
+  fn from(position: BlockPosition) -> Self {
+    ChunkPosition { x: position.x >> 4, z: position.z >> 4 }
+  }
+}
+
+impl From<Vector3f> for ChunkPosition {
+  fn from(position: Vector3f) -> Self {
+    ChunkPosition::from(position.floor())
+  }
+}
+
+/**
+ * An iterator over the chunks within a radius, see `ChunkPosition::within_radius`.
+*/
+#[derive(Debug, Clone)]
+pub struct ChunksInRadius {
+  center: ChunkPosition,
+  radius: i32,
+  dx: i32,
+  dz: i32,
+}
+
+impl Iterator for ChunksInRadius {
+  type Item = ChunkPosition;
+
+  fn next(&mut self) -> Option<ChunkPosition> {
+    while self.dx <= self.radius {
+      let offset = ChunkPosition { x: self.dx, z: self.dz };
+
+      // Move to the next chunk of the square around the center
+      if self.dz < self.radius {
+        self.dz += 1;
+      } else {
+        self.dz = -self.radius;
+        self.dx += 1;
+      }
+
+      if ChunkPosition::ZERO.is_within_radius(offset, self.radius) {
+        return Some(ChunkPosition { x: self.center.x + offset.x, z: self.center.z + offset.z });
+      }
+    }
+
+    None
+  }
+}
@@ -0,0 +1,254 @@
+use super::aabb::Aabb;
+use super::block_face::BlockFace;
+use super::block_position::BlockPosition;
+use super::chunk_position::ChunkPosition;
+use super::vector3f::Vector3f;
+
+/**
+ * A cuboid region of blocks, the min and max corner are both part of the region.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cuboid {
+  pub min: BlockPosition,
+  pub max: BlockPosition,
+}
+
+impl Cuboid {
+  /**
+   * Creates the region spanning between the two corners, in any order.
+  */
+  pub fn new(a: BlockPosition, b: BlockPosition) -> Self {
+    Cuboid {
+      min: BlockPosition { x: a.x.min(b.x), y: a.y.min(b.y), z: a.z.min(b.z) },
+      max: BlockPosition { x: a.x.max(b.x), y: a.y.max(b.y), z: a.z.max(b.z) },
+    }
+  }
+
+  /**
+   * Gets the number of blocks along each axis.
+  */
+  pub fn size(&self) -> BlockPosition {
+    self.max - self.min + BlockPosition { x: 1, y: 1, z: 1 }
+  }
+
+  /**
+   * Gets the number of blocks in the region.
+  */
+  pub fn volume(&self) -> u64 {
+    let size = self.size();
+
+    size.x as u64 * size.y as u64 * size.z as u64
+  }
+
+  pub fn contains(&self, position: BlockPosition) -> bool {
+    position.x >= self.min.x && position.x <= self.max.x
+      && position.y >= self.min.y && position.y <= self.max.y
+      && position.z >= self.min.z && position.z <= self.max.z
+  }
+
+  /**
+   * Checks if the two regions share at least one block.
+  */
+  pub fn intersects(&self, other: &Cuboid) -> bool {
+    self.min.x <= other.max.x && self.max.x >= other.min.x
+      && self.min.y <= other.max.y && self.max.y >= other.min.y
+      && self.min.z <= other.max.z && self.max.z >= other.min.z
+  }
+
+  /**
+   * Gets the blocks shared by the two regions, if any.
+  */
+  pub fn intersection(&self, other: &Cuboid) -> Option<Cuboid> {
+    if !self.intersects(other) {
+      return None;
+    }
+
+    Some(Cuboid {
+      min: BlockPosition { x: self.min.x.max(other.min.x), y: self.min.y.max(other.min.y), z: self.min.z.max(other.min.z) },
+      max: BlockPosition { x: self.max.x.min(other.max.x), y: self.max.y.min(other.max.y), z: self.max.z.min(other.max.z) },
+    })
+  }
+
+  /**
+   * Gets the smallest region containing both regions.
+  */
+  pub fn union(&self, other: &Cuboid) -> Cuboid {
+    Cuboid {
+      min: BlockPosition { x: self.min.x.min(other.min.x), y: self.min.y.min(other.min.y), z: self.min.z.min(other.min.z) },
+      max: BlockPosition { x: self.max.x.max(other.max.x), y: self.max.y.max(other.max.y), z: self.max.z.max(other.max.z) },
+    }
+  }
+
+  /**
+   * Grows the region by the amount of blocks on every side.
+  */
+  pub fn expand(&self, amount: i32) -> Cuboid {
+    let amount = BlockPosition { x: amount, y: amount, z: amount };
+
+    Cuboid::new(self.min - amount, self.max + amount)
+  }
+
+  /**
+   * Iterates over every block of the region, ordered by x, then z, then y.
+  */
+  pub fn iter(&self) -> CuboidIter {
+    CuboidIter { cuboid: *self, next: Some(self.min), remaining: self.volume() }
+  }
+
+  /**
+   * Gets the one block thick layer of the region on the face.
+  */
+  pub fn face(&self, face: BlockFace) -> Cuboid {
+    let (mut min, mut max) = (self.min, self.max);
+
+    match face {
+      BlockFace::Down => max.y = min.y,
+      BlockFace::Up => min.y = max.y,
+      BlockFace::North => max.z = min.z,
+      BlockFace::South => min.z = max.z,
+      BlockFace::West => max.x = min.x,
+      BlockFace::East => min.x = max.x,
+    }
+
+    Cuboid { min, max }
+  }
+
+  /**
+   * Iterates over the blocks on the surface of the region, every block once.
+  */
+  pub fn shell(&self) -> impl Iterator<Item = BlockPosition> {
+    let Cuboid { min, max } = *self;
+
+    (min.x..=max.x).flat_map(move |x| (min.z..=max.z).flat_map(move |z| {
+      // Inner columns only have their top and bottom block on the surface
+      let side = x == min.x || x == max.x || z == min.z || z == max.z;
+      let step = if side { 1 } else { (max.y - min.y).max(1) as usize };
+
+      (min.y..=max.y).step_by(step).map(move |y| BlockPosition { x, y, z })
+    }))
+  }
+
+  /**
+   * Iterates over the blocks on the twelve edges of the region, every block once.
+  */
+  pub fn edges(&self) -> impl Iterator<Item = BlockPosition> {
+    let cuboid = *self;
+
+    self.shell().filter(move |position| {
+      let bounds = [
+        position.x == cuboid.min.x || position.x == cuboid.max.x,
+        position.y == cuboid.min.y || position.y == cuboid.max.y,
+        position.z == cuboid.min.z || position.z == cuboid.max.z,
+      ];
+
+      bounds.into_iter().filter(|bound| *bound).count() >= 2
+    })
+  }
+
+  /**
+   * Splits the region into the pieces lying within each chunk.
+  */
+  pub fn split_chunks(&self) -> Vec<(ChunkPosition, Cuboid)> {
+    let from = ChunkPosition::from(self.min);
+    let to = ChunkPosition::from(self.max);
+
+    let mut pieces = Vec::new();
+    for x in from.x..=to.x {
+      for z in from.z..=to.z {
+        let chunk = ChunkPosition { x, z };
+        let column = Cuboid {
+          min: chunk.min_block(self.min.y),
+          max: chunk.min_block(self.max.y) + BlockPosition { x: 15, y: 0, z: 15 },
+        };
+
+        if let Some(piece) = self.intersection(&column) {
+          pieces.push((chunk, piece));
+        }
+      }
+    }
+
+    pieces
+  }
+}
+
+impl IntoIterator for Cuboid {
+  type Item = BlockPosition;
+  type IntoIter = CuboidIter;
+
+  fn into_iter(self) -> CuboidIter {
+    self.iter()
+  }
+}
+
+impl IntoIterator for &Cuboid {
+  type Item = BlockPosition;
+  type IntoIter = CuboidIter;
+
+  fn into_iter(self) -> CuboidIter {
+    self.iter()
+  }
+}
+
+/**
+ * Gets the box covering every block of the region.
+*/
+impl From<Cuboid> for Aabb {
+  fn from(cuboid: Cuboid) -> Self {
+    Aabb { min: Vector3f::from(cuboid.min), max: Vector3f::from(cuboid.max + BlockPosition { x: 1, y: 1, z: 1 }) }
+  }
+}
+
+/**
+ * Gets the region of every block the box overlaps, a flat box still covers the blocks it lies in.
+*/
+impl From<Aabb> for Cuboid {
+  fn from(aabb: Aabb) -> Self {
+    let min = aabb.min.floor();
+    let max = BlockPosition {
+      x: (aabb.max.x.ceil() as i32 - 1).max(min.x),
+      y: (aabb.max.y.ceil() as i32 - 1).max(min.y),
+      z: (aabb.max.z.ceil() as i32 - 1).max(min.z),
+    };
+
+    Cuboid { min, max }
+  }
+}
+
+/**
+ * An iterator over the blocks of a region, see `Cuboid::iter`.
+*/
+#[derive(Debug, Clone)]
+pub struct CuboidIter {
+  cuboid: Cuboid,
+  next: Option<BlockPosition>,
+  remaining: u64,
+}
+
+impl Iterator for CuboidIter {
+  type Item = BlockPosition;
+
+  fn next(&mut self) -> Option<BlockPosition> {
+    let current = self.next?;
+    let Cuboid { min, max } = self.cuboid;
+
+    // Step along y first, then z, then x
+    self.next = if current.y < max.y {
+      Some(BlockPosition { y: current.y + 1, ..current })
+    } else if current.z < max.z {
+      Some(BlockPosition { y: min.y, z: current.z + 1, ..current })
+    } else if current.x < max.x {
+      Some(BlockPosition { x: current.x + 1, y: min.y, z: min.z })
+    } else {
+      None
+    };
+    self.remaining -= 1;
+
+    Some(current)
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    let remaining = usize::try_from(self.remaining).ok();
+
+    (remaining.unwrap_or(usize::MAX), remaining)
+  }
+}
@@ -1,2 +1,10 @@
 pub mod vector3f;
-pub mod block_position;
\ No newline at end of file
+pub mod block_position;
+pub mod block_face;
+pub mod chunk_position;
+pub mod sub_chunk_position;
+pub mod chunk_local_position;
+pub mod aabb;
+pub mod cuboid;
+pub mod voxel_ray;
+pub mod rotation;
\ No newline at end of file
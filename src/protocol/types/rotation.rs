@@ -0,0 +1,102 @@
+use napi::bindgen_prelude::FromNapiValue;
+use napi_derive::napi;
+
+use crate::error::error::SerenityError;
+use crate::utils::node_converter::*;
+
+use super::vector3f::Vector3f;
+
+const WRAPPER: &str = "Rotation";
+
+/**
+ * The rotation of an entity in degrees.
+ * A yaw of 0 faces south and increases clockwise, a positive pitch looks down.
+*/
+#[napi]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rotation {
+  pub pitch: f64,
+  pub yaw: f64,
+  pub head_yaw: f64,
+}
+
+impl Rotation {
+  /**
+   * Creates the rotation from its angles, in degrees.
+  */
+  pub const fn new(pitch: f64, yaw: f64, head_yaw: f64) -> Self {
+    Rotation { pitch, yaw, head_yaw }
+  }
+
+  /**
+   * Reads the rotation from a JS object with pitch, yaw and headYaw properties.
+  */
+  pub fn from_js_object(object: napi::JsObject) -> Result<Self, SerenityError> {
+    // Get the pitch value
+    let pitch = get_node_float64(&object, WRAPPER, "pitch")?;
+
+    // Get the yaw value
+    let yaw = get_node_float64(&object, WRAPPER, "yaw")?;
+
+    // Get the head yaw value
+    let head_yaw = get_node_float64(&object, WRAPPER, "headYaw")?;
+
+    Ok(Rotation { pitch, yaw, head_yaw })
+  }
+
+  /**
+   * Creates a JS object with the pitch, yaw and headYaw properties of the rotation.
+  */
+  pub fn to_js_object(&self, env: napi::Env) -> Result<napi::JsObject, SerenityError> {
+    let mut object = create_node_object(&env, WRAPPER)?;
+    self.write_to(&env, &mut object)?;
+
+    Ok(object)
+  }
+
+  /**
+   * Writes the rotation into the properties of an existing JS rotation object.
+  */
+  pub fn write_to(&self, env: &napi::Env, object: &mut napi::JsObject) -> Result<(), SerenityError> {
+    let pitch = create_node_float64(env, WRAPPER, self.pitch)?;
+    let yaw = create_node_float64(env, WRAPPER, self.yaw)?;
+    let head_yaw = create_node_float64(env, WRAPPER, self.head_yaw)?;
+
+    set_node_property(object, WRAPPER, "pitch", pitch)?;
+    set_node_property(object, WRAPPER, "yaw", yaw)?;
+    set_node_property(object, WRAPPER, "headYaw", head_yaw)?;
+
+    Ok(())
+  }
+
+  /**
+   * Creates the rotation facing along the direction, with the head facing the same way.
+  */
+  pub fn from_direction(direction: Vector3f) -> Self {
+    let direction = direction.normalize();
+    let yaw = (-direction.x).atan2(direction.z).to_degrees();
+    let pitch = (-direction.y).asin().to_degrees();
+
+    Rotation { pitch, yaw, head_yaw: yaw }
+  }
+
+  /**
+   * Gets the unit vector pointing in the direction of the pitch and yaw.
+  */
+  pub fn direction(&self) -> Vector3f {
+    let pitch = self.pitch.to_radians();
+    let yaw = self.yaw.to_radians();
+
+    Vector3f { x: -yaw.sin() * pitch.cos(), y: -pitch.sin(), z: yaw.cos() * pitch.cos() }
+  }
+}
+
+impl FromNapiValue for Rotation {
+  unsafe fn from_napi_value(env: napi::sys::napi_env, value: napi::sys::napi_value) -> napi::Result<Self> {
+    // Create the JsObject from the napi_value
+    let object = object_from_raw(env, value, WRAPPER)?;
+
+    // Return the Rotation instance
+    Ok(Rotation::from_js_object(object)?)
+  }
+}
@@ -0,0 +1,87 @@
+use super::block_position::BlockPosition;
+use super::chunk_local_position::SubChunkLocalPosition;
+use super::chunk_position::ChunkPosition;
+use super::vector3f::Vector3f;
+
+/**
+ * The position of a sub-chunk, a 16 by 16 by 16 block section of a chunk column.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SubChunkPosition {
+  pub x: i32,
+  pub y: i32,
+  pub z: i32,
+}
+
+impl SubChunkPosition {
+  /** The number of blocks along each axis of a sub-chunk. */
+  pub const SIZE: i32 = 16;
+
+  /**
+   * Gets the chunk column the sub-chunk is part of.
+  */
+  pub fn chunk(&self) -> ChunkPosition {
+    ChunkPosition { x: self.x, z: self.z }
+  }
+
+  /**
+   * Gets the block at the local position within the sub-chunk, the inverse of `SubChunkLocalPosition::from`.
+  */
+  pub fn block_at(&self, local: SubChunkLocalPosition) -> BlockPosition {
+    BlockPosition {
+      x: self.x * Self::SIZE + local.x as i32,
+      y: self.y * Self::SIZE + local.y as i32,
+      z: self.z * Self::SIZE + local.z as i32,
+    }
+  }
+
+  /**
+   * Gets the block with the lowest coordinates of the sub-chunk.
+  */
+  pub fn min_block(&self) -> BlockPosition {
+    self.block_at(SubChunkLocalPosition { x: 0, y: 0, z: 0 })
+  }
+
+  /**
+   * Checks if the block lies within the sub-chunk.
+  */
+  pub fn contains(&self, position: BlockPosition) -> bool {
+    SubChunkPosition::from(position) == *self
+  }
+
+  /**
+   * Packs the position into a single key.
+   * The key is unique for x and z within ±2^27 and y within ±2^7, which covers every valid world position.
+  */
+  pub fn key(&self) -> i64 {
+    let x = (self.x as i64 & 0x0FFF_FFFF) << 36;
+    let z = (self.z as i64 & 0x0FFF_FFFF) << 8;
+    let y = self.y as i64 & 0xFF;
+
+    x | z | y
+  }
+
+  /**
+   * Unpacks a position packed by `key`.
+  */
+  pub fn from_key(key: i64) -> SubChunkPosition {
+    // Shift every field to the top of the key first, so the sign is extended back
+    SubChunkPosition {
+      x: (key >> 36) as i32,
+      y: ((key << 56) >> 56) as i32,
+      z: ((key << 28) >> 36) as i32,
+    }
+  }
+}
+
+impl From<BlockPosition> for SubChunkPosition {
+  fn from(position: BlockPosition) -> Self {
+    SubChunkPosition { x: position.x >> 4, y: position.y >> 4, z: position.z >> 4 }
+  }
+}
+
+impl From<Vector3f> for SubChunkPosition {
+  fn from(position: Vector3f) -> Self {
+    SubChunkPosition::from(position.floor())
+  }
+}
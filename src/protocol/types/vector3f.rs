@@ -1,10 +1,17 @@
-use napi::{bindgen_prelude::FromNapiValue, NapiValue};
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+
+use napi::bindgen_prelude::FromNapiValue;
 use napi_derive::napi;
 
+use crate::error::error::SerenityError;
 use crate::utils::node_converter::*;
 
+use super::block_position::BlockPosition;
+
+const WRAPPER: &str = "Vector3f";
+
 #[napi]
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Vector3f {
   pub x: f64,
   pub y: f64,
@@ -12,51 +19,199 @@ pub struct Vector3f {
 }
 
 impl Vector3f {
-  pub fn new(object: napi::JsObject) -> Self {
-    // Get the x object
-    let x_object = get_node_float64(&object, "x").unwrap();
-    let x = x_object.get_double().unwrap();
+  pub const ZERO: Vector3f = Vector3f { x: 0.0, y: 0.0, z: 0.0 };
+  pub const ONE: Vector3f = Vector3f { x: 1.0, y: 1.0, z: 1.0 };
+  pub const UP: Vector3f = Vector3f { x: 0.0, y: 1.0, z: 0.0 };
+  pub const DOWN: Vector3f = Vector3f { x: 0.0, y: -1.0, z: 0.0 };
+  pub const NORTH: Vector3f = Vector3f { x: 0.0, y: 0.0, z: -1.0 };
+  pub const SOUTH: Vector3f = Vector3f { x: 0.0, y: 0.0, z: 1.0 };
+  pub const WEST: Vector3f = Vector3f { x: -1.0, y: 0.0, z: 0.0 };
+  pub const EAST: Vector3f = Vector3f { x: 1.0, y: 0.0, z: 0.0 };
+
+  /**
+   * Creates the vector from its coordinates.
+  */
+  pub const fn new(x: f64, y: f64, z: f64) -> Self {
+    Vector3f { x, y, z }
+  }
 
-    // Get the y object
-    let y_object = get_node_float64(&object, "y").unwrap();
-    let y = y_object.get_double().unwrap();
+  /**
+   * Reads the vector from a JS object with x, y and z properties.
+  */
+  pub fn from_js_object(object: napi::JsObject) -> Result<Self, SerenityError> {
+    // Get the x value
+    let x = get_node_float64(&object, WRAPPER, "x")?;
 
-    // Get the z object
-    let z_object = get_node_float64(&object, "z").unwrap();
-    let z = z_object.get_double().unwrap();
+    // Get the y value
+    let y = get_node_float64(&object, WRAPPER, "y")?;
 
-    Vector3f { x, y, z }
+    // Get the z value
+    let z = get_node_float64(&object, WRAPPER, "z")?;
+
+    Ok(Vector3f { x, y, z })
   }
-}
 
-impl Vector3f {
-  pub fn to_js_object(&self, env: napi::Env) -> napi::JsObject {
-    let mut object = create_node_object(&env).unwrap();
+  /**
+   * Creates a JS object with the x, y and z properties of the vector.
+  */
+  pub fn to_js_object(&self, env: napi::Env) -> Result<napi::JsObject, SerenityError> {
+    let mut object = create_node_object(&env, WRAPPER)?;
+
+    let x = create_node_float64(&env, WRAPPER, self.x)?;
+    let y = create_node_float64(&env, WRAPPER, self.y)?;
+    let z = create_node_float64(&env, WRAPPER, self.z)?;
+
+    set_node_property(&mut object, WRAPPER, "x", x)?;
+    set_node_property(&mut object, WRAPPER, "y", y)?;
+    set_node_property(&mut object, WRAPPER, "z", z)?;
 
-    let x = create_node_float64(&env, self.x).unwrap();
-    let y = create_node_float64(&env, self.y).unwrap();
-    let z = create_node_float64(&env, self.z).unwrap();
+    Ok(object)
+  }
+
+  /**
+   * Gets the dot product of the two vectors.
+  */
+  pub fn dot(&self, other: Vector3f) -> f64 {
+    self.x * other.x + self.y * other.y + self.z * other.z
+  }
 
-    object.set_named_property("x", x).unwrap();
-    object.set_named_property("y", y).unwrap();
-    object.set_named_property("z", z).unwrap();
+  /**
+   * Gets the cross product of the two vectors.
+  */
+  pub fn cross(&self, other: Vector3f) -> Vector3f {
+    Vector3f {
+      x: self.y * other.z - self.z * other.y,
+      y: self.z * other.x - self.x * other.z,
+      z: self.x * other.y - self.y * other.x,
+    }
+  }
+
+  /**
+   * Gets the squared length, cheaper than `length` when only comparing lengths.
+  */
+  pub fn length_squared(&self) -> f64 {
+    self.dot(*self)
+  }
+
+  /**
+   * Gets the length of the vector.
+  */
+  pub fn length(&self) -> f64 {
+    self.length_squared().sqrt()
+  }
+
+  /**
+   * Gets the squared distance to the other vector, cheaper than `distance` when only comparing distances.
+  */
+  pub fn distance_squared(&self, other: Vector3f) -> f64 {
+    (*self - other).length_squared()
+  }
+
+  /**
+   * Gets the distance to the other vector.
+  */
+  pub fn distance(&self, other: Vector3f) -> f64 {
+    (*self - other).length()
+  }
+
+  /**
+   * Gets the vector scaled to a length of 1, the zero vector stays zero.
+  */
+  pub fn normalize(&self) -> Vector3f {
+    let length = self.length();
+    if length == 0.0 {
+      return Vector3f::ZERO;
+    }
+
+    *self / length
+  }
+
+  /**
+   * Interpolates linearly between the two vectors, `t` of 0 gives this vector and 1 gives the other.
+  */
+  pub fn lerp(&self, other: Vector3f, t: f64) -> Vector3f {
+    *self + (other - *self) * t
+  }
+
+  /**
+   * Gets the position of the block the vector is inside of.
+  */
+  pub fn floor(&self) -> BlockPosition {
+    BlockPosition { x: self.x.floor() as i32, y: self.y.floor() as i32, z: self.z.floor() as i32 }
+  }
 
-    object
+  /**
+   * Gets the block position closest to the vector, rounding halves away from zero.
+  */
+  pub fn round(&self) -> BlockPosition {
+    BlockPosition { x: self.x.round() as i32, y: self.y.round() as i32, z: self.z.round() as i32 }
   }
 }
 
 impl FromNapiValue for Vector3f {
   unsafe fn from_napi_value(env: napi::sys::napi_env, value: napi::sys::napi_value) -> napi::Result<Self> {
     // Create the JsObject from the napi_value
-    let object = match napi::JsObject::from_raw(env.clone(), value.clone()) {
-      Ok(obj) => obj,
-      Err(e) => return Err(napi::Error::new(
-        napi::Status::GenericFailure,
-        e.to_string()
-      ))
-    };
+    let object = object_from_raw(env, value, WRAPPER)?;
 
     // Return the Vector3f instance
-    Ok(Vector3f::new(object))
+    Ok(Vector3f::from_js_object(object)?)
   }
-}
\ No newline at end of file
+}
+
+impl From<BlockPosition> for Vector3f {
+  fn from(position: BlockPosition) -> Self {
+    Vector3f { x: position.x as f64, y: position.y as f64, z: position.z as f64 }
+  }
+}
+
+impl Add for Vector3f {
+  type Output = Vector3f;
+
+  fn add(self, other: Vector3f) -> Vector3f {
+    Vector3f { x: self.x + other.x, y: self.y + other.y, z: self.z + other.z }
+  }
+}
+
+impl Sub for Vector3f {
+  type Output = Vector3f;
+
+  fn sub(self, other: Vector3f) -> Vector3f {
+    Vector3f { x: self.x - other.x, y: self.y - other.y, z: self.z - other.z }
+  }
+}
+
+impl Mul<f64> for Vector3f {
+  type Output = Vector3f;
+
+  fn mul(self, scalar: f64) -> Vector3f {
+    Vector3f { x: self.x * scalar, y: self.y * scalar, z: self.z * scalar }
+  }
+}
+
+impl Div<f64> for Vector3f {
+  type Output = Vector3f;
+
+  fn div(self, scalar: f64) -> Vector3f {
+    Vector3f { x: self.x / scalar, y: self.y / scalar, z: self.z / scalar }
+  }
+}
+
+impl Neg for Vector3f {
+  type Output = Vector3f;
+
+  fn neg(self) -> Vector3f {
+    Vector3f { x: -self.x, y: -self.y, z: -self.z }
+  }
+}
+
+impl AddAssign for Vector3f {
+  fn add_assign(&mut self, other: Vector3f) {
+    *self = *self + other;
+  }
+}
+
+impl SubAssign for Vector3f {
+  fn sub_assign(&mut self, other: Vector3f) {
+    *self = *self - other;
+  }
+}
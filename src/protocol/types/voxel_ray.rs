@@ -0,0 +1,118 @@
+use super::block_face::BlockFace;
+use super::block_position::BlockPosition;
+use super::vector3f::Vector3f;
+
+/**
+ * A block crossed by a `VoxelRay`.
+*/
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VoxelStep {
+  /** The position of the crossed block. */
+  pub position: BlockPosition,
+
+  /** The face the ray entered the block through, `None` for the block the ray starts in. */
+  pub face: Option<BlockFace>,
+
+  /** The point the ray entered the block at. */
+  pub point: Vector3f,
+
+  /** The distance from the origin to the point. */
+  pub distance: f64,
+}
+
+/**
+ * Walks every block a ray crosses, in order, using a DDA voxel traversal.
+ * The walk starts with the block of the origin and ends once the max distance is passed.
+*/
+#[derive(Debug, Clone)]
+pub struct VoxelRay {
+  origin: Vector3f,
+  direction: Vector3f,
+  max_distance: f64,
+  current: BlockPosition,
+  step: BlockPosition,
+  // The distance along the ray to the next boundary on each axis
+  next_boundary: Vector3f,
+  // The distance along the ray between two boundaries on each axis
+  boundary_delta: Vector3f,
+  started: bool,
+  done: bool,
+}
+
+impl VoxelRay {
+  pub fn new(origin: Vector3f, direction: Vector3f, max_distance: f64) -> Self {
+    let direction = direction.normalize();
+    let current = origin.floor();
+
+    let axis = |origin: f64, direction: f64, block: i32| -> (i32, f64, f64) {
+      if direction > 0.0 {
+        (1, (block as f64 + 1.0 - origin) / direction, 1.0 / direction)
+      } else if direction < 0.0 {
+        (-1, (origin - block as f64) / -direction, -1.0 / direction)
+      } else {
+        (0, f64::INFINITY, f64::INFINITY)
+      }
+    };
+
+    let (step_x, next_x, delta_x) = axis(origin.x, direction.x, current.x);
+    let (step_y, next_y, delta_y) = axis(origin.y, direction.y, current.y);
+    let (step_z, next_z, delta_z) = axis(origin.z, direction.z, current.z);
+
+    VoxelRay {
+      origin,
+      direction,
+      max_distance,
+      current,
+      step: BlockPosition { x: step_x, y: step_y, z: step_z },
+      next_boundary: Vector3f { x: next_x, y: next_y, z: next_z },
+      boundary_delta: Vector3f { x: delta_x, y: delta_y, z: delta_z },
+      started: false,
+      // A ray without a direction never leaves its block
+      done: direction == Vector3f::ZERO || max_distance < 0.0,
+    }
+  }
+}
+
+impl Iterator for VoxelRay {
+  type Item = VoxelStep;
+
+  fn next(&mut self) -> Option<VoxelStep> {
+    if self.done {
+      return None;
+    }
+
+    // Start with the block the origin lies in
+    if !self.started {
+      self.started = true;
+      return Some(VoxelStep { position: self.current, face: None, point: self.origin, distance: 0.0 });
+    }
+
+    // Cross the nearest boundary
+    let next = self.next_boundary;
+    let (distance, face) = if next.x <= next.y && next.x <= next.z {
+      self.current.x += self.step.x;
+      self.next_boundary.x += self.boundary_delta.x;
+      (next.x, if self.step.x > 0 { BlockFace::West } else { BlockFace::East })
+    } else if next.y <= next.z {
+      self.current.y += self.step.y;
+      self.next_boundary.y += self.boundary_delta.y;
+      (next.y, if self.step.y > 0 { BlockFace::Down } else { BlockFace::Up })
+    } else {
+      self.current.z += self.step.z;
+      self.next_boundary.z += self.boundary_delta.z;
+      (next.z, if self.step.z > 0 { BlockFace::North } else { BlockFace::South })
+    };
+
+    if distance > self.max_distance {
+      self.done = true;
+      return None;
+    }
+
+    Some(VoxelStep {
+      position: self.current,
+      face: Some(face),
+      point: self.origin + self.direction * distance,
+      distance,
+    })
+  }
+}
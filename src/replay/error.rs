@@ -0,0 +1,55 @@
+use std::fmt;
+
+use crate::error::error::SerenityError;
+
+/**
+ * An error raised while recording or replaying signals.
+*/
+#[derive(Debug)]
+pub enum ReplayError {
+  /** Reading or writing the recording failed. */
+  Io(std::io::Error),
+
+  /** A line of the recording or baseline is not valid JSON for what it should hold. */
+  Parse { line: usize, message: String },
+
+  /** A handler returned an error while replaying a line of the recording. */
+  Handler { line: usize, error: SerenityError },
+
+  /** Registering or removing the recorder's listeners failed. */
+  Serenity(SerenityError),
+}
+
+impl fmt::Display for ReplayError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      ReplayError::Io(e) => write!(f, "replay: {}", e),
+      ReplayError::Parse { line, message } => write!(f, "replay: line {} could not be parsed: {}", line, message),
+      ReplayError::Handler { line, error } => write!(f, "replay: handler failed on line {}: {}", line, error),
+      ReplayError::Serenity(error) => write!(f, "replay: {}", error),
+    }
+  }
+}
+
+impl std::error::Error for ReplayError {
+  fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+    match self {
+      ReplayError::Io(e) => Some(e),
+      ReplayError::Handler { error, .. } => Some(error),
+      ReplayError::Serenity(error) => Some(error),
+      ReplayError::Parse { .. } => None,
+    }
+  }
+}
+
+impl From<std::io::Error> for ReplayError {
+  fn from(error: std::io::Error) -> Self {
+    ReplayError::Io(error)
+  }
+}
+
+impl From<SerenityError> for ReplayError {
+  fn from(error: SerenityError) -> Self {
+    ReplayError::Serenity(error)
+  }
+}
@@ -0,0 +1,4 @@
+pub mod error;
+pub mod snapshot;
+pub mod recorder;
+pub mod replayer;
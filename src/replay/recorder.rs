@@ -0,0 +1,172 @@
+use std::cell::RefCell;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::rc::Rc;
+
+use crate::error::error::SerenityError;
+use crate::logger::logger::Logger;
+use crate::serenity::listener::ListenerHandle;
+use crate::serenity::world::Worlds;
+use crate::world::events::player_chat::PlayerChatSignal;
+use crate::world::events::player_execute_command::PlayerExecuteCommandSignal;
+use crate::world::events::player_join::PlayerJoinSignal;
+use crate::world::events::player_leave::PlayerLeaveSignal;
+use crate::world::events::world_tick::WorldTickSignal;
+
+use super::error::ReplayError;
+use super::snapshot::{PlayerSnapshot, RecordedSignal, WorldSnapshot};
+
+type RecordingWriter = Rc<RefCell<RecordingState>>;
+
+struct RecordingState {
+  writer: Box<dyn Write>,
+  // Only the first write failure is logged, so a full disk does not flood the console
+  failed: bool,
+}
+
+/**
+ * Records every received player and world tick signal to a JSONL file, one `RecordedSignal` per line.
+ * The signals stop being recorded once the recorder is stopped or dropped.
+*/
+pub struct SignalRecorder {
+  writer: RecordingWriter,
+  listeners: Vec<ListenerHandle>,
+}
+
+impl SignalRecorder {
+  /**
+   * Starts recording into the file at the path, replacing it if it exists.
+  */
+  pub fn create(worlds: &Worlds, path: impl AsRef<Path>) -> Result<Self, ReplayError> {
+    let file = File::create(path)?;
+
+    SignalRecorder::start(worlds, BufWriter::new(file))
+  }
+
+  /**
+   * Starts recording into the writer.
+  */
+  pub fn start(worlds: &Worlds, writer: impl Write + 'static) -> Result<Self, ReplayError> {
+    let writer = Rc::new(RefCell::new(RecordingState { writer: Box::new(writer), failed: false }));
+
+    let listeners = vec![
+      listen(worlds, &writer, |signal: &PlayerJoinSignal| {
+        Ok(RecordedSignal::PlayerJoin {
+          world: WorldSnapshot::capture(&signal.world),
+          player: PlayerSnapshot::capture(&signal.player)?,
+        })
+      })?,
+      listen(worlds, &writer, |signal: &PlayerChatSignal| {
+        Ok(RecordedSignal::PlayerChat {
+          world: WorldSnapshot::capture(&signal.world),
+          player: PlayerSnapshot::capture(&signal.player)?,
+          message: signal.message.clone(),
+        })
+      })?,
+      listen(worlds, &writer, |signal: &PlayerExecuteCommandSignal| {
+        Ok(RecordedSignal::PlayerExecuteCommand {
+          world: WorldSnapshot::capture(&signal.world),
+          player: PlayerSnapshot::capture(&signal.player)?,
+          command: signal.command.clone(),
+        })
+      })?,
+      listen(worlds, &writer, |signal: &PlayerLeaveSignal| {
+        Ok(RecordedSignal::PlayerLeave {
+          world: WorldSnapshot::capture(&signal.world),
+          player: PlayerSnapshot::capture(&signal.player)?,
+          reason: signal.reason,
+          message: signal.message.clone(),
+        })
+      })?,
+      listen(worlds, &writer, |signal: &WorldTickSignal| {
+        Ok(RecordedSignal::WorldTick { world: WorldSnapshot::capture(&signal.world) })
+      })?,
+    ];
+
+    Ok(SignalRecorder { writer, listeners })
+  }
+
+  /**
+   * Stops recording, removing the listeners and flushing the recording.
+  */
+  pub fn stop(mut self) -> Result<(), ReplayError> {
+    for listener in self.listeners.drain(..) {
+      listener.cancel()?;
+    }
+
+    self.writer.borrow_mut().writer.flush()?;
+
+    Ok(())
+  }
+}
+
+impl Drop for SignalRecorder {
+  fn drop(&mut self) {
+    let _ = self.writer.borrow_mut().writer.flush();
+  }
+}
+
+/**
+ * A signal that can be recorded, knowing the logger to report write failures to.
+*/
+trait RecordableSignal: crate::world::events::signal::Signal {
+  fn logger(&self) -> &Logger;
+}
+
+impl RecordableSignal for PlayerJoinSignal {
+  fn logger(&self) -> &Logger {
+    &self.world.logger
+  }
+}
+
+impl RecordableSignal for PlayerChatSignal {
+  fn logger(&self) -> &Logger {
+    &self.world.logger
+  }
+}
+
+impl RecordableSignal for PlayerExecuteCommandSignal {
+  fn logger(&self) -> &Logger {
+    &self.world.logger
+  }
+}
+
+impl RecordableSignal for PlayerLeaveSignal {
+  fn logger(&self) -> &Logger {
+    &self.world.logger
+  }
+}
+
+impl RecordableSignal for WorldTickSignal {
+  fn logger(&self) -> &Logger {
+    &self.world.logger
+  }
+}
+
+fn listen<S, F>(worlds: &Worlds, writer: &RecordingWriter, snapshot: F) -> Result<ListenerHandle, SerenityError>
+where
+  S: RecordableSignal,
+  F: Fn(&S) -> Result<RecordedSignal, SerenityError> + 'static
+{
+  let writer = writer.clone();
+
+  worlds.on(move |signal: S| {
+    let recorded = snapshot(&signal)?;
+
+    // Write the signal as a single line
+    let mut state = writer.borrow_mut();
+    let result = serde_json::to_writer(&mut state.writer, &recorded)
+      .map_err(std::io::Error::from)
+      .and_then(|_| state.writer.write_all(b"\n"));
+
+    match result {
+      Ok(()) => Ok(()),
+      Err(_) if state.failed => Ok(()),
+      Err(e) => {
+        state.failed = true;
+        signal.logger().error(&format!("Failed to record a signal: {}", e))
+      }
+    }
+  })
+}
@@ -0,0 +1,218 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+
+use crate::backend::mock::*;
+use crate::error::error::SerenityError;
+use crate::protocol::types::rotation::Rotation;
+use crate::protocol::types::vector3f::Vector3f;
+
+use super::error::ReplayError;
+use super::snapshot::{PlayerSnapshot, RecordedSignal, WorldSnapshot};
+
+/**
+ * The plugin handlers a recording is fed through, every handler defaults to doing nothing.
+ * Implement them by calling the same generic handlers the plugin registers for the real signals.
+*/
+pub trait ReplayHandlers {
+  fn on_player_join(&mut self, _signal: &MockPlayerJoinSignal) -> Result<(), SerenityError> {
+    Ok(())
+  }
+
+  fn on_player_chat(&mut self, _signal: &mut MockPlayerChatSignal) -> Result<(), SerenityError> {
+    Ok(())
+  }
+
+  fn on_player_execute_command(&mut self, _signal: &mut MockPlayerExecuteCommandSignal) -> Result<(), SerenityError> {
+    Ok(())
+  }
+
+  fn on_player_leave(&mut self, _signal: &mut MockPlayerLeaveSignal) -> Result<(), SerenityError> {
+    Ok(())
+  }
+
+  fn on_world_tick(&mut self, _signal: &MockWorldTickSignal) -> Result<(), SerenityError> {
+    Ok(())
+  }
+}
+
+/**
+ * Replays a recording against a mock host, collecting the outgoing calls the handlers make.
+ *
+ * ```ignore
+ * let calls = Replayer::new().replay_file("signals.jsonl", &mut MyHandlers)?;
+ * let baseline = read_baseline(BufReader::new(File::open("baseline.jsonl")?))?;
+ *
+ * for diff in diff_calls(&baseline, &calls) {
+ *   println!("{:?}", diff);
+ * }
+ * ```
+*/
+#[derive(Default)]
+pub struct Replayer {
+  host: MockHost,
+  worlds: HashMap<String, MockWorld>,
+}
+
+impl Replayer {
+  pub fn new() -> Self {
+    Replayer::default()
+  }
+
+  /**
+   * Gets the mock host the recording is replayed against.
+  */
+  pub fn host(&self) -> &MockHost {
+    &self.host
+  }
+
+  /**
+   * Replays the recording in the file at the path.
+  */
+  pub fn replay_file<H: ReplayHandlers>(&mut self, path: impl AsRef<Path>, handlers: &mut H) -> Result<Vec<MockCall>, ReplayError> {
+    let file = File::open(path)?;
+
+    self.replay(BufReader::new(file), handlers)
+  }
+
+  /**
+   * Replays the recording, returning the calls the handlers made.
+  */
+  pub fn replay<R: BufRead, H: ReplayHandlers>(&mut self, reader: R, handlers: &mut H) -> Result<Vec<MockCall>, ReplayError> {
+    self.host.clear_calls();
+
+    for (index, line) in reader.lines().enumerate() {
+      let line_number = index + 1;
+      let line = line?;
+      if line.trim().is_empty() {
+        continue;
+      }
+
+      let signal: RecordedSignal = serde_json::from_str(&line)
+        .map_err(|e| ReplayError::Parse { line: line_number, message: e.to_string() })?;
+
+      self.dispatch(signal, handlers)
+        .map_err(|error| ReplayError::Handler { line: line_number, error })?;
+    }
+
+    Ok(self.host.calls())
+  }
+
+  fn dispatch<H: ReplayHandlers>(&mut self, signal: RecordedSignal, handlers: &mut H) -> Result<(), SerenityError> {
+    // Bring the world and the player to the state they were recorded in
+    let world = self.world(signal.world());
+    let player = signal.player().map(|player| restore_player(&world, player));
+
+    match (signal, player) {
+      (RecordedSignal::PlayerJoin { .. }, Some(player)) => {
+        handlers.on_player_join(&MockPlayerJoinSignal::new(&world, &player))
+      },
+      (RecordedSignal::PlayerChat { message, .. }, Some(player)) => {
+        handlers.on_player_chat(&mut MockPlayerChatSignal::new(&world, &player, &message))
+      },
+      (RecordedSignal::PlayerExecuteCommand { command, .. }, Some(player)) => {
+        handlers.on_player_execute_command(&mut MockPlayerExecuteCommandSignal::new(&world, &player, &command))
+      },
+      (RecordedSignal::PlayerLeave { reason, message, .. }, Some(player)) => {
+        let result = handlers.on_player_leave(&mut MockPlayerLeaveSignal::new(&world, &player, reason, &message));
+        world.remove_player(&player);
+
+        result
+      },
+      (RecordedSignal::WorldTick { .. }, _) => {
+        handlers.on_world_tick(&MockWorldTickSignal::new(&world))
+      },
+      // Player signals always carry a player
+      (_, None) => Ok(())
+    }
+  }
+
+  fn world(&mut self, snapshot: &WorldSnapshot) -> MockWorld {
+    let world = self.worlds.entry(snapshot.identifier.clone())
+      .or_insert_with(|| self.host.world(&snapshot.identifier))
+      .clone();
+    world.set_current_tick(snapshot.current_tick);
+
+    world
+  }
+}
+
+fn restore_player(world: &MockWorld, snapshot: &PlayerSnapshot) -> MockPlayer {
+  let player = world.find_player(&snapshot.uuid)
+    .unwrap_or_else(|| world.add_player_with_ids(&snapshot.username, &snapshot.xuid, &snapshot.uuid));
+
+  let [x, y, z] = snapshot.position;
+  player.set_position(Vector3f { x, y, z });
+  let [pitch, yaw, head_yaw] = snapshot.rotation;
+  player.set_rotation(Rotation { pitch, yaw, head_yaw });
+  player.set_current_gamemode(snapshot.gamemode);
+  player.set_op(snapshot.op);
+
+  player
+}
+
+/**
+ * A difference between the baseline calls and the calls made by a replay.
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub enum CallDiff {
+  /** The call at the index differs from the baseline. */
+  Changed { index: usize, expected: MockCall, actual: MockCall },
+
+  /** The baseline has a call at the index the replay did not make. */
+  Missing { index: usize, expected: MockCall },
+
+  /** The replay made a call at the index the baseline does not have. */
+  Unexpected { index: usize, actual: MockCall },
+}
+
+/**
+ * Compares the calls of a replay with the baseline, call by call.
+*/
+pub fn diff_calls(baseline: &[MockCall], actual: &[MockCall]) -> Vec<CallDiff> {
+  let length = baseline.len().max(actual.len());
+
+  (0..length).filter_map(|index| match (baseline.get(index), actual.get(index)) {
+    (Some(expected), Some(actual)) if expected != actual => {
+      Some(CallDiff::Changed { index, expected: expected.clone(), actual: actual.clone() })
+    },
+    (Some(expected), None) => Some(CallDiff::Missing { index, expected: expected.clone() }),
+    (None, Some(actual)) => Some(CallDiff::Unexpected { index, actual: actual.clone() }),
+    _ => None
+  }).collect()
+}
+
+/**
+ * Reads a baseline written by `write_baseline`, one call per line.
+*/
+pub fn read_baseline<R: BufRead>(reader: R) -> Result<Vec<MockCall>, ReplayError> {
+  let mut calls = Vec::new();
+
+  for (index, line) in reader.lines().enumerate() {
+    let line = line?;
+    if line.trim().is_empty() {
+      continue;
+    }
+
+    let call = serde_json::from_str(&line)
+      .map_err(|e| ReplayError::Parse { line: index + 1, message: e.to_string() })?;
+    calls.push(call);
+  }
+
+  Ok(calls)
+}
+
+/**
+ * Writes the calls as a baseline, one call per line.
+*/
+pub fn write_baseline<W: Write>(mut writer: W, calls: &[MockCall]) -> Result<(), ReplayError> {
+  for call in calls {
+    serde_json::to_writer(&mut writer, call).map_err(std::io::Error::from)?;
+    writer.write_all(b"\n")?;
+  }
+
+  writer.flush()?;
+
+  Ok(())
+}
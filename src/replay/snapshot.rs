@@ -0,0 +1,93 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::error::SerenityError;
+use crate::world::player::player::Player;
+use crate::world::world::world::World;
+
+/**
+ * The state of a player at the time a signal was received.
+*/
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlayerSnapshot {
+  pub username: String,
+  pub xuid: String,
+  pub uuid: String,
+  pub position: [f64; 3],
+  /** The pitch, yaw and head yaw, missing from recordings made before rotations were recorded. */
+  #[serde(default)]
+  pub rotation: [f64; 3],
+  pub dimension: String,
+  pub gamemode: i32,
+  pub op: bool,
+}
+
+impl PlayerSnapshot {
+  pub fn capture(player: &Player) -> Result<Self, SerenityError> {
+    Ok(PlayerSnapshot {
+      username: player.username.clone(),
+      xuid: player.xuid.clone(),
+      uuid: player.uuid.clone(),
+      position: [player.position.x, player.position.y, player.position.z],
+      rotation: [player.rotation.pitch, player.rotation.yaw, player.rotation.head_yaw],
+      dimension: player.dimension.identifier.clone(),
+      gamemode: player.get_gamemode()?,
+      op: player.is_op()?,
+    })
+  }
+}
+
+/**
+ * The state of a world at the time a signal was received.
+*/
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorldSnapshot {
+  pub identifier: String,
+  pub current_tick: u64,
+}
+
+impl WorldSnapshot {
+  pub fn capture(world: &World) -> Self {
+    WorldSnapshot { identifier: world.identifier.clone(), current_tick: world.current_tick }
+  }
+}
+
+/**
+ * A received signal, written as one line of a recording.
+*/
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "signal")]
+pub enum RecordedSignal {
+  PlayerJoin { world: WorldSnapshot, player: PlayerSnapshot },
+  PlayerChat { world: WorldSnapshot, player: PlayerSnapshot, message: String },
+  PlayerExecuteCommand { world: WorldSnapshot, player: PlayerSnapshot, command: String },
+  PlayerLeave { world: WorldSnapshot, player: PlayerSnapshot, reason: i32, message: String },
+  WorldTick { world: WorldSnapshot },
+}
+
+impl RecordedSignal {
+  /**
+   * Gets the snapshot of the world the signal was received for.
+  */
+  pub fn world(&self) -> &WorldSnapshot {
+    match self {
+      RecordedSignal::PlayerJoin { world, .. } => world,
+      RecordedSignal::PlayerChat { world, .. } => world,
+      RecordedSignal::PlayerExecuteCommand { world, .. } => world,
+      RecordedSignal::PlayerLeave { world, .. } => world,
+      RecordedSignal::WorldTick { world } => world,
+    }
+  }
+
+  /**
+   * Gets the snapshot of the player the signal was received for, if any.
+  */
+  pub fn player(&self) -> Option<&PlayerSnapshot> {
+    match self {
+      RecordedSignal::PlayerJoin { player, .. } => Some(player),
+      RecordedSignal::PlayerChat { player, .. } => Some(player),
+      RecordedSignal::PlayerExecuteCommand { player, .. } => Some(player),
+      RecordedSignal::PlayerLeave { player, .. } => Some(player),
+      RecordedSignal::WorldTick { .. } => None,
+    }
+  }
+}
@@ -0,0 +1,117 @@
+use napi::threadsafe_function::{ThreadSafeCallContext, ThreadsafeFunction, ThreadsafeFunctionCallMode};
+use napi::{CallContext, JsObject};
+
+use crate::error::error::SerenityError;
+use crate::utils::js_reference::JsReference;
+use crate::utils::js_wrapper::JsWrapper;
+use crate::utils::panic::catch_panic;
+
+use super::serenity::Serenity;
+
+const WRAPPER: &str = "MainThreadDispatcher";
+
+type Job = Box<dyn FnOnce(&Serenity) -> Result<(), SerenityError> + Send>;
+
+/**
+ * Queues closures from any thread to run on the JS thread, where the wrappers can be used.
+ * The dispatcher is cheap to clone, and does not keep the Node process alive on its own.
+ *
+ * ```ignore
+ * let dispatcher = MainThreadDispatcher::new(&serenity)?;
+ * let player = PlayerRef::from(&player);
+ *
+ * std::thread::spawn(move || {
+ *   dispatcher.run(move |serenity| match player.resolve(serenity)? {
+ *     Some(player) => player.send_message("Done!"),
+ *     None => Ok(())
+ *   })
+ * });
+ * ```
+*/
+#[derive(Clone)]
+pub struct MainThreadDispatcher {
+  function: ThreadsafeFunction<Job>,
+}
+
+impl MainThreadDispatcher {
+  /**
+   * Creates a dispatcher for the serenity instance, must be called on the JS thread.
+  */
+  pub fn new(serenity: &Serenity) -> Result<Self, SerenityError> {
+    let env = serenity.js_env();
+
+    // Keep the serenity object alive for as long as jobs can be queued
+    let serenity_ref = SerenityReference::new(env, serenity.js_object())?;
+
+    // The threadsafe function needs a JS function to call, the jobs run before it is called
+    let noop = env.create_function_from_closure("serenityrsDispatcher", |_: CallContext| Ok(()))
+      .map_err(|e| SerenityError::NapiFailure { wrapper: WRAPPER, message: e.to_string() })?;
+
+    let mut function = env.create_threadsafe_function(&noop, 0, move |ctx: ThreadSafeCallContext<Job>| {
+      serenity_ref.run(ctx.env, ctx.value);
+
+      Ok(Vec::<()>::new())
+    }).map_err(|e| SerenityError::NapiFailure { wrapper: WRAPPER, message: e.to_string() })?;
+
+    // Queued jobs should not keep the server from shutting down
+    function.unref(&env)
+      .map_err(|e| SerenityError::NapiFailure { wrapper: WRAPPER, message: e.to_string() })?;
+
+    Ok(MainThreadDispatcher { function })
+  }
+
+  /**
+   * Queues the closure to run on the JS thread, with the serenity instance the dispatcher was created for.
+   * An error returned by the closure is logged through the serenity logger.
+  */
+  pub fn run<F>(&self, job: F) -> Result<(), SerenityError>
+  where
+    F: FnOnce(&Serenity) -> Result<(), SerenityError> + Send + 'static
+  {
+    match self.function.call(Ok(Box::new(job)), ThreadsafeFunctionCallMode::NonBlocking) {
+      napi::Status::Ok => Ok(()),
+      status => Err(SerenityError::NapiFailure { wrapper: WRAPPER, message: format!("failed to queue the job: {}", status) })
+    }
+  }
+}
+
+/**
+ * The reference to the serenity object, owned by the threadsafe function.
+ * It is only touched on the JS thread, where the jobs run and the function is finalized.
+*/
+struct SerenityReference(JsReference);
+
+// The threadsafe function only runs jobs and drops its callback on the JS thread,
+// so the reference is never used or released anywhere else
+unsafe impl Send for SerenityReference {}
+
+impl SerenityReference {
+  fn new(env: napi::Env, object: &JsObject) -> Result<Self, SerenityError> {
+    Ok(SerenityReference(JsReference::new(env, object, WRAPPER)?))
+  }
+
+  fn run(&self, env: napi::Env, job: Job) {
+    // Get the serenity instance back from the reference
+    let serenity = self.0.object().and_then(|object| Serenity::new(env, object));
+
+    let serenity = match serenity {
+      Ok(serenity) => serenity,
+      Err(e) => {
+        // There is no logger to report to without the serenity instance
+        eprintln!("{}", e);
+        return;
+      }
+    };
+
+    // Run the job, logging any error it returns or panic it raises
+    let report = match catch_panic(WRAPPER, || job(&serenity)) {
+      Ok(()) => return,
+      Err(SerenityError::Panic { message, backtrace, .. }) => format!("Dispatched job panicked: {}\n{}", message, backtrace),
+      Err(e) => e.to_string(),
+    };
+
+    if serenity.logger.error(&report).is_err() {
+      eprintln!("{}", report);
+    }
+  }
+}
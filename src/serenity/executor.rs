@@ -0,0 +1,482 @@
+use std::any::{type_name, Any};
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::rc::{Rc, Weak};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Wake, Waker};
+
+use napi::{JsObject, NapiRaw, NapiValue};
+
+use crate::backend::backend::{LoggerBackend, WorldBackend};
+use crate::error::error::SerenityError;
+use crate::utils::js_reference::JsReference;
+use crate::utils::js_wrapper::JsWrapper;
+use crate::utils::panic::catch_panic;
+use crate::world::events::signal::Signal;
+use crate::world::events::world_tick::WorldTickSignal;
+use crate::world::world::world::World;
+
+use super::listener::ListenerHandle;
+use super::scheduler::TaskToken;
+use super::world::Worlds;
+
+const WRAPPER: &str = "Executor";
+
+type SpawnedFuture = Pin<Box<dyn Future<Output = Result<(), SerenityError>>>>;
+type WorldSource<W> = Box<dyn Fn() -> Result<W, SerenityError>>;
+type WorldsSource = Rc<dyn Fn() -> Result<Worlds, SerenityError>>;
+
+thread_local! {
+  // The executor and task currently being polled, used by `wait_ticks` and `wait_for`
+  static CURRENT: RefCell<Option<(Rc<dyn Any>, u64)>> = const { RefCell::new(None) };
+}
+
+/**
+ * A single-threaded executor for async plugin logic, polled from the world tick event.
+ * Ticks follow the first world that ticks, like the global tasks of the `Scheduler`.
+ *
+ * Wrappers are only valid until the task awaits again, so keep a `PlayerRef` or `WorldRef`
+ * across an await instead, and resolve it with the world `wait_ticks` resolves to.
+ *
+ * ```ignore
+ * let executor = Executor::new(&serenity.worlds)?;
+ *
+ * executor.spawn(async move {
+ *   let signal = wait_for::<PlayerChatSignal, _>(|signal| signal.message == "!countdown").await?;
+ *   let player = PlayerRef::from(&signal.player);
+ *
+ *   for seconds in (1..=3).rev() {
+ *     let world = wait_ticks(20).await?;
+ *     if let Some(player) = player.resolve_in(&world)? {
+ *       player.send_message(&format!("{}...", seconds))?;
+ *     }
+ *   }
+ *
+ *   Ok(())
+ * });
+ * ```
+*/
+pub struct Executor<W: WorldBackend = World> {
+  state: Rc<ExecutorState<W>>,
+  // The tick listener, type-erased so a detached executor never links against N-API
+  _listener: Option<Rc<dyn Any>>,
+}
+
+impl<W: WorldBackend> Clone for Executor<W> {
+  fn clone(&self) -> Self {
+    Executor { state: self.state.clone(), _listener: self._listener.clone() }
+  }
+}
+
+impl Executor {
+  /**
+   * Creates an executor, registering its tick listener.
+  */
+  pub fn new(worlds: &Worlds) -> Result<Self, SerenityError> {
+    // Keep the worlds object alive, to register the listeners of `wait_for` and log failed tasks
+    let reference = JsReference::new(worlds.js_env(), worlds.js_object(), WRAPPER)?;
+    let get_worlds: WorldsSource = Rc::new(move || {
+      reference.object().and_then(|object| Worlds::new(reference.env(), object))
+    });
+
+    let logger = get_worlds.clone();
+    let report = Box::new(move |report: &str| {
+      if logger().and_then(|worlds| worlds.logger.error(report)).is_err() {
+        eprintln!("{}", report);
+      }
+    });
+
+    let state = Rc::new(ExecutorState::new(Some(get_worlds), report));
+
+    // The listener only holds a weak reference, so dropping the executor drops the tasks
+    let weak: Weak<ExecutorState<World>> = Rc::downgrade(&state);
+    let listener = worlds.on(move |signal: WorldTickSignal| {
+      let state = match weak.upgrade() {
+        Some(state) => state,
+        None => return Ok(())
+      };
+
+      // The world object is only valid during this callback, which is when the tasks are polled
+      let env = signal.world.js_env();
+      let raw = unsafe { signal.world.js_object().raw() };
+      state.tick(signal.world.identifier.clone(), Box::new(move || {
+        World::new(env, unsafe { JsObject::from_raw_unchecked(env.raw(), raw) })
+      }));
+
+      Ok(())
+    })?;
+    let listener: Rc<ListenerHandle> = Rc::new(listener);
+
+    Ok(Executor { state, _listener: Some(listener) })
+  }
+}
+
+impl<W: WorldBackend + Clone + 'static> Executor<W> {
+  /**
+   * Creates an executor without a tick listener, its tasks only advance when `tick` is called.
+   * Failed tasks are logged through the logger, and `wait_for` can not be awaited on it.
+  */
+  pub fn detached<L: LoggerBackend + 'static>(logger: L) -> Self {
+    let report = Box::new(move |report: &str| {
+      if logger.error(report).is_err() {
+        eprintln!("{}", report);
+      }
+    });
+
+    Executor { state: Rc::new(ExecutorState::new(None, report)), _listener: None }
+  }
+
+  /**
+   * Advances the executor by a tick of the world, polling the tasks whose ticks are due.
+  */
+  pub fn tick(&self, world: &W) {
+    let world = world.clone();
+
+    self.state.tick(world.identifier(), Box::new(move || Ok(world.clone())));
+  }
+}
+
+impl<W: WorldBackend + 'static> Executor<W> {
+  /**
+   * Spawns the future, which runs until its first await right away.
+   * An error returned by the future, or a panic raised by it, is logged through the worlds logger.
+  */
+  pub fn spawn<F>(&self, future: F) -> TaskToken
+  where
+    F: Future<Output = Result<(), SerenityError>> + 'static
+  {
+    let token = TaskToken::default();
+
+    let id = self.state.next_id.get();
+    self.state.next_id.set(id + 1);
+
+    self.state.tasks.borrow_mut().insert(id, SpawnedTask { future: Box::pin(future), token: token.clone() });
+    self.state.wake(id);
+    self.state.run_ready();
+
+    token
+  }
+
+  /**
+   * Gets the number of tasks that have not finished or been cancelled yet.
+  */
+  pub fn task_count(&self) -> usize {
+    self.state.tasks.borrow().values().filter(|task| !task.token.is_cancelled()).count()
+  }
+}
+
+/**
+ * Waits for the given number of ticks, and resolves to the world that ticked.
+ * Always waits until at least the next tick.
+ *
+ * # Panics
+ * Panics when polled outside of a task spawned on an `Executor`.
+*/
+pub fn wait_ticks(ticks: u64) -> WaitTicks {
+  WaitTicks::new(ticks)
+}
+
+/**
+ * Waits for the next signal `S` the predicate accepts, and resolves to it.
+ *
+ * # Panics
+ * Panics when polled outside of a task spawned on an `Executor` created with `Executor::new`.
+*/
+pub fn wait_for<S, P>(predicate: P) -> WaitFor<S, P>
+where
+  S: Signal,
+  P: FnMut(&S) -> bool + 'static
+{
+  WaitFor { predicate: Some(predicate), slot: Rc::new(RefCell::new(None)), listener: None }
+}
+
+/**
+ * The future returned by `wait_ticks`.
+*/
+pub struct WaitTicks<W = World> {
+  ticks: u64,
+  due: Option<u64>,
+  world: PhantomData<fn() -> W>,
+}
+
+impl<W> WaitTicks<W> {
+  /**
+   * Waits for the ticks of an executor driven by another world backend, e.g. a detached `Executor<MockWorld>`.
+  */
+  pub fn new(ticks: u64) -> Self {
+    WaitTicks { ticks: ticks.max(1), due: None, world: PhantomData }
+  }
+}
+
+impl<W: WorldBackend + 'static> Future for WaitTicks<W> {
+  type Output = Result<W, SerenityError>;
+
+  fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+    let (state, _) = current::<W>("wait_ticks");
+    let now = state.tick.get();
+
+    match self.due {
+      // Count the ticks from the first poll, the timer wakes the task once they passed
+      None => {
+        let due = now + self.ticks;
+        self.due = Some(due);
+        state.timers.borrow_mut().push((due, cx.waker().clone()));
+      },
+      // Resolve only while a tick is being handled, which is when the world is available
+      Some(due) if now >= due => {
+        if let Some(world) = state.current_world() {
+          return Poll::Ready(world);
+        }
+
+        // The timer already fired, so wait for the next tick
+        state.timers.borrow_mut().push((now + 1, cx.waker().clone()));
+      },
+      // The timer is still waiting
+      Some(_) => {}
+    }
+
+    Poll::Pending
+  }
+}
+
+/**
+ * The future returned by `wait_for`.
+*/
+pub struct WaitFor<S: Signal, P> {
+  predicate: Option<P>,
+  slot: Rc<RefCell<Option<S>>>,
+  listener: Option<ListenerHandle>,
+}
+
+impl<S: Signal, P> Unpin for WaitFor<S, P> {}
+
+impl<S, P> Future for WaitFor<S, P>
+where
+  S: Signal,
+  P: FnMut(&S) -> bool + 'static
+{
+  type Output = Result<S, SerenityError>;
+
+  fn poll(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Self::Output> {
+    let (state, task) = current::<World>("wait_for");
+
+    // Check if the listener already delivered a signal
+    let signal = self.slot.borrow_mut().take();
+    if let Some(signal) = signal {
+      self.listener = None;
+      return Poll::Ready(Ok(signal));
+    }
+
+    // Register the listener on the first poll
+    if let Some(mut predicate) = self.predicate.take() {
+      let get_worlds = state.worlds.clone()
+        .unwrap_or_else(|| panic!("wait_for can not be awaited on a detached Executor"));
+      let slot = self.slot.clone();
+      let weak = Rc::downgrade(&state);
+
+      let listener = get_worlds().and_then(|worlds| worlds.on(move |signal: S| {
+        let state = match weak.upgrade() {
+          Some(state) => state,
+          None => return Ok(())
+        };
+
+        // The signal is only valid during this callback, so the task has to be polled right away
+        if !state.tasks.borrow().contains_key(&task) || slot.borrow().is_some() || !predicate(&signal) {
+          return Ok(());
+        }
+
+        *slot.borrow_mut() = Some(signal);
+        state.wake(task);
+        state.run_ready();
+
+        Ok(())
+      }));
+
+      match listener {
+        Ok(listener) => self.listener = Some(listener),
+        Err(e) => return Poll::Ready(Err(e))
+      }
+    }
+
+    Poll::Pending
+  }
+}
+
+fn current<W: 'static>(future: &str) -> (Rc<ExecutorState<W>>, u64) {
+  let (state, task) = CURRENT.with(|current| current.borrow().clone())
+    .unwrap_or_else(|| panic!("{} must be awaited inside a task spawned on an Executor", future));
+
+  match state.downcast::<ExecutorState<W>>() {
+    Ok(state) => (state, task),
+    Err(_) => panic!("{} must be awaited inside a task spawned on an Executor<{}>", future, type_name::<W>())
+  }
+}
+
+/**
+ * Makes a task the current one, until dropped, which also happens when polling the task panics.
+*/
+struct CurrentGuard(Option<(Rc<dyn Any>, u64)>);
+
+impl CurrentGuard {
+  fn enter(state: Rc<dyn Any>, task: u64) -> Self {
+    CurrentGuard(CURRENT.with(|current| current.replace(Some((state, task)))))
+  }
+}
+
+impl Drop for CurrentGuard {
+  fn drop(&mut self) {
+    let previous = self.0.take();
+    CURRENT.with(|current| *current.borrow_mut() = previous);
+  }
+}
+
+/**
+ * Makes the ticking world available to the tasks, until dropped.
+*/
+struct WorldGuard<'a, W> {
+  slot: &'a RefCell<Option<WorldSource<W>>>,
+  previous: Option<WorldSource<W>>,
+}
+
+impl<'a, W> WorldGuard<'a, W> {
+  fn enter(slot: &'a RefCell<Option<WorldSource<W>>>, world: WorldSource<W>) -> Self {
+    let previous = slot.replace(Some(world));
+
+    WorldGuard { slot, previous }
+  }
+}
+
+impl<W> Drop for WorldGuard<'_, W> {
+  fn drop(&mut self) {
+    *self.slot.borrow_mut() = self.previous.take();
+  }
+}
+
+struct SpawnedTask {
+  future: SpawnedFuture,
+  token: TaskToken,
+}
+
+struct TaskWaker {
+  id: u64,
+  ready: Arc<Mutex<VecDeque<u64>>>,
+}
+
+impl Wake for TaskWaker {
+  fn wake(self: Arc<Self>) {
+    let mut ready = self.ready.lock().unwrap_or_else(|e| e.into_inner());
+    if !ready.contains(&self.id) {
+      ready.push_back(self.id);
+    }
+  }
+}
+
+struct ExecutorState<W> {
+  // Gets the worlds, to register the listeners of `wait_for`, unless the executor is detached
+  worlds: Option<WorldsSource>,
+  // Logs the error of a failed task
+  report: Box<dyn Fn(&str)>,
+  tasks: RefCell<HashMap<u64, SpawnedTask>>,
+  // The tasks to poll, shared with their wakers
+  ready: Arc<Mutex<VecDeque<u64>>>,
+  // The tick each waiting `wait_ticks` is due on
+  timers: RefCell<Vec<(u64, Waker)>>,
+  tick: Cell<u64>,
+  // The world global ticks follow, and the one ticking right now
+  primary: RefCell<Option<String>>,
+  world: RefCell<Option<WorldSource<W>>>,
+  next_id: Cell<u64>,
+}
+
+impl<W: 'static> ExecutorState<W> {
+  fn new(worlds: Option<WorldsSource>, report: Box<dyn Fn(&str)>) -> Self {
+    ExecutorState {
+      worlds,
+      report,
+      tasks: RefCell::default(),
+      ready: Arc::default(),
+      timers: RefCell::default(),
+      tick: Cell::new(0),
+      primary: RefCell::default(),
+      world: RefCell::new(None),
+      next_id: Cell::new(0),
+    }
+  }
+
+  fn current_world(&self) -> Option<Result<W, SerenityError>> {
+    // Create a new wrapper for the world that is ticking
+    self.world.borrow().as_ref().map(|world| world())
+  }
+
+  fn wake(&self, id: u64) {
+    Arc::new(TaskWaker { id, ready: self.ready.clone() }).wake();
+  }
+
+  fn tick(self: &Rc<Self>, identifier: String, world: WorldSource<W>) {
+    // Only the first world that ticks advances the executor
+    let is_primary = {
+      let mut primary = self.primary.borrow_mut();
+      *primary.get_or_insert_with(|| identifier.clone()) == identifier
+    };
+    if !is_primary {
+      return;
+    }
+
+    self.tick.set(self.tick.get() + 1);
+
+    // Drop cancelled tasks, which also removes their listeners
+    self.tasks.borrow_mut().retain(|_, task| !task.token.is_cancelled());
+
+    // Wake the tasks whose ticks are due
+    let now = self.tick.get();
+    let due: Vec<Waker> = {
+      let mut timers = self.timers.borrow_mut();
+      let (due, waiting) = std::mem::take(&mut *timers).into_iter().partition(|(tick, _)| *tick <= now);
+      *timers = waiting;
+      due.into_iter().map(|(_, waker)| waker).collect()
+    };
+    due.into_iter().for_each(Waker::wake);
+
+    // Poll the tasks while the world is available
+    let _world = WorldGuard::enter(&self.world, world);
+    self.run_ready();
+  }
+
+  fn run_ready(self: &Rc<Self>) {
+    loop {
+      let id = match self.ready.lock().unwrap_or_else(|e| e.into_inner()).pop_front() {
+        Some(id) => id,
+        None => break
+      };
+
+      // A task that is already being polled is skipped
+      let mut task = match self.tasks.borrow_mut().remove(&id) {
+        Some(task) if !task.token.is_cancelled() => task,
+        _ => continue
+      };
+
+      // Poll the task, with the executor as the current one
+      let waker = Waker::from(Arc::new(TaskWaker { id, ready: self.ready.clone() }));
+      let result = {
+        let _current = CurrentGuard::enter(self.clone(), id);
+        catch_panic(WRAPPER, || Ok(task.future.as_mut().poll(&mut Context::from_waker(&waker))))
+      };
+
+      match result {
+        Ok(Poll::Pending) => {
+          self.tasks.borrow_mut().insert(id, task);
+        },
+        Ok(Poll::Ready(Ok(()))) => {},
+        Ok(Poll::Ready(Err(e))) => (self.report)(&format!("Async task failed: {}", e)),
+        // A future that panicked can not be polled again, so the task is dropped
+        Err(SerenityError::Panic { message, backtrace, .. }) => {
+          (self.report)(&format!("Async task panicked: {}\n{}", message, backtrace))
+        },
+        Err(e) => (self.report)(&format!("Async task failed: {}", e)),
+      }
+    }
+  }
+}
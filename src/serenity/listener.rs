@@ -0,0 +1,187 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use napi::{JsFunction, JsObject, Ref};
+
+use crate::error::error::SerenityError;
+use crate::utils::node_converter::*;
+use crate::world::events::world_event::WorldEvent;
+
+const WRAPPER: &str = "ListenerHandle";
+
+/**
+ * What a listener is registered for.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ListenerEvent {
+  World(WorldEvent),
+
+  /** A packet id, for listeners registered through `Serenity::on_packet`. */
+  Packet(u32),
+}
+
+impl ListenerEvent {
+  /**
+   * Gets the id the emitter uses for the event.
+  */
+  pub fn id(self) -> i32 {
+    match self {
+      ListenerEvent::World(event) => event.id(),
+      ListenerEvent::Packet(id) => id as i32,
+    }
+  }
+}
+
+impl From<WorldEvent> for ListenerEvent {
+  fn from(event: WorldEvent) -> Self {
+    ListenerEvent::World(event)
+  }
+}
+
+/**
+ * A handle to a listener registered through `Worlds::on`, `Worlds::once`, `Worlds::before` or `Serenity::on_packet`.
+ * The listener is removed from SerenityJS when the handle is cancelled or dropped,
+ * use `detach` to keep it registered for the lifetime of the server.
+*/
+#[must_use = "dropping the handle removes the listener, call `detach` to keep it registered"]
+pub struct ListenerHandle {
+  env: napi::Env,
+  emitter: Ref<()>,
+  callback: Ref<()>,
+  event: ListenerEvent,
+  remove_method: &'static str,
+  active: bool,
+  guard: Rc<PanicGuard>,
+}
+
+impl ListenerHandle {
+  pub(crate) fn new(env: napi::Env, emitter: &JsObject, callback: &JsFunction, event: ListenerEvent, remove_method: &'static str, guard: Rc<PanicGuard>) -> Result<Self, SerenityError> {
+    // Keep both the emitter and the callback alive until the listener is removed
+    let emitter = env.create_reference(emitter)
+      .map_err(|e| SerenityError::NapiFailure { wrapper: WRAPPER, message: e.to_string() })?;
+    let callback = env.create_reference(callback)
+      .map_err(|e| SerenityError::NapiFailure { wrapper: WRAPPER, message: e.to_string() })?;
+
+    Ok(ListenerHandle { env, emitter, callback, event, remove_method, active: true, guard })
+  }
+
+  /**
+   * Gets the event the listener is registered for.
+  */
+  pub fn event(&self) -> ListenerEvent {
+    self.event
+  }
+
+  /**
+   * Checks if the listener is still registered through this handle.
+  */
+  pub fn is_active(&self) -> bool {
+    self.active
+  }
+
+  /**
+   * Disables the listener after it panicked the given number of times in a row, `None` never disables it.
+   * A disabled listener stays registered, but is no longer called.
+  */
+  pub fn set_panic_limit(&self, limit: Option<u32>) {
+    self.guard.limit.set(limit);
+  }
+
+  /**
+   * Checks if the listener was disabled for panicking too often.
+  */
+  pub fn is_disabled(&self) -> bool {
+    self.guard.disabled.get()
+  }
+
+  /**
+   * Removes the listener from SerenityJS.
+  */
+  pub fn cancel(mut self) -> Result<(), SerenityError> {
+    self.remove()
+  }
+
+  /**
+   * Releases the handle without removing the listener, which then stays registered.
+  */
+  pub fn detach(mut self) {
+    self.active = false;
+    self.release();
+  }
+
+  fn remove(&mut self) -> Result<(), SerenityError> {
+    // Check if the listener was already removed
+    if !self.active {
+      return Ok(());
+    }
+    self.active = false;
+
+    // Get the emitter and the callback back from their references
+    let result = (|| {
+      let emitter = self.env.get_reference_value::<JsObject>(&self.emitter)
+        .map_err(|e| SerenityError::NapiFailure { wrapper: WRAPPER, message: e.to_string() })?;
+      let callback = self.env.get_reference_value::<JsFunction>(&self.callback)
+        .map_err(|e| SerenityError::NapiFailure { wrapper: WRAPPER, message: e.to_string() })?;
+
+      // Convert the event to a JsNumber
+      let event = convert_to_js_number(&self.env, WRAPPER, self.event.id())?;
+
+      // Call the off or removeBefore function
+      call_node_method(&emitter, WRAPPER, self.remove_method, &[event.into_unknown(), callback.into_unknown()])?;
+
+      Ok(())
+    })();
+
+    // Release the references even if the listener could not be removed
+    self.release();
+
+    result
+  }
+
+  fn release(&mut self) {
+    let _ = self.emitter.unref(self.env);
+    let _ = self.callback.unref(self.env);
+  }
+}
+
+impl Drop for ListenerHandle {
+  fn drop(&mut self) {
+    let _ = self.remove();
+  }
+}
+
+/**
+ * Tracks the consecutive panics of a listener, shared between its handle and its callback.
+*/
+#[derive(Default)]
+pub(crate) struct PanicGuard {
+  limit: Cell<Option<u32>>,
+  panics: Cell<u32>,
+  disabled: Cell<bool>,
+}
+
+impl PanicGuard {
+  pub fn is_disabled(&self) -> bool {
+    self.disabled.get()
+  }
+
+  pub fn record_success(&self) {
+    self.panics.set(0);
+  }
+
+  /**
+   * Counts a panic, returning the number of consecutive panics when the listener got disabled by it.
+  */
+  pub fn record_panic(&self) -> Option<u32> {
+    let panics = self.panics.get() + 1;
+    self.panics.set(panics);
+
+    match self.limit.get() {
+      Some(limit) if panics >= limit => {
+        self.disabled.set(true);
+        Some(panics)
+      },
+      _ => None
+    }
+  }
+}
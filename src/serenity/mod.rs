@@ -1,2 +1,7 @@
 pub mod serenity;
-pub mod world;
\ No newline at end of file
+pub mod world;
+pub mod network;
+pub mod listener;
+pub mod dispatcher;
+pub mod scheduler;
+pub mod executor;
@@ -0,0 +1,237 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use napi::{CallContext, JsBuffer, JsFunction, JsObject, JsUnknown, NapiRaw, NapiValue, ValueType};
+
+use crate::error::error::SerenityError;
+use crate::protocol::packets::intercept::{intercept, Interception, PacketAction, PacketDirection};
+use crate::protocol::packets::NetworkPacket;
+use crate::utils::js_reference::JsReference;
+use crate::utils::js_wrapper::FromNodeValue;
+use crate::utils::node_converter::*;
+use crate::utils::panic::catch_panic;
+use crate::world::player::player::Player;
+use crate::JsWrapper;
+
+use super::listener::{ListenerEvent, ListenerHandle, PanicGuard};
+use super::serenity::Serenity;
+use super::world::{log_report, report_panic};
+
+const WRAPPER: &str = "Network";
+
+#[derive(JsWrapper)]
+pub struct Network {
+  pub object: JsObject,
+  pub env: napi::Env,
+}
+
+impl Network {
+  /**
+   * Registers a handler for the packets of type `P` travelling in the direction, see `Serenity::on_packet`.
+  */
+  pub fn on_packet<P, F>(&self, serenity: &Serenity, direction: PacketDirection, handler: F) -> Result<ListenerHandle, SerenityError>
+  where
+    P: NetworkPacket + 'static,
+    F: FnMut(Option<&Player>, &mut P) -> PacketAction + 'static
+  {
+    let handler = RefCell::new(handler);
+    let guard = Rc::new(PanicGuard::default());
+    let listener_guard = guard.clone();
+
+    // The short name of the packet type, used in reports
+    let name = std::any::type_name::<P>().rsplit("::").next().unwrap_or(WRAPPER);
+
+    // Keep serenity and its logger alive for as long as the listener is registered
+    let logger = JsReference::new(self.env, &serenity.logger.object, WRAPPER)?;
+    let serenity = JsReference::new(self.env, &serenity.object, WRAPPER)?;
+
+    // Create the JS function SerenityJS calls before it handles or sends the packet
+    let function = self.env.create_function_from_closure("serenityrsPacketListener", move |ctx: CallContext| -> napi::Result<bool> {
+      // A disabled listener lets every packet through
+      if listener_guard.is_disabled() {
+        return Ok(true);
+      }
+
+      let env = *ctx.env;
+      let result = (|| -> Result<bool, SerenityError> {
+        // Only intercept packets travelling in the direction of the listener
+        let event = ctx.get::<JsObject>(0)
+          .map_err(|e| SerenityError::NapiFailure { wrapper: WRAPPER, message: e.to_string() })?;
+        if get_node_string(&event, WRAPPER, "bound")? != direction.bound() {
+          return Ok(true);
+        }
+
+        // Get the packet in its encoded form
+        let mut packet = get_node_object(&event, WRAPPER, "packet")?;
+        let bytes = match read_packet(env, &packet) {
+          Ok(bytes) => bytes,
+          Err(e) => {
+            // A packet that cannot be serialized is let through as it is
+            log_report(&logger, &format!("Could not read {} for its handler: {}", name, e));
+            return Ok(true);
+          }
+        };
+
+        // A handler that sends a packet of its own type would borrow the handler twice, so that packet is let through
+        let Ok(mut handler) = handler.try_borrow_mut() else {
+          log_report(&logger, &format!("{} handler was re-entered, the packet it sent was not handled", name));
+          return Ok(true);
+        };
+
+        // Catch panics, so they never unwind into SerenityJS
+        // The player is only looked up once the packet decoded, right before the handler runs
+        let mut lookup = Ok(());
+        let result = catch_panic(WRAPPER, || Ok(intercept::<P, _>(&bytes, |packet| {
+          match find_player(env, &serenity, &event) {
+            Ok(player) => handler(player.as_ref(), packet),
+            Err(e) => {
+              lookup = Err(e);
+              PacketAction::Pass
+            }
+          }
+        })));
+        lookup?;
+
+        match result {
+          Ok(Ok(interception)) => {
+            listener_guard.record_success();
+
+            match interception {
+              Interception::Unchanged => Ok(true),
+              Interception::Modified(bytes) => {
+                // The packet is still let through, without the changes of the handler
+                if let Err(e) = write_packet(env, &mut packet, bytes) {
+                  log_report(&logger, &format!("Could not apply the changes of the {} handler: {}", name, e));
+                }
+                Ok(true)
+              },
+              Interception::Cancelled => Ok(false),
+            }
+          },
+          Ok(Err(e)) => {
+            // The packet does not match its definition, so it is let through as it is
+            log_report(&logger, &format!("Could not decode {} for its handler: {}", name, e));
+            Ok(true)
+          },
+          Err(SerenityError::Panic { message, backtrace, .. }) => {
+            // A panicking handler lets the packet through
+            let limit = listener_guard.record_panic();
+            report_panic(&logger, name, &message, &backtrace, limit);
+            Ok(true)
+          },
+          Err(e) => Err(e),
+        }
+      })();
+
+      // Any other failure is reported and lets the packet through, so a listener never throws into SerenityJS
+      Ok(result.unwrap_or_else(|e| {
+        log_report(&logger, &format!("Could not handle {}: {}", name, e));
+        true
+      }))
+    }).map_err(|e| SerenityError::NapiFailure { wrapper: WRAPPER, message: e.to_string() })?;
+
+    // Keep a handle to the listener, so it can be removed later
+    let handle = ListenerHandle::new(self.env, &self.object, &function, ListenerEvent::Packet(P::ID), "removeBefore", guard)?;
+
+    // Convert the packet id to a JsNumber
+    let id = convert_to_js_number(&self.env, WRAPPER, P::ID as i32)?;
+
+    // Call the before function
+    if let Err(e) = call_node_method(&self.object, WRAPPER, "before", &[id.into_unknown(), function.into_unknown()]) {
+      // The listener was never registered, so there is nothing to remove
+      handle.detach();
+      return Err(e);
+    }
+
+    Ok(handle)
+  }
+}
+
+/**
+ * Serializes the JS packet into its bytes, with its header.
+ * A copy of the packet is serialized, so the stream of the packet itself is left untouched.
+*/
+fn read_packet(env: napi::Env, packet: &JsObject) -> Result<Vec<u8>, SerenityError> {
+  let object = global_object(env)?;
+
+  // Copy the packet with a fresh stream
+  let prototype = call_node_method(&object, WRAPPER, "getPrototypeOf", &[packet])?;
+  let copy = call_node_method(&object, WRAPPER, "create", &[prototype])?;
+  let mut stream = create_node_object(&env, WRAPPER)?;
+  let binary = env.create_array_with_length(0)
+    .map_err(|e| SerenityError::NapiFailure { wrapper: WRAPPER, message: e.to_string() })?;
+  set_node_property(&mut stream, WRAPPER, "binary", binary)?;
+  set_node_property(&mut stream, WRAPPER, "offset", 0)?;
+
+  let packet = unsafe { JsUnknown::from_raw_unchecked(env.raw(), packet.raw()) };
+  let copy = call_node_method(&object, WRAPPER, "assign", &[copy, packet, stream.into_unknown()])?;
+  let copy = node_value_to_object(copy, WRAPPER, "assign")?;
+  let buffer = call_node_method::<JsUnknown>(&copy, WRAPPER, "serialize", &[])?;
+
+  // Check that serializing returned a buffer
+  let is_buffer = buffer.is_buffer()
+    .map_err(|e| SerenityError::NapiFailure { wrapper: WRAPPER, message: e.to_string() })?;
+  if !is_buffer {
+    let found = buffer.get_type().map(|value_type| format!("{:?}", value_type)).unwrap_or_default();
+    return Err(SerenityError::InvalidType { wrapper: WRAPPER, property: "serialize".to_string(), expected: "Buffer", found });
+  }
+
+  let buffer = unsafe { buffer.cast::<JsBuffer>() }.into_value()
+    .map_err(|e| SerenityError::NapiFailure { wrapper: WRAPPER, message: e.to_string() })?;
+
+  Ok(buffer.to_vec())
+}
+
+/**
+ * Deserializes the bytes into a new packet of the same class, and copies its fields onto the JS packet SerenityJS keeps using.
+*/
+fn write_packet(env: napi::Env, packet: &mut JsObject, bytes: Vec<u8>) -> Result<(), SerenityError> {
+  let constructor = get_node_property(packet, WRAPPER, "constructor")?;
+  let class = expect_node_type::<JsFunction>(constructor, WRAPPER, "constructor", ValueType::Function)?;
+  let buffer = env.create_buffer_with_data(bytes)
+    .map_err(|e| SerenityError::NapiFailure { wrapper: WRAPPER, message: e.to_string() })?;
+
+  // Deserialize the bytes into a new packet
+  let changed = class.new_instance(&[buffer.into_raw()])
+    .map_err(|e| SerenityError::JsException { wrapper: WRAPPER, method: "constructor".to_string(), message: e.reason })?;
+  let changed = call_node_method::<JsUnknown>(&changed, WRAPPER, "deserialize", &[])?;
+  let changed = node_value_to_object(changed, WRAPPER, "deserialize")?;
+
+  // Copy every field, except the stream of the new packet
+  let keys = call_node_method(&global_object(env)?, WRAPPER, "keys", &[&changed])?;
+  let keys = node_value_to_object(keys, WRAPPER, "keys")?;
+  let length = keys.get_array_length()
+    .map_err(|e| SerenityError::NapiFailure { wrapper: WRAPPER, message: e.to_string() })?;
+  for index in 0..length {
+    let key = keys.get_element::<JsUnknown>(index)
+      .map_err(|e| SerenityError::NapiFailure { wrapper: WRAPPER, message: e.to_string() })?;
+    let key = String::from_node_value(env, key, WRAPPER, "keys")?;
+    if key == "binary" || key == "offset" {
+      continue;
+    }
+
+    let value = get_node_property(&changed, WRAPPER, &key)?;
+    set_node_property(packet, WRAPPER, &key, value)?;
+  }
+
+  Ok(())
+}
+
+/**
+ * Gets the global `Object` class.
+*/
+fn global_object(env: napi::Env) -> Result<JsObject, SerenityError> {
+  env.get_global()
+    .and_then(|global| global.get_named_property::<JsObject>("Object"))
+    .map_err(|e| SerenityError::NapiFailure { wrapper: WRAPPER, message: e.to_string() })
+}
+
+/**
+ * Gets the player of the connection the packet travels through, if it has spawned one yet.
+*/
+fn find_player(env: napi::Env, serenity: &JsReference, event: &JsObject) -> Result<Option<Player>, SerenityError> {
+  let connection = get_node_object(event, WRAPPER, "connection")?;
+  let player = call_node_method(&serenity.object()?, WRAPPER, "getPlayerByConnection", &[connection])?;
+
+  Option::<Player>::from_node_value(env, player, WRAPPER, "player")
+}
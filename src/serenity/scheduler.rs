@@ -0,0 +1,345 @@
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::rc::{Rc, Weak};
+use std::time::{Duration, Instant};
+
+use crate::backend::backend::{LoggerBackend, WorldBackend};
+use crate::error::error::SerenityError;
+use crate::utils::panic::catch_panic;
+use crate::world::events::world_tick::WorldTickSignal;
+use crate::world::world::world::World;
+use crate::world::world::world_ref::WorldRef;
+
+use super::listener::ListenerHandle;
+use super::world::Worlds;
+
+/** The time tasks may take per tick, unless changed with `Scheduler::set_tick_budget`. */
+pub const DEFAULT_TICK_BUDGET: Duration = Duration::from_millis(5);
+
+const WRAPPER: &str = "Scheduler";
+
+type TaskCallback<W> = Box<dyn FnMut(&W) -> Result<(), SerenityError>>;
+
+/**
+ * Runs tasks after a number of ticks, or every few ticks, driven by the world tick event.
+ * Global tasks follow the ticks of the first world that ticks, scoped tasks the ticks of their own world.
+ *
+ * The scheduler is cheap to clone, its tick listener is removed once every clone is dropped.
+ * A task holding a clone of its own scheduler keeps it alive until the task is cancelled or finishes.
+ *
+ * ```ignore
+ * let scheduler = Scheduler::new(&serenity.worlds)?;
+ *
+ * scheduler.run_repeating(20, |world| world.send_message("Another second passed"));
+ * ```
+*/
+pub struct Scheduler<W: WorldBackend = World> {
+  state: Rc<SchedulerState<W>>,
+  // The tick listener, type-erased so a detached scheduler never links against N-API
+  _listener: Option<Rc<dyn Any>>,
+}
+
+impl<W: WorldBackend> Clone for Scheduler<W> {
+  fn clone(&self) -> Self {
+    Scheduler { state: self.state.clone(), _listener: self._listener.clone() }
+  }
+}
+
+impl Scheduler {
+  /**
+   * Creates a scheduler, registering its tick listener.
+  */
+  pub fn new(worlds: &Worlds) -> Result<Self, SerenityError> {
+    let state = Rc::new(SchedulerState::default());
+
+    // The listener only holds a weak reference, so dropping the scheduler drops the tasks
+    let weak: Weak<SchedulerState<World>> = Rc::downgrade(&state);
+    let listener = worlds.on(move |signal: WorldTickSignal| {
+      match weak.upgrade() {
+        Some(state) => state.tick(&signal.world),
+        None => Ok(())
+      }
+    })?;
+
+    let listener: Rc<ListenerHandle> = Rc::new(listener);
+
+    Ok(Scheduler { state, _listener: Some(listener) })
+  }
+}
+
+impl<W: WorldBackend> Scheduler<W> {
+  /**
+   * Creates a scheduler without a tick listener, its tasks only run when `tick` is called.
+   * Lets the tasks be driven by the worlds of a `MockHost`.
+  */
+  pub fn detached() -> Self {
+    Scheduler { state: Rc::new(SchedulerState::default()), _listener: None }
+  }
+
+  /**
+   * Advances the scheduler by a tick of the world, running the tasks that became due.
+   * The tick listener of a scheduler created with `new` already calls this for every world tick.
+  */
+  pub fn tick(&self, world: &W) -> Result<(), SerenityError> {
+    self.state.tick(world)
+  }
+
+  /**
+   * Runs the task once, after the given number of ticks.
+  */
+  pub fn run_later<F>(&self, ticks: u64, task: F) -> TaskToken
+  where
+    F: FnOnce(&W) -> Result<(), SerenityError> + 'static
+  {
+    self.state.schedule(TaskScope::Global, ticks, None, once(task))
+  }
+
+  /**
+   * Runs the task every `period` ticks, starting `period` ticks from now.
+  */
+  pub fn run_repeating<F>(&self, period: u64, task: F) -> TaskToken
+  where
+    F: FnMut(&W) -> Result<(), SerenityError> + 'static
+  {
+    self.state.schedule(TaskScope::Global, period, Some(period), Box::new(task))
+  }
+
+  /**
+   * Runs the task once, after the given number of ticks of the world.
+  */
+  pub fn run_later_in<F>(&self, world: &WorldRef, ticks: u64, task: F) -> TaskToken
+  where
+    F: FnOnce(&W) -> Result<(), SerenityError> + 'static
+  {
+    self.state.schedule(TaskScope::World(world.identifier().to_string()), ticks, None, once(task))
+  }
+
+  /**
+   * Runs the task every `period` ticks of the world, starting `period` ticks from now.
+  */
+  pub fn run_repeating_in<F>(&self, world: &WorldRef, period: u64, task: F) -> TaskToken
+  where
+    F: FnMut(&W) -> Result<(), SerenityError> + 'static
+  {
+    self.state.schedule(TaskScope::World(world.identifier().to_string()), period, Some(period), Box::new(task))
+  }
+
+  /**
+   * Sets how long tasks may run per tick, due tasks past the budget are deferred to the next tick.
+  */
+  pub fn set_tick_budget(&self, budget: Duration) {
+    self.state.budget.set(budget);
+  }
+
+  /**
+   * Gets how long tasks may run per tick.
+  */
+  pub fn tick_budget(&self) -> Duration {
+    self.state.budget.get()
+  }
+
+  /**
+   * Gets the number of tasks that have not finished or been cancelled yet.
+  */
+  pub fn task_count(&self) -> usize {
+    let tasks = self.state.tasks.borrow();
+    let pending = self.state.pending.borrow();
+
+    tasks.iter().chain(pending.iter()).filter(|task| !task.token.is_cancelled()).count()
+  }
+}
+
+/**
+ * A token to cancel a scheduled task, which can be cloned and moved into the task itself.
+*/
+#[derive(Clone, Default)]
+pub struct TaskToken {
+  cancelled: Rc<Cell<bool>>,
+}
+
+impl TaskToken {
+  /**
+   * Cancels the task, it will not run again.
+  */
+  pub fn cancel(&self) {
+    self.cancelled.set(true);
+  }
+
+  /**
+   * Checks if the task was cancelled.
+  */
+  pub fn is_cancelled(&self) -> bool {
+    self.cancelled.get()
+  }
+}
+
+fn once<W, F>(task: F) -> TaskCallback<W>
+where
+  F: FnOnce(&W) -> Result<(), SerenityError> + 'static
+{
+  // The scheduler drops the task after its first run, so it is only ever called once
+  let mut task = Some(task);
+
+  Box::new(move |world| match task.take() {
+    Some(task) => task(world),
+    None => Ok(())
+  })
+}
+
+#[derive(Clone, PartialEq, Eq)]
+enum TaskScope {
+  Global,
+  World(String),
+}
+
+struct Task<W> {
+  id: u64,
+  scope: TaskScope,
+  due: u64,
+  period: Option<u64>,
+  callback: TaskCallback<W>,
+  token: TaskToken,
+}
+
+struct SchedulerState<W> {
+  // Tasks that are waiting to become due
+  tasks: RefCell<Vec<Task<W>>>,
+  // Tasks scheduled since the last tick, kept apart so tasks can schedule tasks
+  pending: RefCell<Vec<Task<W>>>,
+  // The number of ticks each scope has seen
+  ticks: RefCell<Vec<(TaskScope, u64)>>,
+  // The world global tasks follow
+  primary: RefCell<Option<String>>,
+  budget: Cell<Duration>,
+  next_id: Cell<u64>,
+}
+
+impl<W> Default for SchedulerState<W> {
+  fn default() -> Self {
+    SchedulerState {
+      tasks: RefCell::default(),
+      pending: RefCell::default(),
+      ticks: RefCell::default(),
+      primary: RefCell::default(),
+      budget: Cell::new(DEFAULT_TICK_BUDGET),
+      next_id: Cell::new(0),
+    }
+  }
+}
+
+impl<W: WorldBackend> SchedulerState<W> {
+  fn schedule(&self, scope: TaskScope, delay: u64, period: Option<u64>, callback: TaskCallback<W>) -> TaskToken {
+    let token = TaskToken::default();
+
+    // A task always waits at least until the next tick
+    let due = self.current_tick(&scope) + delay.max(1);
+    let period = period.map(|period| period.max(1));
+
+    let id = self.next_id.get();
+    self.next_id.set(id + 1);
+
+    self.pending.borrow_mut().push(Task { id, scope, due, period, callback, token: token.clone() });
+
+    token
+  }
+
+  fn current_tick(&self, scope: &TaskScope) -> u64 {
+    self.ticks.borrow().iter()
+      .find(|(s, _)| s == scope)
+      .map_or(0, |(_, tick)| *tick)
+  }
+
+  fn advance(&self, scope: TaskScope) -> u64 {
+    let mut ticks = self.ticks.borrow_mut();
+
+    match ticks.iter_mut().find(|(s, _)| *s == scope) {
+      Some((_, tick)) => {
+        *tick += 1;
+        *tick
+      },
+      None => {
+        ticks.push((scope, 1));
+        1
+      }
+    }
+  }
+
+  fn tick(&self, world: &W) -> Result<(), SerenityError> {
+    let started = Instant::now();
+    let identifier = world.identifier();
+
+    // The first world that ticks drives the global tasks
+    let is_primary = {
+      let mut primary = self.primary.borrow_mut();
+      *primary.get_or_insert_with(|| identifier.clone()) == identifier
+    };
+
+    // Advance the scopes of the world
+    let world_scope = TaskScope::World(identifier);
+    let world_tick = self.advance(world_scope.clone());
+    let global_tick = match is_primary {
+      true => Some(self.advance(TaskScope::Global)),
+      false => None
+    };
+
+    // Take the tasks out, so they can schedule new ones while running
+    let mut tasks = std::mem::take(&mut *self.tasks.borrow_mut());
+    tasks.append(&mut self.pending.borrow_mut());
+    tasks.retain(|task| !task.token.is_cancelled());
+
+    // Run overdue tasks first, in the order they were scheduled
+    tasks.sort_by_key(|task| (task.due, task.id));
+
+    let budget = self.budget.get();
+    let mut finished = Vec::new();
+    let mut ran = 0;
+    let mut result = Ok(());
+    for (index, task) in tasks.iter_mut().enumerate() {
+      let now = match &task.scope {
+        TaskScope::Global => global_tick,
+        scope if *scope == world_scope => Some(world_tick),
+        _ => None
+      };
+
+      // Skip tasks that are not due, or belong to another world
+      let now = match now {
+        Some(now) if task.due <= now => now,
+        _ => continue
+      };
+
+      // Defer the remaining tasks once the budget is spent, running at least one per tick
+      if ran > 0 && started.elapsed() >= budget {
+        break;
+      }
+      ran += 1;
+
+      // A failing or panicking task is logged, and does not keep the other tasks from running
+      let report = match catch_panic(WRAPPER, || (task.callback)(world)) {
+        Ok(()) => None,
+        Err(SerenityError::Panic { message, backtrace, .. }) => Some(format!("Scheduled task panicked: {}\n{}", message, backtrace)),
+        Err(e) => Some(format!("Scheduled task failed: {}", e)),
+      };
+      if let Some(report) = report {
+        if let Err(e) = world.logger().error(&report) {
+          result = Err(e);
+        }
+      }
+
+      // Reschedule repeating tasks, and drop the others
+      match task.period {
+        Some(period) => task.due = now + period,
+        None => finished.push(index)
+      }
+    }
+
+    // Remove the finished tasks
+    for index in finished.into_iter().rev() {
+      tasks.swap_remove(index);
+    }
+    tasks.retain(|task| !task.token.is_cancelled());
+
+    *self.tasks.borrow_mut() = tasks;
+
+    result
+  }
+}
@@ -1,53 +1,54 @@
-use napi::{bindgen_prelude::FromNapiValue, JsObject, NapiValue, Result};
+use napi::JsObject;
 
-use crate::{logger::logger::Logger, utils::node_converter::get_node_object};
+use crate::error::error::SerenityError;
+use crate::logger::logger::Logger;
+use crate::protocol::packets::intercept::{PacketAction, PacketDirection};
+use crate::protocol::packets::NetworkPacket;
+use crate::world::player::player::Player;
+use crate::JsWrapper;
 
+use super::listener::ListenerHandle;
+use super::network::Network;
 use super::world::Worlds;
 
+#[derive(JsWrapper)]
 pub struct Serenity {
   pub object: JsObject,
   pub env: napi::Env,
+  #[js(nested)]
   pub logger: Logger,
-  pub worlds: Worlds
+  #[js(nested)]
+  pub worlds: Worlds,
+  #[js(nested)]
+  pub network: Network,
 }
 
 impl Serenity {
-  pub fn start(&self) {
-    self.logger.log("Starting the Serenity application");
+  pub fn start(&self) -> Result<(), SerenityError> {
+    self.logger.log("Starting the Serenity application")
   }
-}
-
-impl FromNapiValue for Serenity {
-  unsafe fn from_napi_value(env: napi::sys::napi_env, value: napi::sys::napi_value) -> Result<Self> {
-    // Create the JsObject from the napi_value
-    let object = match JsObject::from_raw(env.clone(), value.clone()) {
-      Ok(obj) => obj,
-      Err(e) => return Err(napi::Error::new(
-        napi::Status::GenericFailure,
-        e.to_string()
-      ))
-    };
-
-    // Create the logger instance
-    let logger_object = get_node_object(&object, "logger");
-    let logger = match logger_object {
-      Ok(logger) => Logger::new(env.into(), logger),
-      Err(e) => return Err(napi::Error::new(
-        napi::Status::GenericFailure,
-        e.to_string()
-      ))
-    };
 
-    // Create the worlds instance
-    let worlds_object = get_node_object(&object, "worlds");
-    let worlds = match worlds_object {
-      Ok(worlds) => Worlds::new(env.into(), worlds),
-      Err(e) => return Err(napi::Error::new(
-        napi::Status::GenericFailure,
-        e.to_string()
-      ))
-    };
-
-    Ok(Serenity { logger, env: env.into(), object, worlds })
+  /**
+   * Registers a handler for the packets of type `P` travelling in the direction.
+   * The handler gets the decoded packet before SerenityJS handles or sends it, along with the player
+   * of the connection, which is `None` until the player has spawned.
+   * Changes the handler makes are written back to the packet, and `PacketAction::Cancel` drops it.
+   *
+   * ```ignore
+   * serenity.on_packet::<TextPacket, _>(PacketDirection::Clientbound, |_, packet| {
+   *   packet.message = format!("[Server] {}", packet.message);
+   *   PacketAction::Pass
+   * })?.detach();
+   * ```
+   *
+   * Packets that do not match the definition of `P` are let through untouched, and so are packets
+   * while the handler panics. The listener stays registered until the returned handle is cancelled or dropped.
+  */
+  pub fn on_packet<P, F>(&self, direction: PacketDirection, handler: F) -> Result<ListenerHandle, SerenityError>
+  where
+    P: NetworkPacket + 'static,
+    F: FnMut(Option<&Player>, &mut P) -> PacketAction + 'static
+  {
+    self.network.on_packet(self, direction, handler)
   }
-}
\ No newline at end of file
+}
@@ -1,57 +1,165 @@
-use napi::{bindgen_prelude::FromNapiValue, NapiValue};
+use std::cell::RefCell;
+use std::rc::Rc;
 
+use napi::CallContext;
+
+use crate::error::error::SerenityError;
 use crate::logger::logger::Logger;
+use crate::utils::js_reference::JsReference;
+use crate::utils::js_wrapper::JsWrapper;
+use crate::utils::panic::catch_panic;
 use crate::utils::node_converter::*;
+use crate::world::events::cancellable::Cancellable;
+use crate::world::events::signal::Signal;
+use crate::world::player::player::Player;
+use crate::world::world::world::World;
+use crate::{js_method, JsWrapper};
+
+use super::listener::{ListenerHandle, PanicGuard};
 
+#[derive(JsWrapper)]
 pub struct Worlds {
   pub object: napi::JsObject,
   pub env: napi::Env,
+  #[js(nested)]
   pub logger: Logger,
 }
 
 impl Worlds {
-  pub fn new(env: napi::Env, object: napi::JsObject) -> Self {
-    // Get the logger object
-    let logger_object = get_node_object(&object, "logger").unwrap();
+  /**
+   * Gets the world with the given identifier, if it exists.
+  */
+  #[js_method("getWorld")]
+  pub fn get_world(&self, identifier: &str) -> Result<Option<World>, SerenityError>;
+
+  /**
+   * Gets every player that is currently online, across all worlds.
+  */
+  #[js_method("getPlayers")]
+  pub fn get_players(&self) -> Result<Vec<Player>, SerenityError>;
 
-    Worlds { env, object, logger: Logger::new(env.clone(), logger_object) }
+  /**
+   * Registers a listener for the event of the signal `S`.
+   * The listener stays registered until the returned handle is cancelled or dropped.
+  */
+  pub fn on<S, F>(&self, callback: F) -> Result<ListenerHandle, SerenityError>
+  where
+    S: Signal,
+    F: FnMut(S) -> Result<(), SerenityError> + 'static
+  {
+    self.register("on", "off", || (), callback)
   }
 
-  pub fn on(&self, event: i32, callback: napi::JsFunction) {
-    // Get the on function
-    let on = get_node_func(&self.object, "on").unwrap();
+  /**
+   * Registers a listener that is removed after the first time the event of the signal `S` is emitted.
+  */
+  pub fn once<S, F>(&self, callback: F) -> Result<ListenerHandle, SerenityError>
+  where
+    S: Signal,
+    F: FnMut(S) -> Result<(), SerenityError> + 'static
+  {
+    self.register("once", "off", || (), callback)
+  }
+
+  /**
+   * Registers a listener that runs before SerenityJS processes the event of the signal `S`.
+   * The listener may modify or cancel the signal, a cancelled signal is never processed.
+  */
+  pub fn before<S, F>(&self, mut callback: F) -> Result<ListenerHandle, SerenityError>
+  where
+    S: Cancellable,
+    F: FnMut(&mut S) -> Result<(), SerenityError> + 'static
+  {
+    // SerenityJS cancels the event when a before listener returns false,
+    // a disabled listener lets every event through
+    self.register("before", "removeBefore", || true, move |mut signal: S| {
+      callback(&mut signal)?;
+
+      Ok(!signal.is_cancelled()?)
+    })
+  }
+
+  fn register<S, F, R>(&self, method: &str, remove_method: &'static str, disabled: fn() -> R, callback: F) -> Result<ListenerHandle, SerenityError>
+  where
+    S: Signal,
+    F: FnMut(S) -> Result<R, SerenityError> + 'static,
+    R: napi::bindgen_prelude::ToNapiValue + 'static
+  {
+    let callback = RefCell::new(callback);
+    let guard = Rc::new(PanicGuard::default());
+    let listener_guard = guard.clone();
+
+    // Keep the logger alive, so panics can be reported after the registering scope ended
+    let logger = JsReference::new(self.env, &self.logger.object, Self::WRAPPER)?;
+
+    // Create the JS function that decodes the signal and hands it to the callback
+    let function = self.env.create_function_from_closure("serenityrsListener", move |ctx: CallContext| -> napi::Result<R> {
+      // Check if the listener was disabled for panicking too often
+      if listener_guard.is_disabled() {
+        return Ok(disabled());
+      }
+
+      let object = ctx.get::<napi::JsObject>(0)?;
+      let signal = S::from_js_object(*ctx.env, object)?;
 
-    let event = convert_to_js_number(&self.env, event).unwrap();
+      // A listener that emits its own event would borrow the callback twice
+      let mut callback = callback.try_borrow_mut()
+        .map_err(|_| napi::Error::from_reason(format!("{} listener was re-entered", S::WRAPPER)))?;
 
-    let ev_val = event.into_unknown();
-    let cb_val = callback.into_unknown();
+      // Catch panics, so they never unwind into SerenityJS
+      match catch_panic(S::WRAPPER, || callback(signal)) {
+        Err(SerenityError::Panic { wrapper, message, backtrace }) => {
+          let limit = listener_guard.record_panic();
+          report_panic(&logger, wrapper, &message, &backtrace, limit);
 
-    // Call the on function
-    on.call(Some(&self.object), &[ev_val, cb_val]).unwrap();
+          Err(SerenityError::Panic { wrapper, message, backtrace }.into())
+        },
+        result => {
+          listener_guard.record_success();
+
+          Ok(result?)
+        }
+      }
+    }).map_err(|e| SerenityError::NapiFailure { wrapper: Self::WRAPPER, message: e.to_string() })?;
+
+    // Keep a handle to the listener, so it can be removed later
+    let handle = ListenerHandle::new(self.env, &self.object, &function, S::EVENT.into(), remove_method, guard)?;
+
+    // Convert the event to a JsNumber
+    let event = convert_to_js_number(&self.env, Self::WRAPPER, S::EVENT.id())?;
+
+    // Call the on or once function
+    if let Err(e) = call_node_method(&self.object, Self::WRAPPER, method, &[event.into_unknown(), function.into_unknown()]) {
+      // The listener was never registered, so there is nothing to remove
+      handle.detach();
+      return Err(e);
+    }
+
+    Ok(handle)
   }
 }
 
-impl FromNapiValue for Worlds {
-  unsafe fn from_napi_value(env: napi::sys::napi_env, value: napi::sys::napi_value) -> napi::Result<Self> {
-    // Create the JsObject from the napi_value
-    let object = match napi::JsObject::from_raw(env.clone(), value.clone()) {
-      Ok(obj) => obj,
-      Err(e) => return Err(napi::Error::new(
-        napi::Status::GenericFailure,
-        e.to_string()
-      ))
-    };
-
-    // Create the logger instance
-    let logger_object = get_node_object(&object, "logger");
-    let logger = match logger_object {
-      Ok(logger) => Logger::new(env.into(), logger),
-      Err(e) => return Err(napi::Error::new(
-        napi::Status::GenericFailure,
-        e.to_string()
-      ))
-    };
-
-    Ok(Worlds { logger, env: env.into(), object })
+/**
+ * Reports a panicked listener through the logger, falling back to stderr when it can not be reached.
+*/
+pub(crate) fn report_panic(logger: &JsReference, wrapper: &str, message: &str, backtrace: &str, disabled_after: Option<u32>) {
+  let mut report = format!("Listener for {} panicked: {}\n{}", wrapper, message, backtrace);
+  if let Some(panics) = disabled_after {
+    report.push_str(&format!("\nListener for {} was disabled after {} consecutive panics", wrapper, panics));
   }
-}
\ No newline at end of file
+
+  log_report(logger, &report);
+}
+
+/**
+ * Logs the report as an error, falling back to stderr when the logger can not be reached.
+*/
+pub(crate) fn log_report(logger: &JsReference, report: &str) {
+  let logged = logger.object()
+    .and_then(|object| Logger::from_js_object(logger.env(), object))
+    .and_then(|logger| logger.error(report));
+
+  if logged.is_err() {
+    eprintln!("{}", report);
+  }
+}
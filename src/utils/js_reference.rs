@@ -0,0 +1,46 @@
+use napi::{JsObject, JsUnknown, NapiRaw, NapiValue, Ref};
+
+use crate::error::error::SerenityError;
+
+/**
+ * A reference keeping a JS object alive beyond the callback it was received in.
+ * The reference is released when dropped, which has to happen on the JS thread.
+*/
+pub(crate) struct JsReference {
+  env: napi::sys::napi_env,
+  reference: Ref<()>,
+  wrapper: &'static str,
+}
+
+impl JsReference {
+  pub fn new<V: NapiRaw>(env: napi::Env, value: &V, wrapper: &'static str) -> Result<Self, SerenityError> {
+    // The value is only borrowed, so reference it through its raw handle
+    let value = unsafe { JsUnknown::from_raw_unchecked(env.raw(), value.raw()) };
+    let reference = env.create_reference(value)
+      .map_err(|e| SerenityError::NapiFailure { wrapper, message: e.to_string() })?;
+
+    Ok(JsReference { env: env.raw(), reference, wrapper })
+  }
+
+  /**
+   * Gets the env the object belongs to.
+  */
+  pub fn env(&self) -> napi::Env {
+    unsafe { napi::Env::from_raw(self.env) }
+  }
+
+  /**
+   * Gets the object back from the reference.
+  */
+  pub fn object(&self) -> Result<JsObject, SerenityError> {
+    self.env().get_reference_value::<JsObject>(&self.reference)
+      .map_err(|e| SerenityError::NapiFailure { wrapper: self.wrapper, message: e.to_string() })
+  }
+}
+
+impl Drop for JsReference {
+  fn drop(&mut self) {
+    let env = self.env();
+    let _ = self.reference.unref(env);
+  }
+}
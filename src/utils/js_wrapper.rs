@@ -0,0 +1,291 @@
+use napi::{JsObject, JsUnknown, ValueType};
+
+use crate::error::error::SerenityError;
+use crate::protocol::types::block_position::BlockPosition;
+use crate::block::block_permutation::BlockPermutation;
+use crate::protocol::types::rotation::Rotation;
+use crate::protocol::types::vector3f::Vector3f;
+use crate::utils::node_converter::*;
+
+/**
+ * A Rust struct backed by a SerenityJS object.
+ * Usually implemented through `#[derive(JsWrapper)]`.
+*/
+pub trait JsWrapper: Sized {
+  /** The name used as context in errors raised by the wrapper. */
+  const WRAPPER: &'static str;
+
+  /**
+   * Creates the wrapper from the JS object it represents.
+  */
+  fn from_js_object(env: napi::Env, object: JsObject) -> Result<Self, SerenityError>;
+
+  /**
+   * Gets the JS object the wrapper represents.
+  */
+  fn js_object(&self) -> &JsObject;
+
+  /**
+   * Gets the JS object the wrapper represents, for writing properties to it.
+  */
+  fn js_object_mut(&mut self) -> &mut JsObject;
+
+  /**
+   * Gets the env the JS object belongs to.
+  */
+  fn js_env(&self) -> napi::Env;
+}
+
+/**
+ * A Rust value that can be read from a JS value, used for wrapper fields and method results.
+*/
+pub trait FromNodeValue: Sized {
+  fn from_node_value(env: napi::Env, value: JsUnknown, wrapper: &'static str, name: &str) -> Result<Self, SerenityError>;
+}
+
+/**
+ * A Rust value that can be passed to a JS method as an argument.
+*/
+pub trait ToNodeValue {
+  fn to_node_value(&self, env: napi::Env, wrapper: &'static str) -> Result<JsUnknown, SerenityError>;
+}
+
+impl FromNodeValue for () {
+  fn from_node_value(_: napi::Env, _: JsUnknown, _: &'static str, _: &str) -> Result<Self, SerenityError> {
+    Ok(())
+  }
+}
+
+impl FromNodeValue for String {
+  fn from_node_value(_: napi::Env, value: JsUnknown, wrapper: &'static str, name: &str) -> Result<Self, SerenityError> {
+    let string = expect_node_type::<napi::JsString>(value, wrapper, name, ValueType::String)?;
+
+    string.into_utf8()
+      .and_then(|s| s.into_owned())
+      .map_err(|e| SerenityError::NapiFailure { wrapper, message: e.to_string() })
+  }
+}
+
+impl FromNodeValue for i32 {
+  fn from_node_value(_: napi::Env, value: JsUnknown, wrapper: &'static str, name: &str) -> Result<Self, SerenityError> {
+    node_value_to_i32(value, wrapper, name)
+  }
+}
+
+impl FromNodeValue for f64 {
+  fn from_node_value(_: napi::Env, value: JsUnknown, wrapper: &'static str, name: &str) -> Result<Self, SerenityError> {
+    let number = expect_node_type::<napi::JsNumber>(value, wrapper, name, ValueType::Number)?;
+
+    number.get_double().map_err(|e| SerenityError::NapiFailure { wrapper, message: e.to_string() })
+  }
+}
+
+impl FromNodeValue for u64 {
+  fn from_node_value(_: napi::Env, value: JsUnknown, wrapper: &'static str, name: &str) -> Result<Self, SerenityError> {
+    let bigint = expect_node_type::<napi::JsBigInt>(value, wrapper, name, ValueType::BigInt)?;
+
+    // Get the value, checking that no precision was lost
+    match bigint.get_u64() {
+      Ok((value, true)) => Ok(value),
+      Ok((_, false)) => Err(SerenityError::ConversionOverflow { wrapper, property: name.to_string(), target: "u64" }),
+      Err(e) => Err(SerenityError::NapiFailure { wrapper, message: e.to_string() })
+    }
+  }
+}
+
+impl FromNodeValue for bool {
+  fn from_node_value(_: napi::Env, value: JsUnknown, wrapper: &'static str, name: &str) -> Result<Self, SerenityError> {
+    node_value_to_bool(value, wrapper, name)
+  }
+}
+
+impl FromNodeValue for JsObject {
+  fn from_node_value(_: napi::Env, value: JsUnknown, wrapper: &'static str, name: &str) -> Result<Self, SerenityError> {
+    node_value_to_object(value, wrapper, name)
+  }
+}
+
+impl FromNodeValue for Vector3f {
+  fn from_node_value(_: napi::Env, value: JsUnknown, wrapper: &'static str, name: &str) -> Result<Self, SerenityError> {
+    Vector3f::from_js_object(node_value_to_object(value, wrapper, name)?)
+  }
+}
+
+impl FromNodeValue for BlockPosition {
+  fn from_node_value(_: napi::Env, value: JsUnknown, wrapper: &'static str, name: &str) -> Result<Self, SerenityError> {
+    BlockPosition::from_js_object(node_value_to_object(value, wrapper, name)?)
+  }
+}
+
+impl FromNodeValue for Rotation {
+  fn from_node_value(_: napi::Env, value: JsUnknown, wrapper: &'static str, name: &str) -> Result<Self, SerenityError> {
+    Rotation::from_js_object(node_value_to_object(value, wrapper, name)?)
+  }
+}
+
+impl FromNodeValue for BlockPermutation {
+  fn from_node_value(env: napi::Env, value: JsUnknown, wrapper: &'static str, name: &str) -> Result<Self, SerenityError> {
+    BlockPermutation::from_js_object(env, &node_value_to_object(value, wrapper, name)?)
+  }
+}
+
+impl<T: FromNodeValue> FromNodeValue for Option<T> {
+  fn from_node_value(env: napi::Env, value: JsUnknown, wrapper: &'static str, name: &str) -> Result<Self, SerenityError> {
+    // Undefined and null read as None
+    match value.get_type() {
+      Ok(ValueType::Undefined | ValueType::Null) => Ok(None),
+      Ok(_) => Ok(Some(T::from_node_value(env, value, wrapper, name)?)),
+      Err(e) => Err(SerenityError::NapiFailure { wrapper, message: e.to_string() })
+    }
+  }
+}
+
+impl<T: FromNodeValue> FromNodeValue for Vec<T> {
+  fn from_node_value(env: napi::Env, value: JsUnknown, wrapper: &'static str, name: &str) -> Result<Self, SerenityError> {
+    let array = node_value_to_object(value, wrapper, name)?;
+
+    // Check that the object is an array
+    let is_array = array.is_array().map_err(|e| SerenityError::NapiFailure { wrapper, message: e.to_string() })?;
+    if !is_array {
+      return Err(SerenityError::InvalidType { wrapper, property: name.to_string(), expected: "array", found: "object".to_string() });
+    }
+
+    // Convert every element of the array
+    let length = array.get_array_length().map_err(|e| SerenityError::NapiFailure { wrapper, message: e.to_string() })?;
+    (0..length).map(|index| {
+      let element = array.get_element::<JsUnknown>(index).map_err(|e| SerenityError::NapiFailure { wrapper, message: e.to_string() })?;
+
+      T::from_node_value(env, element, wrapper, name)
+    }).collect()
+  }
+}
+
+impl ToNodeValue for str {
+  fn to_node_value(&self, env: napi::Env, wrapper: &'static str) -> Result<JsUnknown, SerenityError> {
+    Ok(convert_to_js_string(&env, wrapper, self)?.into_unknown())
+  }
+}
+
+impl ToNodeValue for String {
+  fn to_node_value(&self, env: napi::Env, wrapper: &'static str) -> Result<JsUnknown, SerenityError> {
+    self.as_str().to_node_value(env, wrapper)
+  }
+}
+
+impl ToNodeValue for i32 {
+  fn to_node_value(&self, env: napi::Env, wrapper: &'static str) -> Result<JsUnknown, SerenityError> {
+    Ok(convert_to_js_number(&env, wrapper, *self)?.into_unknown())
+  }
+}
+
+impl ToNodeValue for f64 {
+  fn to_node_value(&self, env: napi::Env, wrapper: &'static str) -> Result<JsUnknown, SerenityError> {
+    Ok(create_node_float64(&env, wrapper, *self)?.into_unknown())
+  }
+}
+
+impl ToNodeValue for bool {
+  fn to_node_value(&self, env: napi::Env, wrapper: &'static str) -> Result<JsUnknown, SerenityError> {
+    Ok(convert_to_js_boolean(&env, wrapper, *self)?.into_unknown())
+  }
+}
+
+impl ToNodeValue for Vector3f {
+  fn to_node_value(&self, env: napi::Env, _: &'static str) -> Result<JsUnknown, SerenityError> {
+    Ok(self.to_js_object(env)?.into_unknown())
+  }
+}
+
+impl ToNodeValue for BlockPosition {
+  fn to_node_value(&self, env: napi::Env, _: &'static str) -> Result<JsUnknown, SerenityError> {
+    Ok(self.to_js_object(env)?.into_unknown())
+  }
+}
+
+impl ToNodeValue for Rotation {
+  fn to_node_value(&self, env: napi::Env, _: &'static str) -> Result<JsUnknown, SerenityError> {
+    Ok(self.to_js_object(env)?.into_unknown())
+  }
+}
+
+impl<T: ToNodeValue> ToNodeValue for Option<T> {
+  fn to_node_value(&self, env: napi::Env, wrapper: &'static str) -> Result<JsUnknown, SerenityError> {
+    // Pass undefined for a missing argument, which JS treats like an omitted one
+    match self {
+      Some(value) => value.to_node_value(env, wrapper),
+      None => env.get_undefined()
+        .map(|u| u.into_unknown())
+        .map_err(|e| SerenityError::NapiFailure { wrapper, message: e.to_string() })
+    }
+  }
+}
+
+impl<T: ToNodeValue + ?Sized> ToNodeValue for &T {
+  fn to_node_value(&self, env: napi::Env, wrapper: &'static str) -> Result<JsUnknown, SerenityError> {
+    (**self).to_node_value(env, wrapper)
+  }
+}
+
+/**
+ * Reads a required property of the object.
+*/
+pub fn read_node_property<T: FromNodeValue>(env: napi::Env, object: &JsObject, wrapper: &'static str, name: &str) -> Result<T, SerenityError> {
+  let property = get_node_property(object, wrapper, name)?;
+
+  T::from_node_value(env, property, wrapper, name)
+}
+
+/**
+ * Reads an optional property of the object, a missing, undefined or null property reads as `None`.
+*/
+pub fn read_optional_node_property<T: FromNodeValue>(env: napi::Env, object: &JsObject, wrapper: &'static str, name: &str) -> Result<Option<T>, SerenityError> {
+  match get_optional_node_property(object, wrapper, name)? {
+    Some(property) => Ok(Some(T::from_node_value(env, property, wrapper, name)?)),
+    None => Ok(None)
+  }
+}
+
+/**
+ * Reads a required property of the object holding another wrapped object.
+*/
+pub fn read_nested_node_property<T: JsWrapper>(env: napi::Env, object: &JsObject, wrapper: &'static str, name: &str) -> Result<T, SerenityError> {
+  let property = get_node_object(object, wrapper, name)?;
+
+  T::from_js_object(env, property)
+}
+
+/**
+ * Reads an optional property of the object holding another wrapped object.
+*/
+pub fn read_optional_nested_node_property<T: JsWrapper>(env: napi::Env, object: &JsObject, wrapper: &'static str, name: &str) -> Result<Option<T>, SerenityError> {
+  match get_optional_node_property(object, wrapper, name)? {
+    Some(property) => Ok(Some(T::from_js_object(env, node_value_to_object(property, wrapper, name)?)?)),
+    None => Ok(None)
+  }
+}
+
+fn get_optional_node_property(object: &JsObject, wrapper: &'static str, name: &str) -> Result<Option<JsUnknown>, SerenityError> {
+  // A missing property reads as None
+  let property = match get_node_property(object, wrapper, name) {
+    Ok(property) => property,
+    Err(SerenityError::MissingProperty { .. }) => return Ok(None),
+    Err(e) => return Err(e)
+  };
+
+  // So does null
+  match property.get_type() {
+    Ok(ValueType::Null) => Ok(None),
+    Ok(_) => Ok(Some(property)),
+    Err(e) => Err(SerenityError::NapiFailure { wrapper, message: e.to_string() })
+  }
+}
+
+/**
+ * Calls a method of a wrapped object, converting the arguments and the result.
+ * This is the shim `#[js_method]` expands to.
+*/
+pub fn call_wrapper_method<T: FromNodeValue>(env: napi::Env, object: &JsObject, wrapper: &'static str, name: &str, args: &[JsUnknown]) -> Result<T, SerenityError> {
+  let result = call_node_method(object, wrapper, name, args)?;
+
+  T::from_node_value(env, result, wrapper, name)
+}
@@ -1 +1,4 @@
-pub mod node_converter;
\ No newline at end of file
+pub mod node_converter;
+pub mod js_wrapper;
+pub(crate) mod js_reference;
+pub mod panic;
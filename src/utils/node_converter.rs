@@ -1,197 +1,236 @@
-use napi::JsObject;
+use napi::bindgen_prelude::ToNapiValue;
+use napi::{JsObject, JsUnknown, NapiRaw, NapiValue, ValueType};
 
-pub fn get_node_func(object: &JsObject, name: &str) -> napi::Result<napi::JsFunction> {
+use crate::error::error::SerenityError;
+
+fn napi_failure(wrapper: &'static str, error: napi::Error) -> SerenityError {
+  SerenityError::NapiFailure { wrapper, message: error.to_string() }
+}
+
+fn value_type_name(value_type: ValueType) -> &'static str {
+  match value_type {
+    ValueType::Undefined => "undefined",
+    ValueType::Null => "null",
+    ValueType::Boolean => "boolean",
+    ValueType::Number => "number",
+    ValueType::String => "string",
+    ValueType::Symbol => "symbol",
+    ValueType::Object => "object",
+    ValueType::Function => "function",
+    ValueType::External => "external",
+    ValueType::BigInt => "bigint",
+    ValueType::Unknown => "unknown",
+  }
+}
+
+/**
+ * Checks that a JS value is of the expected type, and casts it to the matching napi type.
+*/
+pub fn expect_node_type<V: NapiValue>(value: JsUnknown, wrapper: &'static str, name: &str, expected: ValueType) -> Result<V, SerenityError> {
+  // Get the type of the value
+  let value_type = value.get_type().map_err(|e| napi_failure(wrapper, e))?;
+
+  // Check if the value is of the expected type
+  if value_type != expected {
+    return Err(SerenityError::InvalidType {
+      wrapper,
+      property: name.to_string(),
+      expected: value_type_name(expected),
+      found: value_type_name(value_type).to_string()
+    });
+  }
+
+  // Cast the value to the expected type
+  Ok(unsafe { value.cast::<V>() })
+}
+
+/**
+ * Gets a property from the object, raising an error if the property is missing or undefined.
+*/
+pub fn get_node_property(object: &JsObject, wrapper: &'static str, name: &str) -> Result<JsUnknown, SerenityError> {
+  // Check if the object has the property
+  let has_property = object.has_named_property(name).map_err(|e| napi_failure(wrapper, e))?;
+  if !has_property {
+    return Err(SerenityError::MissingProperty { wrapper, property: name.to_string() });
+  }
+
+  // Get the property from the object
+  let property = object.get_named_property::<JsUnknown>(name).map_err(|e| napi_failure(wrapper, e))?;
+
+  // Treat an undefined property as missing
+  match property.get_type().map_err(|e| napi_failure(wrapper, e))? {
+    ValueType::Undefined => Err(SerenityError::MissingProperty { wrapper, property: name.to_string() }),
+    _ => Ok(property)
+  }
+}
+
+pub fn get_node_func(object: &JsObject, wrapper: &'static str, name: &str) -> Result<napi::JsFunction, SerenityError> {
   // Get the function from the object
-  let func = match object.get_named_property::<napi::JsFunction>(name) {
-    Ok(f) => f,
-    Err(e) => return Err(napi::Error::new(
-      napi::Status::GenericFailure,
-      e.to_string()
-    ))
-  };
+  let property = get_node_property(object, wrapper, name)?;
 
   // Return the function
-  return Ok(func);
+  expect_node_type(property, wrapper, name, ValueType::Function)
 }
 
-pub fn get_node_object(object: &JsObject, name: &str) -> napi::Result<napi::JsObject> {
+pub fn get_node_object(object: &JsObject, wrapper: &'static str, name: &str) -> Result<JsObject, SerenityError> {
   // Get the object from the object
-  let obj = match object.get_named_property::<napi::JsObject>(name) {
-    Ok(o) => o,
-    Err(e) => return Err(napi::Error::new(
-      napi::Status::GenericFailure,
-      e.to_string()
-    ))
-  };
+  let property = get_node_property(object, wrapper, name)?;
 
   // Return the object
-  return Ok(obj);
+  expect_node_type(property, wrapper, name, ValueType::Object)
 }
 
-pub fn get_node_string(object: &JsObject, name: &str) -> napi::Result<napi::JsString> {
+pub fn get_node_string(object: &JsObject, wrapper: &'static str, name: &str) -> Result<String, SerenityError> {
   // Get the string from the object
-  let string = match object.get_named_property::<napi::JsString>(name) {
-    Ok(s) => s,
-    Err(e) => return Err(napi::Error::new(
-      napi::Status::GenericFailure,
-      e.to_string()
-    ))
-  };
+  let property = get_node_property(object, wrapper, name)?;
+  let string = expect_node_type::<napi::JsString>(property, wrapper, name, ValueType::String)?;
 
   // Return the string
-  return Ok(string);
+  string.into_utf8()
+    .and_then(|s| s.into_owned())
+    .map_err(|e| napi_failure(wrapper, e))
 }
 
-pub fn get_node_bigint(object: &JsObject, name: &str) -> napi::Result<napi::JsBigInt> {
+pub fn get_node_bigint(object: &JsObject, wrapper: &'static str, name: &str) -> Result<u64, SerenityError> {
   // Get the bigint from the object
-  let bigint = match object.get_named_property::<napi::JsBigInt>(name) {
-    Ok(b) => b,
-    Err(e) => return Err(napi::Error::new(
-      napi::Status::GenericFailure,
-      e.to_string()
-    ))
-  };
+  let property = get_node_property(object, wrapper, name)?;
+  let bigint = expect_node_type::<napi::JsBigInt>(property, wrapper, name, ValueType::BigInt)?;
+
+  // Get the value, checking that no precision was lost
+  let (value, lossless) = bigint.get_u64().map_err(|e| napi_failure(wrapper, e))?;
+  if !lossless {
+    return Err(SerenityError::ConversionOverflow { wrapper, property: name.to_string(), target: "u64" });
+  }
 
   // Return the bigint
-  return Ok(bigint);
+  Ok(value)
 }
 
-pub fn get_node_number(object: &JsObject, name: &str) -> napi::Result<napi::JsNumber> {
+pub fn get_node_number(object: &JsObject, wrapper: &'static str, name: &str) -> Result<i32, SerenityError> {
   // Get the number from the object
-  let number = match object.get_named_property::<napi::JsNumber>(name) {
-    Ok(n) => n,
-    Err(e) => return Err(napi::Error::new(
-      napi::Status::GenericFailure,
-      e.to_string()
-    ))
-  };
+  let property = get_node_property(object, wrapper, name)?;
 
   // Return the number
-  return Ok(number);
+  node_value_to_i32(property, wrapper, name)
 }
 
-pub fn get_node_boolean(object: &JsObject, name: &str) -> napi::Result<napi::JsBoolean> {
+pub fn get_node_boolean(object: &JsObject, wrapper: &'static str, name: &str) -> Result<bool, SerenityError> {
   // Get the boolean from the object
-  let boolean = match object.get_named_property::<napi::JsBoolean>(name) {
-    Ok(b) => b,
-    Err(e) => return Err(napi::Error::new(
-      napi::Status::GenericFailure,
-      e.to_string()
-    ))
-  };
+  let property = get_node_property(object, wrapper, name)?;
 
   // Return the boolean
-  return Ok(boolean);
+  node_value_to_bool(property, wrapper, name)
 }
 
-pub fn get_node_float64(object: &JsObject, name: &str) -> napi::Result<napi::JsNumber> {
+pub fn get_node_float64(object: &JsObject, wrapper: &'static str, name: &str) -> Result<f64, SerenityError> {
   // Get the number from the object
-  let number = match object.get_named_property::<napi::JsNumber>(name) {
-    Ok(n) => n,
-    Err(e) => return Err(napi::Error::new(
-      napi::Status::GenericFailure,
-      e.to_string()
-    ))
-  };
+  let property = get_node_property(object, wrapper, name)?;
+  let number = expect_node_type::<napi::JsNumber>(property, wrapper, name, ValueType::Number)?;
 
   // Return the number
-  return Ok(number);
+  number.get_double().map_err(|e| napi_failure(wrapper, e))
 }
 
-pub fn create_node_object(env: &napi::Env) -> napi::Result<napi::JsObject> {
-  // Create the object from the env
-  let object = match env.create_object() {
-    Ok(o) => o,
-    Err(e) => return Err(napi::Error::new(
-      napi::Status::GenericFailure,
-      e.to_string()
-    ))
-  };
+/**
+ * Converts a JS number into an i32, raising an error if it does not fit.
+*/
+pub fn node_value_to_i32(value: JsUnknown, wrapper: &'static str, name: &str) -> Result<i32, SerenityError> {
+  // Get the number as a double, so out of range values can be detected
+  let number = expect_node_type::<napi::JsNumber>(value, wrapper, name, ValueType::Number)?;
+  let double = number.get_double().map_err(|e| napi_failure(wrapper, e))?;
+
+  // Check that the number fits into an i32
+  if !double.is_finite() || double < i32::MIN as f64 || double > i32::MAX as f64 {
+    return Err(SerenityError::ConversionOverflow { wrapper, property: name.to_string(), target: "i32" });
+  }
+
+  // Return the number, truncating any fraction like napi does
+  Ok(double as i32)
+}
 
-  // Return the object
-  return Ok(object);
+/**
+ * Converts a JS boolean into a bool.
+*/
+pub fn node_value_to_bool(value: JsUnknown, wrapper: &'static str, name: &str) -> Result<bool, SerenityError> {
+  let boolean = expect_node_type::<napi::JsBoolean>(value, wrapper, name, ValueType::Boolean)?;
+
+  boolean.get_value().map_err(|e| napi_failure(wrapper, e))
 }
 
-pub fn create_node_number(env: &napi::Env, value: i32) -> napi::Result<napi::JsNumber> {
-  // Create the number from the env
-  let number = match env.create_int32(value) {
-    Ok(n) => n,
-    Err(e) => return Err(napi::Error::new(
-      napi::Status::GenericFailure,
-      e.to_string()
-    ))
-  };
+/**
+ * Converts a JS object value into a JsObject.
+*/
+pub fn node_value_to_object(value: JsUnknown, wrapper: &'static str, name: &str) -> Result<JsObject, SerenityError> {
+  expect_node_type(value, wrapper, name, ValueType::Object)
+}
 
-  // Return the number
-  return Ok(number);
+/**
+ * Calls a method on the object, converting a thrown JS exception into an error.
+*/
+pub fn call_node_method<V: NapiRaw>(object: &JsObject, wrapper: &'static str, name: &str, args: &[V]) -> Result<JsUnknown, SerenityError> {
+  // Get the method from the object
+  let method = get_node_func(object, wrapper, name)?;
+
+  // Call the method, napi clears the pending exception and hands back its message
+  method.call(Some(object), args).map_err(|e| SerenityError::JsException {
+    wrapper,
+    method: name.to_string(),
+    message: e.reason
+  })
 }
 
-pub fn create_node_float64(env: &napi::Env, value: f64) -> napi::Result<napi::JsNumber> {
+pub fn create_node_object(env: &napi::Env, wrapper: &'static str) -> Result<JsObject, SerenityError> {
+  // Create the object from the env
+  env.create_object().map_err(|e| napi_failure(wrapper, e))
+}
+
+pub fn create_node_number(env: &napi::Env, wrapper: &'static str, value: i32) -> Result<napi::JsNumber, SerenityError> {
   // Create the number from the env
-  let number = match env.create_double(value) {
-    Ok(n) => n,
-    Err(e) => return Err(napi::Error::new(
-      napi::Status::GenericFailure,
-      e.to_string()
-    ))
-  };
+  env.create_int32(value).map_err(|e| napi_failure(wrapper, e))
+}
 
-  // Return the number
-  return Ok(number);
+pub fn create_node_float64(env: &napi::Env, wrapper: &'static str, value: f64) -> Result<napi::JsNumber, SerenityError> {
+  // Create the number from the env
+  env.create_double(value).map_err(|e| napi_failure(wrapper, e))
 }
 
-pub fn create_node_func(env: &napi::Env, name: &str, func: napi::Callback) -> napi::Result<napi::JsFunction> {
+pub fn create_node_func(env: &napi::Env, wrapper: &'static str, name: &str, func: napi::Callback) -> Result<napi::JsFunction, SerenityError> {
   // Create the function from the env
-  let function = match env.create_function(name, func) {
-    Ok(f) => f,
-    Err(e) => return Err(napi::Error::new(
-      napi::Status::GenericFailure,
-      e.to_string()
-    ))
-  };
-
-  // Return the function
-  return Ok(function);
+  env.create_function(name, func).map_err(|e| napi_failure(wrapper, e))
 }
 
-pub fn convert_to_js_string(env: &napi::Env, value: &str) -> napi::Result<napi::JsString> {
+pub fn convert_to_js_string(env: &napi::Env, wrapper: &'static str, value: &str) -> Result<napi::JsString, SerenityError> {
   // Create a string from the env
-  let string = match env.create_string(value) {
-    Ok(s) => s,
-    Err(e) => return Err(napi::Error::new(
-      napi::Status::GenericFailure,
-      e.to_string()
-    ))
-  };
-
-  // Return the string
-  return Ok(string);
+  env.create_string(value).map_err(|e| napi_failure(wrapper, e))
 }
 
-pub fn convert_to_js_number(env: &napi::Env, value: i32) -> napi::Result<napi::JsNumber> {
+pub fn convert_to_js_number(env: &napi::Env, wrapper: &'static str, value: i32) -> Result<napi::JsNumber, SerenityError> {
   // Create a number from the env
-  let number = match env.create_int32(value) {
-    Ok(n) => n,
-    Err(e) => return Err(napi::Error::new(
-      napi::Status::GenericFailure,
-      e.to_string()
-    ))
-  };
-
-  // Return the number
-  return Ok(number);
+  env.create_int32(value).map_err(|e| napi_failure(wrapper, e))
 }
 
-pub fn convert_to_js_boolean(env: &napi::Env, value: bool) -> napi::Result<napi::JsBoolean> {
+pub fn convert_to_js_boolean(env: &napi::Env, wrapper: &'static str, value: bool) -> Result<napi::JsBoolean, SerenityError> {
   // Create a boolean from the env
-  let boolean = match env.get_boolean(value) {
-    Ok(b) => b,
-    Err(e) => return Err(napi::Error::new(
-      napi::Status::GenericFailure,
-      e.to_string()
-    ))
-  };
+  env.get_boolean(value).map_err(|e| napi_failure(wrapper, e))
+}
 
-  // Return the boolean
-  return Ok(boolean);
-}
\ No newline at end of file
+/**
+ * Sets a named property on the object.
+*/
+pub fn set_node_property<V: ToNapiValue>(object: &mut JsObject, wrapper: &'static str, name: &str, value: V) -> Result<(), SerenityError> {
+  object.set_named_property(name, value).map_err(|e| napi_failure(wrapper, e))
+}
+
+/**
+ * Creates a JsObject from a raw napi_value, used by the FromNapiValue implementations.
+ *
+ * # Safety
+ * `env` and `value` must be the raw handles napi passed to `from_napi_value`.
+*/
+pub unsafe fn object_from_raw(env: napi::sys::napi_env, value: napi::sys::napi_value, wrapper: &'static str) -> Result<JsObject, SerenityError> {
+  // Create the JsUnknown from the napi_value
+  let unknown = JsUnknown::from_raw(env, value).map_err(|e| napi_failure(wrapper, e))?;
+
+  // Check that the value is an object
+  node_value_to_object(unknown, wrapper, "this")
+}
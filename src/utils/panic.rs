@@ -0,0 +1,66 @@
+use std::any::Any;
+use std::backtrace::Backtrace;
+use std::cell::{Cell, RefCell};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Once;
+
+use crate::error::error::SerenityError;
+
+static HOOK: Once = Once::new();
+
+thread_local! {
+  // How many `catch_panic` calls are running on the thread
+  static CATCHING: Cell<u32> = const { Cell::new(0) };
+  static BACKTRACE: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/**
+ * Runs the callback, catching any panic so it never unwinds into SerenityJS.
+ * A caught panic is returned as `SerenityError::Panic`, with the backtrace of where it was raised.
+*/
+pub fn catch_panic<T, F>(wrapper: &'static str, callback: F) -> Result<T, SerenityError>
+where
+  F: FnOnce() -> Result<T, SerenityError>
+{
+  install_hook();
+
+  CATCHING.with(|catching| catching.set(catching.get() + 1));
+  let result = panic::catch_unwind(AssertUnwindSafe(callback));
+  CATCHING.with(|catching| catching.set(catching.get() - 1));
+
+  result.unwrap_or_else(|payload| {
+    let backtrace = BACKTRACE.with(|backtrace| backtrace.borrow_mut().take()).unwrap_or_default();
+
+    Err(SerenityError::Panic { wrapper, message: panic_message(payload.as_ref()), backtrace })
+  })
+}
+
+/**
+ * Installs a panic hook that records the backtrace of panics raised inside `catch_panic`.
+ * Panics raised anywhere else are still handed to the previous hook.
+*/
+fn install_hook() {
+  HOOK.call_once(|| {
+    let previous = panic::take_hook();
+
+    panic::set_hook(Box::new(move |info| {
+      if CATCHING.with(|catching| catching.get()) == 0 {
+        return previous(info);
+      }
+
+      // The panic is reported through the plugin logger instead of stderr
+      let backtrace = Backtrace::force_capture().to_string();
+      BACKTRACE.with(|slot| *slot.borrow_mut() = Some(backtrace));
+    }));
+  });
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+  if let Some(message) = payload.downcast_ref::<&str>() {
+    message.to_string()
+  } else if let Some(message) = payload.downcast_ref::<String>() {
+    message.clone()
+  } else {
+    "Box<dyn Any>".to_string()
+  }
+}
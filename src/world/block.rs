@@ -1,55 +1,54 @@
-use napi::{bindgen_prelude::FromNapiValue, NapiValue};
+use napi::ValueType;
 
+use crate::block::block_permutation::BlockPermutation;
+use crate::error::error::SerenityError;
+use crate::utils::js_wrapper::JsWrapper;
 use crate::utils::node_converter::*;
+use crate::{js_method, JsWrapper};
 use super::world::dimension::Dimension;
 use crate::block::block_type::BlockType;
 
+#[derive(JsWrapper)]
 pub struct Block {
   pub object: napi::JsObject,
   pub env: napi::Env,
+  #[js(nested)]
   pub dimension: Dimension,
 }
 
 impl Block {
-  pub fn new(env: napi::Env, object: napi::JsObject) -> Self {
-    // Get the dimension
-    let dimension_object = get_node_object(&object, "dimension").unwrap();
-    let dimension = Dimension::new(env.clone(), dimension_object);
+  /**
+   * Get the block type of the block.
+  */
+  #[js_method("getType")]
+  pub fn get_type(&self) -> Result<BlockType, SerenityError>;
 
-    // Return the Block instance
-    Block { env, object, dimension }
+  /**
+   * Gets the permutation of the block, its type and block states.
+  */
+  pub fn get_permutation(&self) -> Result<BlockPermutation, SerenityError> {
+    let permutation = get_node_object(&self.object, Self::WRAPPER, "permutation")?;
+
+    BlockPermutation::from_js_object(self.env, &permutation)
   }
 
   /**
-   * Gte he block type of the block.
+   * Sets the permutation of the block, changing its type and block states.
   */
-  pub fn get_type(&self) -> BlockType {
-    // Get the getType function
-    let get_type = get_node_func(&self.object, "getType").unwrap();
+  pub fn set_permutation(&self, permutation: &BlockPermutation) -> Result<(), SerenityError> {
+    // Get the BlockPermutation class through the current permutation
+    let current = get_node_object(&self.object, Self::WRAPPER, "permutation")?;
+    let constructor = get_node_property(&current, Self::WRAPPER, "constructor")?;
+    let class = expect_node_type::<napi::JsObject>(constructor, Self::WRAPPER, "constructor", ValueType::Function)?;
 
-    // Call the getType function
-    let get_type_result = get_type.call::<napi::JsUnknown>(Some(&self.object), &[]).unwrap();
+    // Resolve the permutation from its identifier and states
+    let identifier = convert_to_js_string(&self.env, Self::WRAPPER, &permutation.identifier)?;
+    let states = permutation.states_to_js_object(self.env)?;
+    let resolved = call_node_method(&class, Self::WRAPPER, "resolve", &[identifier.into_unknown(), states.into_unknown()])?;
 
-    // Coerce the result to an object
-    let get_type_object = get_type_result.coerce_to_object().unwrap();
+    // Set the resolved permutation
+    call_node_method(&self.object, Self::WRAPPER, "setPermutation", &[resolved])?;
 
-    // Create the BlockType instance
-    BlockType::new(self.env.clone(), get_type_object)
+    Ok(())
   }
 }
-
-impl FromNapiValue for Block {
-  unsafe fn from_napi_value(env: napi::sys::napi_env, value: napi::sys::napi_value) -> napi::Result<Self> {
-    // Create the JsObject from the napi_value
-    let object = match napi::JsObject::from_raw(env.clone(), value.clone()) {
-      Ok(obj) => obj,
-      Err(e) => return Err(napi::Error::new(
-        napi::Status::GenericFailure,
-        e.to_string()
-      ))
-    };
-
-    // Return the Block instance
-    Ok(Block::new(env.into(), object))
-  }
-}
\ No newline at end of file
@@ -1,3 +1,4 @@
+use crate::error::error::SerenityError;
 use crate::world::player::player::Player;
 
 use super::health::PlayerHealthComponent;
@@ -5,7 +6,7 @@ use super::health::PlayerHealthComponent;
 pub struct PlayerComponent {}
 
 impl PlayerComponent {
-  pub fn get_health(player: &Player) -> PlayerHealthComponent {
-    return PlayerHealthComponent::new(player.env, player.get_component("minecraft:health"));
+  pub fn get_health(player: &Player) -> Result<PlayerHealthComponent, SerenityError> {
+    PlayerHealthComponent::new(player.env, player.get_component("minecraft:health")?)
   }
-}
\ No newline at end of file
+}
@@ -0,0 +1,31 @@
+use crate::error::error::SerenityError;
+use crate::utils::node_converter::*;
+
+use super::signal::Signal;
+
+/**
+ * A signal SerenityJS allows cancelling, so the action it announces never happens.
+ * Cancelling only has an effect from a listener registered through `Worlds::before`,
+ * as the action has already been processed by the time `on` listeners run.
+*/
+pub trait Cancellable: Signal {
+  /**
+   * Cancels the signal, the flag is written to the JS object so other listeners see it.
+  */
+  fn cancel(&mut self) -> Result<(), SerenityError> {
+    let cancelled = convert_to_js_boolean(&self.js_env(), Self::WRAPPER, true)?;
+
+    set_node_property(self.js_object_mut(), Self::WRAPPER, "cancelled", cancelled)
+  }
+
+  /**
+   * Checks if the signal has been cancelled, by this or any other listener.
+  */
+  fn is_cancelled(&self) -> Result<bool, SerenityError> {
+    match get_node_boolean(self.js_object(), Self::WRAPPER, "cancelled") {
+      Ok(cancelled) => Ok(cancelled),
+      Err(SerenityError::MissingProperty { .. }) => Ok(false),
+      Err(e) => Err(e)
+    }
+  }
+}
@@ -1,6 +1,10 @@
+pub mod world_event;
+pub mod signal;
+pub mod cancellable;
+
 pub mod world_tick;
 
 pub mod player_join;
 pub mod player_leave;
 pub mod player_chat;
-pub mod player_execute_command;
\ No newline at end of file
+pub mod player_execute_command;
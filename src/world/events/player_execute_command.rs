@@ -1,66 +1,44 @@
-use napi::bindgen_prelude::FromNapiValue;
-use napi::NapiValue;
-
-use crate::utils::node_converter::*;
 use crate::world::player::player::Player;
 use crate::world::world::world::World;
+use crate::error::error::SerenityError;
+use crate::utils::js_wrapper::JsWrapper;
+use crate::utils::node_converter::*;
+use crate::JsWrapper;
 
+use super::cancellable::Cancellable;
+use super::signal::Signal;
+use super::world_event::WorldEvent;
+
+#[derive(JsWrapper)]
 pub struct PlayerExecuteCommandSignal {
   pub object: napi::JsObject,
   pub env: napi::Env,
+  #[js(nested)]
   pub world: World,
+  #[js(nested)]
   pub player: Player,
   pub command: String
 }
 
-impl PlayerExecuteCommandSignal {
-  pub fn new(env: napi::Env, object: napi::JsObject) -> napi::Result<Self> {
-    // Create the world instance
-    let world_object = get_node_object(&object, "world");
-    let world = match world_object {
-      Ok(world) => World::new(env.clone(), world),
-      Err(e) => return Err(napi::Error::new(
-        napi::Status::GenericFailure,
-        e.to_string()
-      ))
-    };
-
-    // Create the player instance
-    let player_object = get_node_object(&object, "player");
-    let player = match player_object {
-      Ok(player) => Player::new(env.clone(), player),
-      Err(e) => return Err(napi::Error::new(
-        napi::Status::GenericFailure,
-        e.to_string()
-      ))
-    };
-
-    // Get the command object
-    let command_object = get_node_string(&object, "command");
-    let command = match command_object {
-      Ok(command) => command.into_utf8().unwrap().into_owned().unwrap(),
-      Err(e) => return Err(napi::Error::new(
-        napi::Status::GenericFailure,
-        e.to_string()
-      ))
-    };
-
-    Ok(PlayerExecuteCommandSignal { env, object, world, player, command })
-  }
+impl Signal for PlayerExecuteCommandSignal {
+  const EVENT: WorldEvent = WorldEvent::PlayerExecuteCommand;
 }
 
-impl FromNapiValue for PlayerExecuteCommandSignal {
-  unsafe fn from_napi_value(env: napi::sys::napi_env, value: napi::sys::napi_value) -> napi::Result<Self> {
-    // Create the JsObject from the napi_value
-    let object = match napi::JsObject::from_raw(env.clone(), value.clone()) {
-      Ok(obj) => obj,
-      Err(e) => return Err(napi::Error::new(
-        napi::Status::GenericFailure,
-        e.to_string()
-      ))
-    };
+impl Cancellable for PlayerExecuteCommandSignal {}
 
-    // Return the PlayerExecuteCommandSignal instance
-    Ok(PlayerExecuteCommandSignal::new(env.into(), object)?)
+impl PlayerExecuteCommandSignal {
+  /**
+   * Replaces the command text executed by the player.
+   * The command is parsed after the `before` listeners ran, so the replacement may name a different command.
+  */
+  pub fn set_command(&mut self, command: &str) -> Result<(), SerenityError> {
+    // Convert the command to a JsString
+    let value = convert_to_js_string(&self.env, Self::WRAPPER, command)?;
+
+    // Write the command to the signal object
+    set_node_property(&mut self.object, Self::WRAPPER, "command", value)?;
+    self.command = command.to_string();
+
+    Ok(())
   }
-}
\ No newline at end of file
+}
@@ -0,0 +1,11 @@
+use crate::utils::js_wrapper::JsWrapper;
+
+use super::world_event::WorldEvent;
+
+/**
+ * A signal SerenityJS passes to the listeners of a world event.
+*/
+pub trait Signal: JsWrapper + 'static {
+  /** The world event the signal is emitted for. */
+  const EVENT: WorldEvent;
+}
@@ -0,0 +1,61 @@
+/**
+ * The world events emitted by SerenityJS, mirroring its `WorldEvent` enum.
+ * The discriminant is the id SerenityJS uses for the event.
+*/
+#[repr(i32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WorldEvent {
+  WorldInitialize = 0,
+  WorldTick = 1,
+  WorldMessage = 2,
+  PlayerJoin = 3,
+  PlayerInitialized = 4,
+  PlayerSpawned = 5,
+  PlayerLeave = 6,
+  PlayerChat = 7,
+  PlayerExecuteCommand = 8,
+  PlayerPlaceBlock = 9,
+  PlayerBreakBlock = 10,
+  PlayerInteractWithBlock = 11,
+  PlayerGamemodeChange = 12,
+  EntitySpawned = 13,
+  EntityDespawned = 14,
+  EntityHurt = 15,
+}
+
+impl WorldEvent {
+  /** Every world event, in id order. */
+  pub const ALL: [WorldEvent; 16] = [
+    WorldEvent::WorldInitialize,
+    WorldEvent::WorldTick,
+    WorldEvent::WorldMessage,
+    WorldEvent::PlayerJoin,
+    WorldEvent::PlayerInitialized,
+    WorldEvent::PlayerSpawned,
+    WorldEvent::PlayerLeave,
+    WorldEvent::PlayerChat,
+    WorldEvent::PlayerExecuteCommand,
+    WorldEvent::PlayerPlaceBlock,
+    WorldEvent::PlayerBreakBlock,
+    WorldEvent::PlayerInteractWithBlock,
+    WorldEvent::PlayerGamemodeChange,
+    WorldEvent::EntitySpawned,
+    WorldEvent::EntityDespawned,
+    WorldEvent::EntityHurt,
+  ];
+
+  /**
+   * Gets the id SerenityJS uses for the event.
+  */
+  pub fn id(self) -> i32 {
+    self as i32
+  }
+}
+
+impl TryFrom<i32> for WorldEvent {
+  type Error = i32;
+
+  fn try_from(id: i32) -> Result<Self, Self::Error> {
+    WorldEvent::ALL.into_iter().find(|event| event.id() == id).ok_or(id)
+  }
+}
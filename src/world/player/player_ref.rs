@@ -0,0 +1,54 @@
+use crate::error::error::SerenityError;
+use crate::serenity::serenity::Serenity;
+use crate::world::world::world::World;
+
+use super::player::Player;
+
+/**
+ * A `Send`-able handle to a player, identified by their uuid.
+ * Can be moved to other threads, and resolved back into a `Player` on the JS thread.
+*/
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PlayerRef {
+  uuid: String,
+}
+
+impl PlayerRef {
+  pub fn new(uuid: impl Into<String>) -> Self {
+    PlayerRef { uuid: uuid.into() }
+  }
+
+  /**
+   * Gets the uuid of the player.
+  */
+  pub fn uuid(&self) -> &str {
+    &self.uuid
+  }
+
+  /**
+   * Resolves the handle into the player, or `None` if the player is no longer online.
+   * Must be called on the JS thread, e.g. from `MainThreadDispatcher::run`.
+  */
+  pub fn resolve(&self, serenity: &Serenity) -> Result<Option<Player>, SerenityError> {
+    // Find the online player with the uuid
+    let players = serenity.worlds.get_players()?;
+
+    Ok(players.into_iter().find(|player| player.uuid == self.uuid))
+  }
+
+  /**
+   * Resolves the handle into the player, or `None` if the player is not in the world.
+  */
+  pub fn resolve_in(&self, world: &World) -> Result<Option<Player>, SerenityError> {
+    // Find the player with the uuid in the world
+    let players = world.get_players()?;
+
+    Ok(players.into_iter().find(|player| player.uuid == self.uuid))
+  }
+}
+
+impl From<&Player> for PlayerRef {
+  fn from(player: &Player) -> Self {
+    PlayerRef::new(player.uuid.clone())
+  }
+}
@@ -0,0 +1,107 @@
+use napi::{JsObject, JsUnknown, ValueType};
+
+use crate::block::block_permutation::BlockPermutation;
+use crate::block::block_volume::BlockVolume;
+use crate::error::error::SerenityError;
+use crate::protocol::types::block_position::BlockPosition;
+use crate::protocol::types::cuboid::Cuboid;
+use crate::utils::node_converter::*;
+
+const WRAPPER: &str = "Dimension";
+
+/**
+ * Reads the blocks of the region in `Cuboid::iter` order.
+ * Every distinct permutation is converted once, blocks sharing a permutation only store its palette index.
+*/
+pub(crate) fn read_blocks(env: napi::Env, dimension: &JsObject, region: Cuboid) -> Result<BlockVolume, SerenityError> {
+  let overflow = || SerenityError::ConversionOverflow { wrapper: WRAPPER, property: "region".to_string(), target: "an in-memory block volume" };
+
+  // Reserve the indices up front, so a huge region is an error rather than an abort
+  let volume = usize::try_from(region.volume()).map_err(|_| overflow())?;
+  let mut indices = Vec::new();
+  indices.try_reserve_exact(volume).map_err(|_| overflow())?;
+
+  // Map each permutation object to its palette index, permutations are shared between blocks in JS
+  let lookup = env.get_global()
+    .and_then(|global| global.get_named_property::<napi::JsFunction>("Map"))
+    .and_then(|map| map.new_instance::<JsUnknown>(&[]))
+    .map_err(|e| SerenityError::NapiFailure { wrapper: WRAPPER, message: e.to_string() })?;
+
+  let mut palette = Vec::new();
+  for position in region.iter() {
+    let index = in_scope(env, || {
+      let permutation = get_node_object(&get_block(env, dimension, position)?, WRAPPER, "permutation")?;
+
+      // Reuse the index of a permutation seen before
+      let index = call_node_method(&lookup, WRAPPER, "get", &[&permutation])?;
+      if node_type(&index)? != ValueType::Undefined {
+        return node_value_to_i32(index, WRAPPER, "getBlocks").map(|index| index as u32);
+      }
+
+      // Otherwise convert it and add it to the palette
+      let index = palette.len() as u32;
+      palette.push(BlockPermutation::from_js_object(env, &permutation)?);
+      let id = create_node_number(&env, WRAPPER, index as i32)?;
+      call_node_method(&lookup, WRAPPER, "set", &[permutation.into_unknown(), id.into_unknown()])?;
+
+      Ok(index)
+    })?;
+
+    indices.push(index);
+  }
+
+  BlockVolume::from_palette(region, palette, indices)
+}
+
+/**
+ * Writes the blocks of the volume in `Cuboid::iter` order.
+ * Every permutation of the palette is resolved once, before any block is set.
+*/
+pub(crate) fn write_blocks(env: napi::Env, dimension: &JsObject, volume: &BlockVolume) -> Result<(), SerenityError> {
+  // Get the BlockPermutation class through the permutation of a block of the region
+  let current = get_node_object(&get_block(env, dimension, volume.region().min)?, WRAPPER, "permutation")?;
+  let constructor = get_node_property(&current, WRAPPER, "constructor")?;
+  let class = expect_node_type::<JsObject>(constructor, WRAPPER, "constructor", ValueType::Function)?;
+
+  // Resolve the palette from the identifiers and states
+  let resolved = volume.palette().iter().map(|permutation| {
+    let identifier = convert_to_js_string(&env, WRAPPER, &permutation.identifier)?;
+    let states = permutation.states_to_js_object(env)?;
+    let resolved = call_node_method(&class, WRAPPER, "resolve", &[identifier.into_unknown(), states.into_unknown()])?;
+
+    node_value_to_object(resolved, WRAPPER, "resolve")
+  }).collect::<Result<Vec<_>, _>>()?;
+
+  for (position, index) in volume.region().iter().zip(volume.indices()) {
+    in_scope(env, || {
+      let block = get_block(env, dimension, position)?;
+      call_node_method(&block, WRAPPER, "setPermutation", &[&resolved[*index as usize]])?;
+
+      Ok(())
+    })?;
+  }
+
+  Ok(())
+}
+
+fn get_block(env: napi::Env, dimension: &JsObject, position: BlockPosition) -> Result<JsObject, SerenityError> {
+  let block = call_node_method(dimension, WRAPPER, "getBlock", &[position.to_js_object(env)?])?;
+
+  node_value_to_object(block, WRAPPER, "getBlock")
+}
+
+fn node_type(value: &JsUnknown) -> Result<ValueType, SerenityError> {
+  value.get_type().map_err(|e| SerenityError::NapiFailure { wrapper: WRAPPER, message: e.to_string() })
+}
+
+/**
+ * Runs the calls for a single block in their own handle scope,
+ * so the values they create are released before the next block instead of piling up until the region is done.
+*/
+fn in_scope<T, F>(env: napi::Env, run: F) -> Result<T, SerenityError>
+where
+  F: FnOnce() -> Result<T, SerenityError>
+{
+  env.run_in_scope(|| Ok(run()))
+    .map_err(|e| SerenityError::NapiFailure { wrapper: WRAPPER, message: e.to_string() })?
+}
@@ -1,9 +1,17 @@
-use napi::{bindgen_prelude::FromNapiValue, NapiValue};
-
-use crate::utils::node_converter::*;
+use crate::block::block_permutation::BlockPermutation;
+use crate::block::block_type::BlockType;
+use crate::block::block_volume::BlockVolume;
+use crate::error::error::SerenityError;
+use crate::protocol::types::block_face::BlockFace;
 use crate::protocol::types::block_position::BlockPosition;
+use crate::protocol::types::chunk_position::{ChunkPosition, ChunksInRadius};
+use crate::protocol::types::cuboid::Cuboid;
+use crate::protocol::types::vector3f::Vector3f;
+use crate::protocol::types::voxel_ray::VoxelRay;
 use crate::world::block::Block;
+use crate::{js_method, JsWrapper};
 
+#[derive(JsWrapper)]
 pub struct Dimension {
   pub object: napi::JsObject,
   pub env: napi::Env,
@@ -13,57 +21,98 @@ pub struct Dimension {
 }
 
 impl Dimension {
-  pub fn new(env: napi::Env, object: napi::JsObject) -> Self {
-    // Get the identifier
-    let identifier_object = get_node_string(&object, "identifier").unwrap();
-    let identifier = identifier_object.into_utf8().unwrap().into_owned().unwrap();
-
+  /**
+   * Get the block at the given position.
+  */
+  #[js_method("getBlock")]
+  pub fn get_block(&self, position: BlockPosition) -> Result<Block, SerenityError>;
 
-    // Get the view distance
-    let view_distance_object = get_node_number(&object, "viewDistance").unwrap();
-    let view_distance = view_distance_object.get_int32().unwrap();
+  /**
+   * Sets the block at the given position to the permutation.
+  */
+  pub fn set_block(&self, position: BlockPosition, permutation: &BlockPermutation) -> Result<(), SerenityError> {
+    self.get_block(position)?.set_permutation(permutation)
+  }
 
-    // Get the simulation distance
-    let simulation_distance_object = get_node_number(&object, "simulationDistance").unwrap();
-    let simulation_distance = simulation_distance_object.get_int32().unwrap();
+  /**
+   * Gets every block of the region, converting each distinct permutation only once.
+   * Far faster than calling `get_block` and `get_permutation` for each block of a large region.
+  */
+  pub fn get_blocks(&self, region: Cuboid) -> Result<BlockVolume, SerenityError> {
+    super::bulk::read_blocks(self.env, &self.object, region)
+  }
 
-    Dimension { env, object, identifier, view_distance, simulation_distance }
+  /**
+   * Sets every block of the volume's region, resolving each permutation of its palette only once.
+  */
+  pub fn set_blocks(&self, volume: &BlockVolume) -> Result<(), SerenityError> {
+    super::bulk::write_blocks(self.env, &self.object, volume)
   }
 
   /**
-   * Get the block at the given position.
+   * Iterates over the chunks a player in the center chunk can see.
   */
-  pub fn get_block(&self, position: BlockPosition) -> Block {
-    // Get the getBlock function
-    let get_block = get_node_func(&self.object, "getBlock").unwrap();
+  pub fn chunks_in_view(&self, center: ChunkPosition) -> ChunksInRadius {
+    center.within_radius(self.view_distance)
+  }
 
-    // Create the position object
-    let position_object = position.to_js_object(self.env.clone());
+  /**
+   * Iterates over the chunks that are simulated around the center chunk.
+  */
+  pub fn chunks_in_simulation(&self, center: ChunkPosition) -> ChunksInRadius {
+    center.within_radius(self.simulation_distance)
+  }
 
+  /**
+   * Casts a ray from the origin along the direction, returning the first block the predicate deems solid.
+   * Blocks further than the max distance are not checked, e.g. to find the first solid block north of a point:
+   *
+   * ```ignore
+   * let origin = Vector3f { x: 0.5, y: 64.5, z: 0.5 };
+   * let hit = dimension.raycast(origin, Vector3f::NORTH, 6.0, |block_type| !block_type.air && !block_type.liquid)?;
+   * ```
+  */
+  pub fn raycast<F>(&self, origin: Vector3f, direction: Vector3f, max_distance: f64, mut is_solid: F) -> Result<Option<RaycastHit>, SerenityError>
+  where
+    F: FnMut(&BlockType) -> bool
+  {
+    for step in VoxelRay::new(origin, direction, max_distance) {
+      // Get the block and its type
+      let block = self.get_block(step.position)?;
+      let block_type = block.get_type()?;
 
-    // Call the getBlock function
-    let get_block_result = get_block.call::<napi::JsUnknown>(Some(&self.object), &[position_object.into_unknown()]).unwrap();
+      if is_solid(&block_type) {
+        return Ok(Some(RaycastHit { block, position: step.position, face: step.face, point: step.point, distance: step.distance }));
+      }
+    }
 
-    // Coerce the result to an object
-    let get_block_object = get_block_result.coerce_to_object().unwrap();
+    Ok(None)
+  }
 
-    // Create the Block instance
-    Block::new(self.env.clone(), get_block_object)
+  /**
+   * Checks if nothing the predicate deems solid lies between the two points.
+  */
+  pub fn has_line_of_sight<F>(&self, from: Vector3f, to: Vector3f, is_solid: F) -> Result<bool, SerenityError>
+  where
+    F: FnMut(&BlockType) -> bool
+  {
+    Ok(self.raycast(from, to - from, from.distance(to), is_solid)?.is_none())
   }
 }
 
-impl FromNapiValue for Dimension {
-  unsafe fn from_napi_value(env: napi::sys::napi_env, value: napi::sys::napi_value) -> napi::Result<Self> {
-    // Create the JsObject from the napi_value
-    let object = match napi::JsObject::from_raw(env.clone(), value.clone()) {
-      Ok(obj) => obj,
-      Err(e) => return Err(napi::Error::new(
-        napi::Status::GenericFailure,
-        e.to_string()
-      ))
-    };
+/**
+ * The block hit by `Dimension::raycast`.
+*/
+pub struct RaycastHit {
+  pub block: Block,
+  pub position: BlockPosition,
 
-    // Return the Dimension instance
-    Ok(Dimension::new(env.into(), object))
-  }
-}
\ No newline at end of file
+  /** The face the ray hit, `None` when the ray started inside the block. */
+  pub face: Option<BlockFace>,
+
+  /** The exact point the ray hit the block at. */
+  pub point: Vector3f,
+
+  /** The distance from the origin to the point. */
+  pub distance: f64,
+}
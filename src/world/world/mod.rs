@@ -1,2 +1,4 @@
 pub mod world;
-pub mod dimension;
\ No newline at end of file
+pub mod dimension;
+pub mod world_ref;
+mod bulk;
\ No newline at end of file
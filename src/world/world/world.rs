@@ -1,56 +1,28 @@
-use napi::{bindgen_prelude::FromNapiValue, NapiValue};
-
+use crate::error::error::SerenityError;
 use crate::logger::logger::Logger;
-use crate::utils::node_converter::*;
+use crate::world::player::player::Player;
+use crate::{js_method, JsWrapper};
 
+#[derive(JsWrapper)]
 pub struct World {
   pub object: napi::JsObject,
   pub env: napi::Env,
   pub identifier: String,
   pub current_tick: u64,
+  #[js(nested)]
   pub logger: Logger,
 }
 
 impl World {
-  pub fn new(env: napi::Env, object: napi::JsObject) -> Self {
-    // Get the logger object
-    let logger_object = get_node_object(&object, "logger").unwrap();
-
-    // Get the identifier object
-    let identifier_object = get_node_string(&object, "identifier").unwrap();
-    let identifier = identifier_object.into_utf8().unwrap().into_owned().unwrap();
-
-    // Get the current_tick object
-    let current_tick_object = get_node_bigint(&object, "currentTick").unwrap();
-    let current_tick = current_tick_object.get_u64().unwrap().0;
-
-    World { env, object, identifier, current_tick, logger: Logger::new(env.clone(), logger_object) }
-  }
-
-  pub fn send_message(&self, message: &str) {
-    // Get the send_message function
-    let send_message = get_node_func(&self.object, "sendMessage").unwrap();
-
-    // Convert the message to a JsString
-    let message = convert_to_js_string(&self.env, message).unwrap();
-
-    // Call the send_message function
-    send_message.call::<napi::JsString>(Some(&self.object), &[napi::JsString::from(message)]).unwrap();
-  }
+  /**
+   * Sends a message to every player in the world.
+  */
+  #[js_method("sendMessage")]
+  pub fn send_message(&self, message: &str) -> Result<(), SerenityError>;
+
+  /**
+   * Gets every player in the world.
+  */
+  #[js_method("getPlayers")]
+  pub fn get_players(&self) -> Result<Vec<Player>, SerenityError>;
 }
-
-impl FromNapiValue for World {
-  unsafe fn from_napi_value(env: napi::sys::napi_env, value: napi::sys::napi_value) -> napi::Result<Self> {
-    // Create the JsObject from the napi_value
-    let object = match napi::JsObject::from_raw(env.clone(), value.clone()) {
-      Ok(obj) => obj,
-      Err(e) => return Err(napi::Error::new(
-        napi::Status::GenericFailure,
-        e.to_string()
-      ))
-    };
-
-    // Return the World instance
-    Ok(World::new(env.into(), object))
-  }
-}
\ No newline at end of file
@@ -0,0 +1,40 @@
+use crate::error::error::SerenityError;
+use crate::serenity::serenity::Serenity;
+
+use super::world::World;
+
+/**
+ * A `Send`-able handle to a world, identified by its identifier.
+ * Can be moved to other threads, and resolved back into a `World` on the JS thread.
+*/
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct WorldRef {
+  identifier: String,
+}
+
+impl WorldRef {
+  pub fn new(identifier: impl Into<String>) -> Self {
+    WorldRef { identifier: identifier.into() }
+  }
+
+  /**
+   * Gets the identifier of the world.
+  */
+  pub fn identifier(&self) -> &str {
+    &self.identifier
+  }
+
+  /**
+   * Resolves the handle into the world, or `None` if the world no longer exists.
+   * Must be called on the JS thread, e.g. from `MainThreadDispatcher::run`.
+  */
+  pub fn resolve(&self, serenity: &Serenity) -> Result<Option<World>, SerenityError> {
+    serenity.worlds.get_world(&self.identifier)
+  }
+}
+
+impl From<&World> for WorldRef {
+  fn from(world: &World) -> Self {
+    WorldRef::new(world.identifier.clone())
+  }
+}
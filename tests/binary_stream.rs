@@ -0,0 +1,191 @@
+use serenityrs::protocol::binary::error::BinaryError;
+use serenityrs::protocol::binary::stream::{BinaryStream, Endianness};
+use serenityrs::protocol::nbt::codec::NbtEncoding;
+use serenityrs::protocol::nbt::tag::{Compound, NamedTag};
+use serenityrs::protocol::types::block_position::BlockPosition;
+use serenityrs::protocol::types::vector3f::Vector3f;
+use uuid::Uuid;
+
+#[test]
+fn writes_fixed_width_numbers_in_both_endiannesses() {
+  let mut stream = BinaryStream::new();
+  stream.write_u8(0xab);
+  stream.write_u16(0x0102, Endianness::Big);
+  stream.write_u16(0x0102, Endianness::Little);
+  stream.write_i32(-2, Endianness::Big);
+  stream.write_u64(1, Endianness::Little);
+  stream.write_f32(1.5, Endianness::Little);
+
+  assert_eq!(stream.as_bytes(), &[
+    0xab,
+    0x01, 0x02,
+    0x02, 0x01,
+    0xff, 0xff, 0xff, 0xfe,
+    0x01, 0, 0, 0, 0, 0, 0, 0,
+    0x00, 0x00, 0xc0, 0x3f,
+  ]);
+
+  assert_eq!(stream.read_u8().unwrap(), 0xab);
+  assert_eq!(stream.read_u16(Endianness::Big).unwrap(), 0x0102);
+  assert_eq!(stream.read_u16(Endianness::Little).unwrap(), 0x0102);
+  assert_eq!(stream.read_i32(Endianness::Big).unwrap(), -2);
+  assert_eq!(stream.read_u64(Endianness::Little).unwrap(), 1);
+  assert_eq!(stream.read_f32(Endianness::Little).unwrap(), 1.5);
+  assert!(stream.is_eof());
+}
+
+#[test]
+fn encodes_varints() {
+  let cases: [(i32, &[u8]); 5] = [
+    (0, &[0x00]),
+    (-1, &[0x01]),
+    (1, &[0x02]),
+    (300, &[0xd8, 0x04]),
+    (i32::MIN, &[0xff, 0xff, 0xff, 0xff, 0x0f]),
+  ];
+
+  for (value, bytes) in cases {
+    let mut stream = BinaryStream::new();
+    stream.write_var_int(value);
+    assert_eq!(stream.as_bytes(), bytes, "{}", value);
+    assert_eq!(stream.read_var_int().unwrap(), value);
+  }
+
+  let mut stream = BinaryStream::new();
+  stream.write_var_u32(u32::MAX);
+  stream.write_var_u64(u64::MAX);
+  stream.write_var_long(i64::MIN);
+  assert_eq!(stream.read_var_u32().unwrap(), u32::MAX);
+  assert_eq!(stream.read_var_u64().unwrap(), u64::MAX);
+  assert_eq!(stream.read_var_long().unwrap(), i64::MIN);
+}
+
+#[test]
+fn encodes_strings_and_uuids() {
+  let uuid = Uuid::from_u128(0x0011_2233_4455_6677_8899_aabb_ccdd_eeff);
+
+  let mut stream = BinaryStream::new();
+  stream.write_var_string("héllo").unwrap();
+  stream.write_string16("ab", Endianness::Little).unwrap();
+  stream.write_uuid(&uuid);
+
+  assert_eq!(&stream.as_bytes()[..7], &[0x06, b'h', 0xc3, 0xa9, b'l', b'l', b'o']);
+  assert_eq!(&stream.as_bytes()[11..], &[
+    0x77, 0x66, 0x55, 0x44, 0x33, 0x22, 0x11, 0x00,
+    0xff, 0xee, 0xdd, 0xcc, 0xbb, 0xaa, 0x99, 0x88,
+  ]);
+
+  assert_eq!(stream.read_var_string().unwrap(), "héllo");
+  assert_eq!(stream.read_string16(Endianness::Little).unwrap(), "ab");
+  assert_eq!(stream.read_uuid().unwrap(), uuid);
+}
+
+#[test]
+fn encodes_positions() {
+  let position = BlockPosition { x: -1, y: 70, z: 2 };
+
+  let mut stream = BinaryStream::new();
+  stream.write_block_position(&position);
+  stream.write_network_block_position(&position);
+  stream.write_vector3f(&Vector3f { x: 0.5, y: -64.0, z: 1.25 });
+
+  assert_eq!(&stream.as_bytes()[..6], &[0x01, 0x8c, 0x01, 0x04, 0x01, 0x46]);
+  assert_eq!(stream.read_block_position().unwrap(), position);
+  assert_eq!(stream.read_network_block_position().unwrap(), position);
+  assert_eq!(stream.read_vector3f().unwrap(), Vector3f { x: 0.5, y: -64.0, z: 1.25 });
+}
+
+#[test]
+fn reads_nbt_in_the_stream() {
+  let named = NamedTag::new("", Compound::new().with("a", 1));
+
+  let mut stream = BinaryStream::new();
+  stream.write_nbt(&named, NbtEncoding::Network).unwrap();
+  stream.write_u8(7);
+
+  assert_eq!(stream.read_nbt(NbtEncoding::Network).unwrap(), named);
+  assert_eq!(stream.read_u8().unwrap(), 7);
+}
+
+#[test]
+fn truncated_reads_fail_without_advancing() {
+  let mut stream = BinaryStream::from_bytes(vec![0x01, 0x02, 0x03]);
+  stream.read_u8().unwrap();
+
+  assert_eq!(stream.read_u32(Endianness::Little), Err(BinaryError::UnexpectedEof { offset: 1, needed: 4, remaining: 2 }));
+  assert_eq!(stream.offset(), 1);
+  assert_eq!(stream.read_u16(Endianness::Big).unwrap(), 0x0203);
+
+  // A string longer than the data left
+  let mut stream = BinaryStream::from_bytes(vec![0x05, b'a']);
+  assert_eq!(stream.read_var_string(), Err(BinaryError::UnexpectedEof { offset: 1, needed: 5, remaining: 1 }));
+
+  let mut stream = BinaryStream::from_bytes(vec![0xff; 6]);
+  assert_eq!(stream.read_var_u32(), Err(BinaryError::VarIntTooLong { offset: 0 }));
+
+  assert_eq!(stream.offset(), 0);
+
+  let mut stream = BinaryStream::from_bytes(vec![0x02, 0xff, 0xfe]);
+  assert_eq!(stream.read_var_string(), Err(BinaryError::InvalidString { offset: 1 }));
+  assert_eq!(stream.offset(), 0);
+}
+
+#[test]
+fn failed_reads_of_several_values_leave_the_offset_alone() {
+  // A varint cut off after its continuation byte
+  let mut stream = BinaryStream::from_bytes(vec![0x80, 0x80]);
+  assert!(stream.read_var_int().is_err());
+  assert_eq!(stream.offset(), 0);
+
+  // A string whose length fits, but whose bytes do not
+  let mut stream = BinaryStream::from_bytes(vec![0x05, b'a']);
+  assert!(stream.read_var_string().is_err());
+  assert_eq!(stream.offset(), 0);
+  let mut stream = BinaryStream::from_bytes(vec![0x05, 0x00, b'a']);
+  assert!(stream.read_string16(Endianness::Little).is_err());
+  assert_eq!(stream.offset(), 0);
+
+  // Values made of several numbers, cut off after the first of them
+  let mut stream = BinaryStream::from_bytes(vec![0x01; 12]);
+  assert!(stream.read_uuid().is_err());
+  assert_eq!(stream.offset(), 0);
+  assert!(stream.read_vector3f().is_ok());
+
+  let mut stream = BinaryStream::from_bytes(vec![0x02, 0x04, 0x80]);
+  assert!(stream.read_block_position().is_err());
+  assert_eq!(stream.offset(), 0);
+  assert!(stream.read_network_block_position().is_err());
+  assert_eq!(stream.offset(), 0);
+
+  let mut stream = BinaryStream::from_bytes(vec![0x00; 8]);
+  assert!(stream.read_vector3f().is_err());
+  assert_eq!(stream.offset(), 0);
+}
+
+#[test]
+fn refuses_to_truncate_string_lengths() {
+  let mut stream = BinaryStream::new();
+  let result = stream.write_string16(&"a".repeat(u16::MAX as usize + 1), Endianness::Little);
+
+  assert_eq!(result, Err(BinaryError::StringTooLong { length: u16::MAX as usize + 1, max: u16::MAX as usize }));
+  assert!(stream.as_bytes().is_empty());
+
+  stream.write_string16(&"a".repeat(u16::MAX as usize), Endianness::Little).unwrap();
+  assert_eq!(stream.read_string16(Endianness::Little).unwrap().len(), u16::MAX as usize);
+}
+
+#[test]
+fn rejects_varints_with_bits_above_their_type() {
+  let mut stream = BinaryStream::from_bytes(vec![0xff, 0xff, 0xff, 0xff, 0x1f]);
+  assert_eq!(stream.read_var_u32(), Err(BinaryError::VarIntTooLong { offset: 0 }));
+  assert_eq!(stream.offset(), 0);
+
+  let mut bytes = vec![0xff; 9];
+  bytes.push(0x02);
+  let mut stream = BinaryStream::from_bytes(bytes);
+  assert_eq!(stream.read_var_u64(), Err(BinaryError::VarIntTooLong { offset: 0 }));
+  assert_eq!(stream.offset(), 0);
+
+  let mut stream = BinaryStream::from_bytes(vec![0xff, 0xff, 0xff, 0xff, 0x0f]);
+  assert_eq!(stream.read_var_u32().unwrap(), u32::MAX);
+}
@@ -0,0 +1,104 @@
+use serenityrs::backend::backend::DimensionBackend;
+use serenityrs::backend::mock::MockHost;
+use serenityrs::block::block_permutation::BlockPermutation;
+use serenityrs::block::block_volume::BlockVolume;
+use serenityrs::protocol::types::block_position::BlockPosition;
+use serenityrs::protocol::types::cuboid::Cuboid;
+
+fn region() -> Cuboid {
+  Cuboid::new(BlockPosition::new(-1, 10, 4), BlockPosition::new(1, 12, 5))
+}
+
+#[test]
+fn indexes_blocks_like_the_cuboid_iterates() {
+  let volume = BlockVolume::new(region(), BlockPermutation::new("minecraft:air")).unwrap();
+
+  assert_eq!(volume.len(), 18);
+  for (index, block) in region().iter().enumerate() {
+    assert_eq!(volume.index_of(block), Some(index));
+  }
+  assert_eq!(volume.index_of(BlockPosition::new(2, 10, 4)), None);
+}
+
+#[test]
+fn sets_and_gets_blocks() {
+  let stone = BlockPermutation::new("minecraft:stone");
+  let mut volume = BlockVolume::new(region(), BlockPermutation::new("minecraft:air")).unwrap();
+
+  assert!(volume.set(BlockPosition::new(0, 11, 5), &stone));
+  assert!(!volume.set(BlockPosition::new(0, 13, 5), &stone));
+  assert_eq!(volume[BlockPosition::new(0, 11, 5)], stone);
+  assert_eq!(volume.get(BlockPosition::new(0, 12, 5)).unwrap().identifier, "minecraft:air");
+  assert_eq!(volume.iter().filter(|(_, permutation)| **permutation == stone).count(), 1);
+
+  volume.fill(&stone);
+  assert!(volume.iter().all(|(_, permutation)| *permutation == stone));
+}
+
+#[test]
+fn compacts_the_palette() {
+  let stone = BlockPermutation::new("minecraft:stone");
+  let mut volume = BlockVolume::new(region(), BlockPermutation::new("minecraft:air")).unwrap();
+  volume.set(BlockPosition::new(-1, 10, 4), &BlockPermutation::new("minecraft:dirt"));
+  volume.fill(&stone);
+  assert_eq!(volume.palette().len(), 3);
+
+  volume.compact();
+  assert_eq!(volume.palette(), std::slice::from_ref(&stone));
+  assert!(volume.indices().iter().all(|index| *index == 0));
+
+  // Duplicate palette entries are merged
+  let duplicates = BlockVolume::from_palette(Cuboid::new(BlockPosition::new(0, 0, 0), BlockPosition::new(0, 1, 0)), vec![stone.clone(), stone.clone()], vec![1, 0]).unwrap();
+  assert_eq!(duplicates.palette(), &[stone]);
+}
+
+#[test]
+fn rejects_regions_too_large_to_hold() {
+  let world = Cuboid::new(BlockPosition::new(-30_000_000, -64, -30_000_000), BlockPosition::new(30_000_000, 320, 30_000_000));
+
+  assert!(BlockVolume::new(world, BlockPermutation::new("minecraft:air")).is_err());
+}
+
+#[test]
+fn compares_volumes_by_their_blocks() {
+  let air = BlockPermutation::new("minecraft:air");
+  let stone = BlockPermutation::new("minecraft:stone");
+  let cuboid = Cuboid::new(BlockPosition::new(0, 0, 0), BlockPosition::new(0, 1, 0));
+
+  // The order of the palette and unused entries do not matter
+  let volume = BlockVolume::from_palette(cuboid, vec![air.clone(), stone.clone()], vec![0, 1]).unwrap();
+  let reordered = BlockVolume::from_palette(cuboid, vec![stone.clone(), BlockPermutation::new("minecraft:dirt"), air.clone()], vec![2, 0]).unwrap();
+  assert_eq!(volume, reordered);
+
+  // Different blocks or a different region do
+  let swapped = BlockVolume::from_palette(cuboid, vec![air.clone(), stone.clone()], vec![1, 0]).unwrap();
+  assert_ne!(volume, swapped);
+  let moved = Cuboid::new(BlockPosition::new(0, 1, 0), BlockPosition::new(0, 2, 0));
+  assert_ne!(volume, BlockVolume::from_palette(moved, vec![air, stone], vec![0, 1]).unwrap());
+}
+
+#[test]
+fn rejects_invalid_palettes() {
+  let cuboid = Cuboid::new(BlockPosition::new(0, 0, 0), BlockPosition::new(0, 1, 0));
+  let air = BlockPermutation::new("minecraft:air");
+
+  assert!(BlockVolume::from_palette(cuboid, vec![air.clone()], vec![0]).is_err());
+  assert!(BlockVolume::from_palette(cuboid, vec![air], vec![0, 1]).is_err());
+}
+
+#[test]
+fn round_trips_through_a_dimension() {
+  let host = MockHost::new();
+  let dimension = host.world("default").dimension().clone();
+  let lamp = BlockPermutation::new("minecraft:redstone_lamp").with_state("lit", true);
+
+  let mut volume = dimension.get_blocks(region()).unwrap();
+  assert_eq!(volume.palette(), &[BlockPermutation::new("minecraft:air")]);
+
+  volume.set(BlockPosition::new(1, 12, 5), &lamp);
+  dimension.set_blocks(&volume).unwrap();
+
+  assert_eq!(dimension.block_permutation(BlockPosition::new(1, 12, 5)).unwrap(), lamp);
+  assert_eq!(dimension.get_blocks(region()).unwrap().palette().len(), 2);
+  assert_eq!(host.calls_to("set_blocks").len(), 1);
+}
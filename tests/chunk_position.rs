@@ -0,0 +1,67 @@
+use serenityrs::protocol::types::block_position::BlockPosition;
+use serenityrs::protocol::types::chunk_local_position::{ChunkLocalPosition, SubChunkLocalPosition};
+use serenityrs::protocol::types::chunk_position::ChunkPosition;
+use serenityrs::protocol::types::sub_chunk_position::SubChunkPosition;
+use serenityrs::protocol::types::vector3f::Vector3f;
+
+#[test]
+fn block_positions_round_trip_through_chunks() {
+  for block in [BlockPosition::new(0, 0, 0), BlockPosition::new(-1, -64, -1), BlockPosition::new(17, 319, -33), BlockPosition::new(-30_000_000, 5, 29_999_999)] {
+    let chunk = ChunkPosition::from(block);
+    assert_eq!(chunk.block_at(ChunkLocalPosition::from(block)), block);
+    assert!(chunk.contains(block));
+
+    let sub_chunk = SubChunkPosition::from(block);
+    assert_eq!(sub_chunk.block_at(SubChunkLocalPosition::from(block)), block);
+    assert_eq!(sub_chunk.chunk(), chunk);
+  }
+}
+
+#[test]
+fn negative_positions_map_to_negative_chunks() {
+  assert_eq!(ChunkPosition::from(BlockPosition::new(-1, 0, 16)), ChunkPosition { x: -1, z: 1 });
+  assert_eq!(SubChunkPosition::from(BlockPosition::new(15, -1, -16)), SubChunkPosition { x: 0, y: -1, z: -1 });
+  assert_eq!(ChunkPosition::from(Vector3f { x: -0.5, y: 70.0, z: 15.9 }), ChunkPosition { x: -1, z: 0 });
+  assert_eq!(SubChunkPosition { x: -1, y: -4, z: 2 }.min_block(), BlockPosition::new(-16, -64, 32));
+}
+
+#[test]
+fn sub_chunk_storage_index() {
+  for index in [0, 1, 15, 16, 255, 256, 4095] {
+    assert_eq!(SubChunkLocalPosition::from_index(index).unwrap().index(), index);
+  }
+
+  assert_eq!(SubChunkLocalPosition { x: 1, y: 2, z: 3 }.index(), 256 + 3 * 16 + 2);
+  assert_eq!(SubChunkLocalPosition::from_index(4096), None);
+}
+
+#[test]
+fn packed_keys_round_trip() {
+  for (x, z) in [(0, 0), (-1, -1), (i32::MAX, i32::MIN), (1_875_000, -1_875_000)] {
+    let chunk = ChunkPosition { x, z };
+    assert_eq!(ChunkPosition::from_key(chunk.key()), chunk);
+  }
+
+  for (x, y, z) in [(0, 0, 0), (-1, -1, -1), (1_875_000, -4, -1_875_000), (-5, 19, 7)] {
+    let sub_chunk = SubChunkPosition { x, y, z };
+    assert_eq!(SubChunkPosition::from_key(sub_chunk.key()), sub_chunk);
+  }
+
+  assert_ne!(ChunkPosition { x: 1, z: 0 }.key(), ChunkPosition { x: 0, z: 1 }.key());
+}
+
+#[test]
+fn chunks_within_radius_are_circular() {
+  let center = ChunkPosition { x: 10, z: -3 };
+
+  assert_eq!(center.within_radius(0).collect::<Vec<_>>(), vec![center]);
+  assert_eq!(center.within_radius(1).count(), 5);
+  assert_eq!(center.within_radius(2).count(), 13);
+  assert_eq!(center.within_radius(-1).count(), 0);
+
+  for chunk in center.within_radius(8) {
+    assert!(center.distance(chunk) <= 8.0);
+  }
+  assert!(!center.is_within_radius(ChunkPosition { x: 16, z: 3 }, 8));
+  assert!(center.is_within_radius(ChunkPosition { x: 16, z: 1 }, 8));
+}
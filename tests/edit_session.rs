@@ -0,0 +1,231 @@
+use serenityrs::backend::backend::DimensionBackend;
+use serenityrs::backend::mock::{MockDimension, MockHost};
+use serenityrs::block::block_permutation::{BlockPermutation, BlockState};
+use serenityrs::block::block_volume::BlockVolume;
+use serenityrs::edit::change_set::ChangeSet;
+use serenityrs::edit::clipboard::{Clipboard, MirrorAxis};
+use serenityrs::edit::history::{EditHistory, PlayerHistories};
+use serenityrs::edit::session::EditSession;
+use serenityrs::protocol::types::block_position::BlockPosition;
+use serenityrs::protocol::types::cuboid::Cuboid;
+use serenityrs::world::player::player_ref::PlayerRef;
+
+fn block(identifier: &str) -> BlockPermutation {
+  BlockPermutation::new(identifier)
+}
+
+fn identifier(dimension: &MockDimension, x: i32, y: i32, z: i32) -> String {
+  dimension.block_identifier(BlockPosition::new(x, y, z)).unwrap()
+}
+
+fn dimension() -> MockDimension {
+  MockHost::new().world("default").dimension().clone()
+}
+
+#[test]
+fn fills_and_replaces_recording_prior_blocks() {
+  let dimension = dimension();
+  let region = Cuboid::new(BlockPosition::new(0, 0, 0), BlockPosition::new(2, 2, 2));
+  dimension.set_current_block(BlockPosition::new(1, 1, 1), &block("minecraft:dirt"));
+
+  let mut session = EditSession::new(&dimension);
+  assert_eq!(session.fill(region, &block("minecraft:stone")).unwrap(), 27);
+  assert_eq!(session.fill(region, &block("minecraft:stone")).unwrap(), 0);
+  assert_eq!(session.replace(region, |current| current.identifier == "minecraft:stone", &block("minecraft:glass")).unwrap(), 27);
+
+  let changes = session.finish();
+  assert_eq!(changes.len(), 27);
+  assert_eq!(changes.get(BlockPosition::new(1, 1, 1)).unwrap().before, block("minecraft:dirt"));
+  assert_eq!(changes.get(BlockPosition::new(1, 1, 1)).unwrap().after, block("minecraft:glass"));
+  assert_eq!(identifier(&dimension, 2, 2, 2), "minecraft:glass");
+}
+
+#[test]
+fn records_nothing_when_the_write_fails() {
+  let dimension = dimension();
+  let region = Cuboid::new(BlockPosition::new(0, 0, 0), BlockPosition::new(2, 2, 2));
+  dimension.set_read_only(true);
+
+  let mut session = EditSession::new(&dimension);
+  assert!(session.fill(region, &block("minecraft:stone")).is_err());
+  assert!(session.changes().is_empty());
+
+  dimension.set_read_only(false);
+  assert_eq!(session.fill(region, &block("minecraft:stone")).unwrap(), 27);
+  assert_eq!(session.changes().len(), 27);
+}
+
+#[test]
+fn builds_walls_and_hollow_shells() {
+  let dimension = dimension();
+  let region = Cuboid::new(BlockPosition::new(0, 0, 0), BlockPosition::new(4, 3, 4));
+  let mut session = EditSession::new(&dimension);
+
+  assert_eq!(session.walls(region, &block("minecraft:stone")).unwrap(), 4 * 16);
+  assert_eq!(identifier(&dimension, 0, 3, 2), "minecraft:stone");
+  assert_eq!(identifier(&dimension, 2, 0, 2), "minecraft:air");
+
+  session.fill(region, &block("minecraft:dirt")).unwrap();
+  session.hollow(region, &block("minecraft:glass")).unwrap();
+  assert_eq!(identifier(&dimension, 2, 0, 2), "minecraft:glass");
+  assert_eq!(identifier(&dimension, 4, 2, 1), "minecraft:glass");
+  assert_eq!(identifier(&dimension, 2, 1, 2), "minecraft:air");
+}
+
+#[test]
+fn copies_and_pastes_with_transforms() {
+  let dimension = dimension();
+  dimension.set_current_block(BlockPosition::new(0, 0, 0), &block("minecraft:stone"));
+  dimension.set_current_block(BlockPosition::new(2, 0, 0), &block("minecraft:gold_block"));
+  dimension.set_current_block(BlockPosition::new(0, 0, 1), &block("minecraft:dirt"));
+
+  let mut session = EditSession::new(&dimension);
+  let clipboard = session.copy(Cuboid::new(BlockPosition::new(0, 0, 0), BlockPosition::new(2, 0, 1))).unwrap();
+  assert_eq!(clipboard.size(), BlockPosition::new(3, 1, 2));
+
+  session.paste(&clipboard, BlockPosition::new(10, 5, 10)).unwrap();
+  assert_eq!(identifier(&dimension, 12, 5, 10), "minecraft:gold_block");
+  assert_eq!(identifier(&dimension, 10, 5, 11), "minecraft:dirt");
+
+  // A quarter turn clockwise moves east to south
+  let rotated = clipboard.rotate(1);
+  assert_eq!(rotated.size(), BlockPosition::new(2, 1, 3));
+  assert_eq!(rotated.get(BlockPosition::new(1, 0, 0)), Some(&block("minecraft:stone")));
+  assert_eq!(rotated.get(BlockPosition::new(1, 0, 2)), Some(&block("minecraft:gold_block")));
+  assert_eq!(rotated.get(BlockPosition::new(0, 0, 0)), Some(&block("minecraft:dirt")));
+  assert_eq!(clipboard.rotate(4), clipboard);
+  assert_eq!(clipboard.rotate(1).rotate(-1), clipboard);
+
+  let mirrored = clipboard.mirror(MirrorAxis::X);
+  assert_eq!(mirrored.get(BlockPosition::new(0, 0, 0)), Some(&block("minecraft:gold_block")));
+  assert_eq!(mirrored.mirror(MirrorAxis::X), clipboard);
+  assert_eq!(clipboard.mirror(MirrorAxis::Z).get(BlockPosition::new(0, 0, 0)), Some(&block("minecraft:dirt")));
+}
+
+#[test]
+fn turns_direction_states_with_the_blocks() {
+  let stairs = block("minecraft:oak_stairs").with_state("weirdo_direction", 0).with_state("upside_down_bit", true);
+  let log = block("minecraft:oak_log").with_state("pillar_axis", "x");
+  let door = block("minecraft:oak_door").with_state("direction", 1).with_state("door_hinge_bit", false);
+  let observer = block("minecraft:observer").with_state("minecraft:facing_direction", "up");
+  let chest = block("minecraft:chest").with_state("minecraft:cardinal_direction", "north");
+  let palette = vec![stairs, log, door, observer, chest];
+
+  // A column of blocks keeps its positions when turned
+  let column = Cuboid::new(BlockPosition::new(0, 0, 0), BlockPosition::new(0, 4, 0));
+  let clipboard = Clipboard::new(&BlockVolume::from_palette(column, palette, vec![0, 1, 2, 3, 4]).unwrap());
+  let state = |clipboard: &Clipboard, y: i32, name: &str| clipboard.get(BlockPosition::new(0, y, 0)).unwrap().state(name).cloned();
+
+  // A quarter turn clockwise turns east to south and west to north
+  let rotated = clipboard.rotate(1);
+  assert_eq!(state(&rotated, 0, "weirdo_direction"), Some(BlockState::Int(2)));
+  assert_eq!(state(&rotated, 0, "upside_down_bit"), Some(BlockState::Bool(true)));
+  assert_eq!(state(&rotated, 1, "pillar_axis"), Some(BlockState::from("z")));
+  assert_eq!(state(&rotated, 2, "direction"), Some(BlockState::Int(2)));
+  assert_eq!(state(&rotated, 3, "minecraft:facing_direction"), Some(BlockState::from("up")));
+  assert_eq!(state(&rotated, 4, "minecraft:cardinal_direction"), Some(BlockState::from("east")));
+  assert_eq!(clipboard.rotate(2).rotate(2), clipboard);
+  assert_eq!(rotated.rotate(-1), clipboard);
+
+  // Mirroring flips east and west, and the side doors open to
+  let mirrored = clipboard.mirror(MirrorAxis::X);
+  assert_eq!(state(&mirrored, 0, "weirdo_direction"), Some(BlockState::Int(1)));
+  assert_eq!(state(&mirrored, 1, "pillar_axis"), Some(BlockState::from("x")));
+  assert_eq!(state(&mirrored, 2, "direction"), Some(BlockState::Int(3)));
+  assert_eq!(state(&mirrored, 2, "door_hinge_bit"), Some(BlockState::Bool(true)));
+  assert_eq!(state(&mirrored, 4, "minecraft:cardinal_direction"), Some(BlockState::from("north")));
+  assert_eq!(state(&clipboard.mirror(MirrorAxis::Z), 4, "minecraft:cardinal_direction"), Some(BlockState::from("south")));
+  assert_eq!(mirrored.mirror(MirrorAxis::X), clipboard);
+}
+
+#[test]
+fn applies_changes_per_sub_chunk() {
+  let host = MockHost::new();
+  let dimension = host.world("default").dimension().clone();
+  let stone = block("minecraft:stone");
+  let air = block("minecraft:air");
+
+  // Two lone changes far apart, and a dense group of changes within one sub-chunk
+  let mut changes = ChangeSet::new();
+  changes.record(BlockPosition::new(0, 0, 0), air.clone(), stone.clone());
+  changes.record(BlockPosition::new(100_000, 0, -100_000), air.clone(), stone.clone());
+  for x in 0..3 {
+    for z in 0..3 {
+      changes.record(BlockPosition::new(32 + x, 16, 32 + z), air.clone(), stone.clone());
+    }
+  }
+
+  changes.redo(&dimension).unwrap();
+
+  // The lone changes are set one by one, the group as its own small region
+  assert_eq!(host.calls_to("set_block").len(), 2);
+  let bulk = host.calls_to("set_blocks");
+  assert_eq!(bulk.len(), 1);
+  assert_eq!(bulk[0].args, vec!["32", "16", "32", "34", "16", "34"]);
+  assert_eq!(identifier(&dimension, 100_000, 0, -100_000), "minecraft:stone");
+  assert_eq!(identifier(&dimension, 34, 16, 34), "minecraft:stone");
+}
+
+#[test]
+fn counts_state_values_in_the_memory_size() {
+  let mut red = ChangeSet::new();
+  red.record(BlockPosition::new(0, 0, 0), block("minecraft:air"), block("minecraft:wool").with_state("color", "red"));
+  let mut light_blue = ChangeSet::new();
+  light_blue.record(BlockPosition::new(0, 0, 0), block("minecraft:air"), block("minecraft:wool").with_state("color", "light_blue"));
+
+  assert_eq!(light_blue.memory_size(), red.memory_size() + "light_blue".len() - "red".len());
+}
+
+#[test]
+fn undoes_and_redoes_edits() {
+  let dimension = dimension();
+  let region = Cuboid::new(BlockPosition::new(0, 0, 0), BlockPosition::new(1, 1, 1));
+  let mut history = EditHistory::default();
+
+  let mut session = EditSession::new(&dimension);
+  session.fill(region, &block("minecraft:stone")).unwrap();
+  history.push(session.finish());
+
+  let mut session = EditSession::new(&dimension);
+  session.fill(Cuboid::new(BlockPosition::new(0, 0, 0), BlockPosition::new(0, 0, 0)), &block("minecraft:dirt")).unwrap();
+  history.push(session.finish());
+
+  assert!(history.undo(&dimension).unwrap());
+  assert_eq!(identifier(&dimension, 0, 0, 0), "minecraft:stone");
+  assert!(history.undo(&dimension).unwrap());
+  assert_eq!(identifier(&dimension, 0, 0, 0), "minecraft:air");
+  assert_eq!(identifier(&dimension, 1, 1, 1), "minecraft:air");
+  assert!(!history.undo(&dimension).unwrap());
+
+  assert!(history.redo(&dimension).unwrap());
+  assert_eq!(identifier(&dimension, 1, 1, 1), "minecraft:stone");
+  assert!(history.can_redo());
+
+  // A new edit clears the redo stack
+  let mut session = EditSession::new(&dimension);
+  session.fill(region, &block("minecraft:glass")).unwrap();
+  history.push(session.finish());
+  assert!(!history.can_redo());
+}
+
+#[test]
+fn caps_history_memory_per_player() {
+  let dimension = dimension();
+  let mut histories = PlayerHistories::new(1);
+  let steve = PlayerRef::new("steve");
+
+  for identifier in ["minecraft:stone", "minecraft:dirt", "minecraft:glass"] {
+    let mut session = EditSession::new(&dimension);
+    session.fill(Cuboid::new(BlockPosition::new(0, 0, 0), BlockPosition::new(3, 3, 3)), &block(identifier)).unwrap();
+    histories.history(&steve).push(session.finish());
+  }
+
+  // Only the latest edit is kept once the cap is exceeded
+  let history = histories.history(&steve);
+  assert!(history.undo(&dimension).unwrap());
+  assert!(!history.undo(&dimension).unwrap());
+  assert_eq!(identifier(&dimension, 0, 0, 0), "minecraft:dirt");
+
+  assert!(!histories.history(&PlayerRef::new("alex")).can_undo());
+  assert!(histories.remove(&steve).is_some());
+}
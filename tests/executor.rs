@@ -0,0 +1,176 @@
+use std::cell::RefCell;
+use std::future::Future;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::task::{Context, Wake, Waker};
+
+use serenityrs::backend::backend::WorldBackend;
+use serenityrs::backend::mock::{MockHost, MockWorld};
+use serenityrs::error::error::SerenityError;
+use serenityrs::serenity::executor::{Executor, WaitTicks};
+
+type Log = Rc<RefCell<Vec<String>>>;
+
+fn executor(world: &MockWorld) -> Executor<MockWorld> {
+  Executor::detached(world.logger().clone())
+}
+
+fn tick(executor: &Executor<MockWorld>, world: &MockWorld) {
+  world.advance_tick();
+  executor.tick(world);
+}
+
+struct NoopWaker;
+
+impl Wake for NoopWaker {
+  fn wake(self: Arc<Self>) {}
+}
+
+#[test]
+fn resolves_wait_ticks_with_the_ticking_world() {
+  let host = MockHost::new();
+  let world = host.world("default");
+  let executor = executor(&world);
+  let log = Log::default();
+
+  let task_log = log.clone();
+  executor.spawn(async move {
+    task_log.borrow_mut().push("started".to_string());
+
+    for ticks in [1, 3] {
+      let world = WaitTicks::<MockWorld>::new(ticks).await?;
+      task_log.borrow_mut().push(format!("tick {}", world.current_tick()));
+    }
+
+    Ok(())
+  });
+
+  // The task runs until its first await right away
+  assert_eq!(*log.borrow(), vec!["started"]);
+  assert_eq!(executor.task_count(), 1);
+
+  for _ in 0..5 {
+    tick(&executor, &world);
+  }
+
+  assert_eq!(*log.borrow(), vec!["started", "tick 1", "tick 4"]);
+  assert_eq!(executor.task_count(), 0);
+}
+
+#[test]
+fn follows_the_first_world_that_ticks() {
+  let host = MockHost::new();
+  let overworld = host.world("overworld");
+  let nether = host.world("nether");
+  let executor = executor(&overworld);
+  let log = Log::default();
+
+  let task_log = log.clone();
+  executor.spawn(async move {
+    let world = WaitTicks::<MockWorld>::new(2).await?;
+    task_log.borrow_mut().push(world.identifier());
+
+    Ok(())
+  });
+
+  tick(&executor, &overworld);
+  tick(&executor, &nether);
+  tick(&executor, &nether);
+  assert!(log.borrow().is_empty());
+
+  tick(&executor, &overworld);
+  assert_eq!(*log.borrow(), vec!["overworld"]);
+}
+
+#[test]
+fn stops_polling_cancelled_tasks() {
+  let host = MockHost::new();
+  let world = host.world("default");
+  let executor = executor(&world);
+  let log = Log::default();
+
+  let task_log = log.clone();
+  let token = executor.spawn(async move {
+    loop {
+      WaitTicks::<MockWorld>::new(1).await?;
+      task_log.borrow_mut().push("tick".to_string());
+    }
+  });
+
+  tick(&executor, &world);
+  token.cancel();
+  tick(&executor, &world);
+
+  assert_eq!(*log.borrow(), vec!["tick"]);
+  assert_eq!(executor.task_count(), 0);
+}
+
+#[test]
+fn logs_failed_and_panicked_tasks() {
+  let host = MockHost::new();
+  let world = host.world("default");
+  let executor = executor(&world);
+  let log = Log::default();
+
+  executor.spawn(async move {
+    WaitTicks::<MockWorld>::new(1).await?;
+    Err(SerenityError::MissingProperty { wrapper: "Test", property: "value".to_string() })
+  });
+  executor.spawn(async move {
+    WaitTicks::<MockWorld>::new(1).await?;
+    panic!("boom");
+  });
+
+  let task_log = log.clone();
+  executor.spawn(async move {
+    WaitTicks::<MockWorld>::new(2).await?;
+    task_log.borrow_mut().push("survived".to_string());
+
+    Ok(())
+  });
+
+  tick(&executor, &world);
+  tick(&executor, &world);
+
+  assert_eq!(*log.borrow(), vec!["survived"]);
+
+  let errors = host.calls_to("error");
+  assert_eq!(errors.len(), 2);
+  assert!(errors[0].args[0].starts_with("Async task failed:"));
+  assert!(errors[1].args[0].starts_with("Async task panicked: boom"));
+}
+
+#[test]
+fn clears_the_current_task_after_a_panic() {
+  let host = MockHost::new();
+  let world = host.world("default");
+  let executor = executor(&world);
+
+  // Held by a task that is still waiting, so it is only dropped along with the executor
+  let held = Rc::new(());
+  let task_held = held.clone();
+  executor.spawn(async move {
+    let _held = task_held;
+    WaitTicks::<MockWorld>::new(100).await?;
+
+    Ok(())
+  });
+  executor.spawn(async move {
+    WaitTicks::<MockWorld>::new(1).await?;
+    panic!("boom");
+  });
+  tick(&executor, &world);
+
+  // Outside of a task, there is no current executor left to wait on
+  let waker = Waker::from(Arc::new(NoopWaker));
+  let mut wait = WaitTicks::<MockWorld>::new(1);
+  let polled = panic::catch_unwind(AssertUnwindSafe(|| {
+    let _ = Pin::new(&mut wait).poll(&mut Context::from_waker(&waker));
+  }));
+  assert!(polled.is_err());
+
+  drop(executor);
+  assert_eq!(Rc::strong_count(&held), 1);
+}
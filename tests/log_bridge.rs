@@ -0,0 +1,141 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::thread;
+
+use log::{Level, Log, Record};
+use serenityrs::backend::backend::LoggerBackend;
+use serenityrs::error::error::SerenityError;
+use serenityrs::logger::bridge::SerenityLogBridge;
+
+type Records = Rc<RefCell<Vec<(&'static str, String)>>>;
+
+/**
+ * A logger that is neither `Send` nor `Sync`, like the JS logger the bridge wraps.
+*/
+#[derive(Clone, Default)]
+struct RecordingLogger {
+  records: Records,
+}
+
+impl RecordingLogger {
+  fn write(&self, method: &'static str, message: &str) -> Result<(), SerenityError> {
+    self.records.borrow_mut().push((method, message.to_string()));
+    Ok(())
+  }
+}
+
+impl LoggerBackend for RecordingLogger {
+  fn log(&self, message: &str) -> Result<(), SerenityError> {
+    self.write("log", message)
+  }
+
+  fn info(&self, message: &str) -> Result<(), SerenityError> {
+    self.write("info", message)
+  }
+
+  fn warn(&self, message: &str) -> Result<(), SerenityError> {
+    self.write("warn", message)
+  }
+
+  fn error(&self, message: &str) -> Result<(), SerenityError> {
+    self.write("error", message)
+  }
+
+  fn success(&self, message: &str) -> Result<(), SerenityError> {
+    self.write("success", message)
+  }
+
+  fn debug(&self, message: &str) -> Result<(), SerenityError> {
+    self.write("debug", message)
+  }
+}
+
+fn log(bridge: &SerenityLogBridge, level: Level, message: &str) {
+  bridge.log(&Record::builder().level(level).target("plugin").args(format_args!("{}", message)).build());
+}
+
+fn recording_bridge() -> (SerenityLogBridge, Records) {
+  let logger = RecordingLogger::default();
+  let records = logger.records.clone();
+
+  (SerenityLogBridge::from_backend(logger), records)
+}
+
+fn messages(records: &Records) -> Vec<(&'static str, String)> {
+  records.borrow().clone()
+}
+
+#[test]
+fn writes_records_of_the_js_thread_right_away() {
+  let (bridge, records) = recording_bridge();
+
+  log(&bridge, Level::Error, "failed");
+  log(&bridge, Level::Warn, "careful");
+  log(&bridge, Level::Info, "started");
+  log(&bridge, Level::Trace, "details");
+
+  assert_eq!(messages(&records), vec![
+    ("error", "[plugin] failed".to_string()),
+    ("warn", "[plugin] careful".to_string()),
+    ("info", "[plugin] started".to_string()),
+    ("debug", "[plugin] details".to_string()),
+  ]);
+}
+
+#[test]
+fn buffers_records_of_other_threads_until_flushed() {
+  let (bridge, records) = recording_bridge();
+
+  let worker = bridge.clone();
+  thread::spawn(move || {
+    log(&worker, Level::Info, "first");
+    log(&worker, Level::Info, "second");
+
+    // Flushing off the JS thread does nothing
+    worker.flush();
+  }).join().unwrap();
+  assert!(records.borrow().is_empty());
+
+  // A record of the JS thread writes the buffered ones first, keeping their order
+  log(&bridge, Level::Info, "third");
+  assert_eq!(messages(&records), vec![
+    ("info", "[plugin] first".to_string()),
+    ("info", "[plugin] second".to_string()),
+    ("info", "[plugin] third".to_string()),
+  ]);
+
+  bridge.flush();
+  assert_eq!(records.borrow().len(), 3);
+}
+
+#[test]
+fn drops_the_oldest_records_past_the_buffer_limit() {
+  let (bridge, records) = recording_bridge();
+
+  let worker = bridge.clone();
+  thread::spawn(move || {
+    for index in 0..1030 {
+      log(&worker, Level::Debug, &index.to_string());
+    }
+  }).join().unwrap();
+
+  bridge.flush();
+
+  let records = messages(&records);
+  assert_eq!(records.len(), 1025);
+  assert_eq!(records[0], ("warn", "6 log records from other threads were dropped".to_string()));
+  assert_eq!(records[1], ("debug", "[plugin] 6".to_string()));
+  assert_eq!(records[1024], ("debug", "[plugin] 1029".to_string()));
+}
+
+#[test]
+fn only_releases_the_logger_on_the_js_thread() {
+  // Dropped off the JS thread, the logger is leaked rather than released on the wrong thread
+  let (bridge, records) = recording_bridge();
+  thread::spawn(move || drop(bridge)).join().unwrap();
+  assert_eq!(Rc::strong_count(&records), 2);
+
+  let (bridge, records) = recording_bridge();
+  drop(bridge);
+  assert_eq!(Rc::strong_count(&records), 1);
+}
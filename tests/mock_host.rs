@@ -0,0 +1,164 @@
+use serenityrs::backend::backend::{DimensionBackend, LoggerBackend, PlayerBackend, WorldBackend};
+use serenityrs::backend::events::{PlayerChatEvent, PlayerExecuteCommandEvent, PlayerJoinEvent, PlayerLeaveEvent};
+use serenityrs::backend::mock::{
+  MockCall, MockHost, MockPlayerChatSignal, MockPlayerExecuteCommandSignal, MockPlayerJoinSignal, MockPlayerLeaveSignal
+};
+use serenityrs::block::block_permutation::{BlockPermutation, BlockState};
+use serenityrs::error::error::SerenityError;
+use serenityrs::protocol::types::block_position::BlockPosition;
+use serenityrs::protocol::types::vector3f::Vector3f;
+
+const SPAWN: Vector3f = Vector3f { x: 0.5, y: 64.0, z: 0.5 };
+
+fn teleport_on_join<E: PlayerJoinEvent>(event: &E) -> Result<(), SerenityError> {
+  let player = event.player();
+
+  player.teleport(SPAWN)?;
+  player.send_message(&format!("Welcome to {}, {}!", event.world().identifier(), player.username()))?;
+  event.world().logger().info(&format!("{} joined", player.username()))
+}
+
+fn filter_chat<E: PlayerChatEvent>(event: &mut E) -> Result<(), SerenityError> {
+  if event.message().contains("badword") {
+    event.player().send_message("Watch your language")?;
+    return event.cancel();
+  }
+
+  let message = event.message().trim().to_string();
+  event.set_message(&message)
+}
+
+fn alias_commands<E: PlayerExecuteCommandEvent>(event: &mut E) -> Result<(), SerenityError> {
+  if event.command().starts_with("/stop") && !event.player().is_op()? {
+    return event.cancel();
+  }
+
+  match event.command().strip_prefix("/s ") {
+    Some(target) => event.set_command(&format!("/spawn {}", target)),
+    None => Ok(())
+  }
+}
+
+fn farewell<E: PlayerLeaveEvent>(event: &mut E) -> Result<(), SerenityError> {
+  let message = format!("{} left ({})", event.player().username(), event.reason());
+  event.set_message(&message)
+}
+
+#[test]
+fn teleports_the_player_on_join() {
+  let host = MockHost::new();
+  let world = host.world("default");
+  let player = world.add_player("Steve");
+
+  teleport_on_join(&MockPlayerJoinSignal::new(&world, &player)).unwrap();
+
+  assert_eq!(player.position(), SPAWN);
+  assert_eq!(host.calls(), vec![
+    MockCall::new("Steve", "teleport", &["0.5", "64", "0.5"]),
+    MockCall::new("Steve", "send_message", &["Welcome to default, Steve!"]),
+    MockCall::new("default", "info", &["Steve joined"]),
+  ]);
+}
+
+#[test]
+fn cancels_filtered_chat_messages() {
+  let host = MockHost::new();
+  let world = host.world("default");
+  let player = world.add_player("Alex");
+
+  let mut signal = MockPlayerChatSignal::new(&world, &player, "a badword");
+  filter_chat(&mut signal).unwrap();
+  assert!(signal.is_cancelled().unwrap());
+  assert_eq!(host.calls_to("send_message"), vec![MockCall::new("Alex", "send_message", &["Watch your language"])]);
+
+  let mut signal = MockPlayerChatSignal::new(&world, &player, "  hello  ");
+  filter_chat(&mut signal).unwrap();
+  assert!(!signal.is_cancelled().unwrap());
+  assert_eq!(signal.message(), "hello");
+}
+
+#[test]
+fn rewrites_and_cancels_commands() {
+  let host = MockHost::new();
+  let world = host.world("default");
+  let player = world.add_player("Steve");
+
+  let mut signal = MockPlayerExecuteCommandSignal::new(&world, &player, "/s Alex");
+  alias_commands(&mut signal).unwrap();
+  assert_eq!(signal.command(), "/spawn Alex");
+  assert!(!signal.is_cancelled().unwrap());
+
+  let mut signal = MockPlayerExecuteCommandSignal::new(&world, &player, "/stop");
+  alias_commands(&mut signal).unwrap();
+  assert!(signal.is_cancelled().unwrap());
+  assert_eq!(signal.command(), "/stop");
+
+  player.set_op(true);
+  let mut signal = MockPlayerExecuteCommandSignal::new(&world, &player, "/stop");
+  alias_commands(&mut signal).unwrap();
+  assert!(!signal.is_cancelled().unwrap());
+}
+
+#[test]
+fn cancelling_twice_keeps_the_signal_cancelled() {
+  let host = MockHost::new();
+  let world = host.world("default");
+  let player = world.add_player("Alex");
+
+  let mut signal = MockPlayerChatSignal::new(&world, &player, "hello");
+  signal.cancel().unwrap();
+  signal.cancel().unwrap();
+  assert!(signal.is_cancelled().unwrap());
+
+  // A cancelled signal can still be modified, for listeners checking the message afterwards
+  signal.set_message("bye").unwrap();
+  assert_eq!(signal.message(), "bye");
+}
+
+#[test]
+fn replaces_the_leave_message() {
+  let host = MockHost::new();
+  let world = host.world("default");
+  let player = world.add_player("Steve");
+
+  let mut signal = MockPlayerLeaveSignal::new(&world, &player, 2, "Steve left the game");
+  farewell(&mut signal).unwrap();
+  assert_eq!(signal.message(), "Steve left (2)");
+  assert!(host.calls().is_empty());
+}
+
+#[test]
+fn tracks_the_players_of_a_world() {
+  let host = MockHost::new();
+  let world = host.world("default");
+  let steve = world.add_player("Steve");
+  let alex = world.add_player("Alex");
+
+  assert_ne!(steve.uuid(), alex.uuid());
+
+  world.remove_player(&steve);
+  let players = world.get_players().unwrap();
+  assert_eq!(players.len(), 1);
+  assert_eq!(players[0].username(), "Alex");
+}
+
+#[test]
+fn sets_blocks_with_permutations() {
+  let host = MockHost::new();
+  let world = host.world("default");
+  let dimension = world.dimension();
+  let position = BlockPosition { x: 1, y: 64, z: -2 };
+
+  assert_eq!(dimension.block_identifier(position).unwrap(), "minecraft:air");
+
+  let stairs = BlockPermutation::new("minecraft:oak_stairs")
+    .with_state("weirdo_direction", 2)
+    .with_state("upside_down_bit", false);
+  dimension.set_block(position, &stairs).unwrap();
+
+  assert_eq!(dimension.block_permutation(position).unwrap(), stairs);
+  assert_eq!(stairs.state("weirdo_direction"), Some(&BlockState::Int(2)));
+  assert_eq!(host.calls_to("set_block"), vec![MockCall::new("minecraft:overworld", "set_block", &[
+    "1", "64", "-2", "minecraft:oak_stairs[\"upside_down_bit\"=false,\"weirdo_direction\"=2]",
+  ])]);
+}
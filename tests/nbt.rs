@@ -0,0 +1,185 @@
+use serenityrs::protocol::nbt::codec::NbtEncoding;
+use serenityrs::protocol::nbt::error::NbtError;
+use serenityrs::protocol::nbt::tag::{Compound, List, NamedTag, Tag, TagType};
+
+// The same compound in both encodings, laid out by hand from the format
+const LITTLE_ENDIAN: &[u8] = &[
+  0x0a, 0x00, 0x00,
+  0x01, 0x01, 0x00, b'b', 0x01,
+  0x03, 0x01, 0x00, b'i', 0x2c, 0x01, 0x00, 0x00,
+  0x04, 0x01, 0x00, b'l', 0xfe, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+  0x08, 0x01, 0x00, b's', 0x02, 0x00, b'h', b'i',
+  0x09, 0x04, 0x00, b'l', b'i', b's', b't', 0x02, 0x02, 0x00, 0x00, 0x00, 0x01, 0x00, 0x02, 0x00,
+  0x0a, 0x01, 0x00, b'c', 0x05, 0x01, 0x00, b'f', 0x00, 0x00, 0xc0, 0x3f, 0x00,
+  0x0b, 0x02, 0x00, b'i', b'a', 0x01, 0x00, 0x00, 0x00, 0xff, 0xff, 0xff, 0xff,
+  0x00,
+];
+
+const NETWORK: &[u8] = &[
+  0x0a, 0x00,
+  0x01, 0x01, b'b', 0x01,
+  0x03, 0x01, b'i', 0xd8, 0x04,
+  0x04, 0x01, b'l', 0x03,
+  0x08, 0x01, b's', 0x02, b'h', b'i',
+  0x09, 0x04, b'l', b'i', b's', b't', 0x02, 0x04, 0x01, 0x00, 0x02, 0x00,
+  0x0a, 0x01, b'c', 0x05, 0x01, b'f', 0x00, 0x00, 0xc0, 0x3f, 0x00,
+  0x0b, 0x02, b'i', b'a', 0x02, 0x01,
+  0x00,
+];
+
+fn fixture() -> NamedTag {
+  let compound = Compound::new()
+    .with("b", 1i8)
+    .with("i", 300)
+    .with("l", -2i64)
+    .with("s", "hi")
+    .with("list", List::new(vec![Tag::Short(1), Tag::Short(2)]).unwrap())
+    .with("c", Compound::new().with("f", 1.5f32))
+    .with("ia", Tag::IntArray(vec![-1]));
+
+  NamedTag::new("", compound)
+}
+
+#[test]
+fn reads_and_writes_both_encodings() {
+  for (encoding, bytes) in [(NbtEncoding::LittleEndian, LITTLE_ENDIAN), (NbtEncoding::Network, NETWORK)] {
+    let named = NamedTag::from_bytes(bytes, encoding).unwrap();
+    assert_eq!(named, fixture());
+    assert_eq!(named.to_bytes(encoding).unwrap(), bytes);
+  }
+}
+
+#[test]
+fn read_advances_past_the_root_tag() {
+  let bytes = [NETWORK, &[0xaa]].concat();
+  let mut slice: &[u8] = &bytes;
+
+  NamedTag::read(&mut slice, NbtEncoding::Network).unwrap();
+  assert_eq!(slice, &[0xaa]);
+
+  assert_eq!(NamedTag::from_bytes(&bytes, NbtEncoding::Network), Err(NbtError::TrailingBytes(1)));
+}
+
+#[test]
+fn empty_lists_keep_their_element_type() {
+  let named = NamedTag::new("", Compound::new().with("empty", List::empty(TagType::Int)));
+
+  for encoding in [NbtEncoding::LittleEndian, NbtEncoding::Network] {
+    let read = NamedTag::from_bytes(&named.to_bytes(encoding).unwrap(), encoding).unwrap();
+    let list = read.tag.as_compound().unwrap().get("empty").unwrap().as_list().unwrap();
+    assert_eq!(list.element_type(), TagType::Int);
+  }
+}
+
+#[test]
+fn looks_up_compound_entries_by_name() {
+  let mut compound = Compound::new().with("a", 1).with("b", 2).with("c", 3);
+
+  assert_eq!(compound.insert("b", 20), Some(Tag::Int(2)));
+  assert_eq!(compound.remove("a"), Some(Tag::Int(1)));
+  assert_eq!(compound.get("c"), Some(&Tag::Int(3)));
+  *compound.get_mut("c").unwrap() = Tag::Int(30);
+  compound.insert("a", 10);
+
+  // Removing keeps the order of the other entries, inserting again adds to the end
+  let entries = compound.iter().map(|(name, tag)| (name.to_string(), tag.clone())).collect::<Vec<_>>();
+  assert_eq!(entries, vec![("b".to_string(), Tag::Int(20)), ("c".to_string(), Tag::Int(30)), ("a".to_string(), Tag::Int(10))]);
+  assert!(!compound.contains("d"));
+  assert_eq!(compound, Compound::new().with("b", 20).with("c", 30).with("a", 10));
+  assert_ne!(compound, Compound::new().with("a", 10).with("b", 20).with("c", 30));
+}
+
+#[test]
+fn reads_large_compounds() {
+  let compound = (0..100_000).map(|index| (index.to_string(), Tag::Int(index))).collect::<Compound>();
+  let named = NamedTag::new("", compound);
+
+  let read = NamedTag::from_bytes(&named.to_bytes(NbtEncoding::Network).unwrap(), NbtEncoding::Network).unwrap();
+  assert_eq!(read.tag.as_compound().unwrap().get("99999"), Some(&Tag::Int(99_999)));
+  assert_eq!(read, named);
+}
+
+#[test]
+fn rejects_strings_too_long_to_write() {
+  let long = "a".repeat(u16::MAX as usize + 1);
+
+  // Little-endian strings are prefixed with an u16, network strings with a VarInt
+  let value = NamedTag::new("", Compound::new().with("s", long.as_str()));
+  assert_eq!(value.to_bytes(NbtEncoding::LittleEndian), Err(NbtError::StringTooLong(long.len())));
+  assert!(value.to_bytes(NbtEncoding::Network).is_ok());
+
+  let name = NamedTag::new(&long, Compound::new());
+  assert_eq!(name.to_bytes(NbtEncoding::LittleEndian), Err(NbtError::StringTooLong(long.len())));
+  assert_eq!(NamedTag::new(&"a".repeat(u16::MAX as usize), 1).to_bytes(NbtEncoding::LittleEndian).unwrap().len(), 1 + 2 + 65535 + 4);
+}
+
+#[test]
+fn rejects_invalid_data() {
+  assert_eq!(NamedTag::from_bytes(&LITTLE_ENDIAN[..20], NbtEncoding::LittleEndian), Err(NbtError::UnexpectedEof));
+  assert_eq!(NamedTag::from_bytes(&[0x0d, 0x00, 0x00], NbtEncoding::LittleEndian), Err(NbtError::InvalidTagType(13)));
+  assert_eq!(NamedTag::from_bytes(&[0x03, 0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01], NbtEncoding::Network), Err(NbtError::VarIntTooLong));
+
+  // A list claiming more elements than there are bytes
+  let list = [0x09, 0x00, 0x00, 0x01, 0xff, 0xff, 0xff, 0x7f];
+  assert_eq!(NamedTag::from_bytes(&list, NbtEncoding::LittleEndian), Err(NbtError::UnexpectedEof));
+
+  // Deeply nested lists
+  let mut nested = vec![0x09, 0x00, 0x00];
+  for _ in 0..1000 {
+    nested.extend_from_slice(&[0x09, 0x01, 0x00, 0x00, 0x00]);
+  }
+  assert_eq!(NamedTag::from_bytes(&nested, NbtEncoding::LittleEndian), Err(NbtError::DepthLimit));
+
+  // Deeply nested compounds
+  let mut nested = vec![0x0a, 0x00, 0x00];
+  for _ in 0..1000 {
+    nested.extend_from_slice(&[0x0a, 0x00, 0x00]);
+  }
+  assert_eq!(NamedTag::from_bytes(&nested, NbtEncoding::LittleEndian), Err(NbtError::DepthLimit));
+
+  assert_eq!(List::new(vec![Tag::Int(1), Tag::Byte(1)]), Err(NbtError::MixedList));
+}
+
+#[test]
+fn prints_snbt() {
+  let snbt = fixture().tag.to_string();
+  assert_eq!(snbt, r#"{b:1b,i:300,l:-2L,s:"hi",list:[1s,2s],c:{f:1.5f},ia:[I;-1]}"#);
+
+  let compound = Compound::new()
+    .with("needs quotes", "say \"hi\"\n")
+    .with("bytes", Tag::ByteArray(vec![1, -1]))
+    .with("longs", Tag::LongArray(vec![5]))
+    .with("double", 2.0);
+  assert_eq!(Tag::Compound(compound).to_string(), r#"{"needs quotes":"say \"hi\"\n",bytes:[B;1b,-1b],longs:[L;5L],double:2.0d}"#);
+}
+
+#[test]
+fn parses_snbt() {
+  let tag = fixture().tag;
+  assert_eq!(Tag::from_snbt(&tag.to_string()).unwrap(), tag);
+
+  let parsed: Tag = r#" { 'single': 'it\'s', flag: true, plain: stone, n: 1.5, e: 1e3F, list: [ [], [1b] ] } "#.parse().unwrap();
+  let compound = parsed.as_compound().unwrap();
+  assert_eq!(compound.get("single"), Some(&Tag::String("it's".to_string())));
+  assert_eq!(compound.get("flag"), Some(&Tag::Byte(1)));
+  assert_eq!(compound.get("plain"), Some(&Tag::String("stone".to_string())));
+  assert_eq!(compound.get("n"), Some(&Tag::Double(1.5)));
+  assert_eq!(compound.get("e"), Some(&Tag::Float(1000.0)));
+  assert_eq!(compound.get("list").unwrap().as_list().unwrap().len(), 2);
+}
+
+#[test]
+fn rejects_invalid_snbt() {
+  for text in ["{a:1", "[1,2b]", "{a 1}", "\"open", "300b", "[B;1b,300]", "[I;2147483648]", "{a:1} extra", ""] {
+    assert!(matches!(Tag::from_snbt(text), Err(NbtError::Snbt { .. })), "{} should not parse", text);
+  }
+}
+
+#[test]
+fn reports_array_values_out_of_range() {
+  let error = Tag::from_snbt("[B; 1, -129]").unwrap_err();
+  assert_eq!(error, NbtError::Snbt { position: 7, message: "number out of range: -129".to_string() });
+
+  assert_eq!(Tag::from_snbt("[B;127,-128]").unwrap(), Tag::ByteArray(vec![127, -128]));
+  assert_eq!(Tag::from_snbt("[L;2147483648L]").unwrap(), Tag::LongArray(vec![2147483648]));
+}
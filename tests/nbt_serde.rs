@@ -0,0 +1,70 @@
+use serde::{Deserialize, Serialize};
+use serenityrs::protocol::nbt::codec::NbtEncoding;
+use serenityrs::protocol::nbt::de::{from_compound, from_tag};
+use serenityrs::protocol::nbt::ser::{to_compound, to_tag};
+use serenityrs::protocol::nbt::tag::{Compound, NamedTag, Tag};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+enum Mode {
+  Survival,
+  Spectating { target: String },
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct PlayerData {
+  name: String,
+  health: f32,
+  level: u8,
+  flying: bool,
+  position: (f64, f64, f64),
+  inventory: Vec<String>,
+  nickname: Option<String>,
+  mode: Mode,
+}
+
+fn player() -> PlayerData {
+  PlayerData {
+    name: "Steve".to_string(),
+    health: 20.0,
+    level: 200,
+    flying: true,
+    position: [0.5, 64.0, -3.5].into(),
+    inventory: vec!["minecraft:stone".to_string()],
+    nickname: None,
+    mode: Mode::Survival,
+  }
+}
+
+#[test]
+fn maps_structs_to_compounds() {
+  let compound = to_compound(&player()).unwrap();
+
+  assert_eq!(compound.get("health"), Some(&Tag::Float(20.0)));
+  assert_eq!(compound.get("level"), Some(&Tag::Byte(200u8 as i8)));
+  assert_eq!(compound.get("flying"), Some(&Tag::Byte(1)));
+  assert_eq!(compound.get("mode"), Some(&Tag::String("Survival".to_string())));
+  assert!(!compound.contains("nickname"));
+
+  assert_eq!(from_compound::<PlayerData>(&compound).unwrap(), player());
+}
+
+#[test]
+fn round_trips_through_bytes() {
+  let mut data = player();
+  data.nickname = Some("Notch".to_string());
+  data.mode = Mode::Spectating { target: "Alex".to_string() };
+
+  let bytes = NamedTag::new("", to_compound(&data).unwrap()).to_bytes(NbtEncoding::Network).unwrap();
+  let named = NamedTag::from_bytes(&bytes, NbtEncoding::Network).unwrap();
+
+  assert_eq!(from_tag::<PlayerData>(named.tag).unwrap(), data);
+}
+
+#[test]
+fn reports_mismatched_tags() {
+  let compound = Compound::new().with("name", 5);
+
+  assert!(from_compound::<PlayerData>(&compound).is_err());
+  assert!(to_tag(&vec![Some(1), None]).is_err());
+  assert!(to_compound(&5).is_err());
+}
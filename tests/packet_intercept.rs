@@ -0,0 +1,98 @@
+use serenityrs::protocol::binary::error::BinaryError;
+use serenityrs::protocol::packets::intercept::{intercept, Interception, PacketAction, PacketDirection};
+use serenityrs::protocol::packets::text::{TextPacket, TextType};
+use serenityrs::protocol::packets::update_block::UpdateBlockPacket;
+use serenityrs::protocol::packets::NetworkPacket;
+use serenityrs::protocol::types::block_position::BlockPosition;
+use serenityrs::serenity::listener::ListenerEvent;
+use serenityrs::world::events::world_event::WorldEvent;
+
+fn chat(message: &str) -> Vec<u8> {
+  TextPacket {
+    text_type: TextType::Chat,
+    needs_translation: false,
+    source_name: "Steve".to_string(),
+    message: message.to_string(),
+    parameters: Vec::new(),
+    xuid: String::new(),
+    platform_chat_id: String::new(),
+    filtered_message: String::new(),
+  }.to_bytes().unwrap()
+}
+
+#[test]
+fn re_encodes_modified_packets() {
+  let result = intercept::<TextPacket, _>(&chat("hello"), |packet| {
+    packet.message = format!("<{}> {}", packet.source_name, packet.message);
+    packet.text_type = TextType::Raw;
+    PacketAction::Pass
+  }).unwrap();
+
+  let Interception::Modified(bytes) = result else { panic!("expected a modified packet, found {:?}", result) };
+  let packet = TextPacket::from_bytes(&bytes).unwrap();
+  assert_eq!(packet.text_type, TextType::Raw);
+  assert_eq!(packet.message, "<Steve> hello");
+}
+
+#[test]
+fn spoofs_blocks() {
+  let update = UpdateBlockPacket { position: BlockPosition { x: 0, y: 64, z: 0 }, block_runtime_id: 10, flags: 3, layer: 0 };
+
+  let result = intercept::<UpdateBlockPacket, _>(&update.to_bytes().unwrap(), |packet| {
+    packet.block_runtime_id = 42;
+    PacketAction::Pass
+  }).unwrap();
+
+  assert_eq!(result, Interception::Modified(UpdateBlockPacket { block_runtime_id: 42, ..update }.to_bytes().unwrap()));
+}
+
+#[test]
+fn passes_and_cancels_packets() {
+  assert_eq!(intercept::<TextPacket, _>(&chat("hello"), |_| PacketAction::Pass), Ok(Interception::Unchanged));
+  assert_eq!(intercept::<TextPacket, _>(&chat("spam"), |_| PacketAction::Cancel), Ok(Interception::Cancelled));
+
+  // Fields the definition does not know of are not counted as changes
+  let mut bytes = chat("hello");
+  bytes.push(0x01);
+  assert_eq!(intercept::<TextPacket, _>(&bytes, |_| PacketAction::Pass), Ok(Interception::Unchanged));
+}
+
+#[test]
+fn keeps_trailing_bytes_and_the_header_of_modified_packets() {
+  // Fields added by a newer protocol follow the ones the definition knows of
+  let mut bytes = chat("hello");
+  bytes.extend_from_slice(&[0x01, 0x02]);
+
+  let result = intercept::<TextPacket, _>(&bytes, |packet| {
+    packet.message = "bye".to_string();
+    PacketAction::Pass
+  }).unwrap();
+  assert_eq!(result, Interception::Modified([chat("bye"), vec![0x01, 0x02]].concat()));
+
+  // The sub client ids in the header are written back as they were
+  let mut bytes = chat("hello");
+  bytes.splice(0..1, [0x89, 0x20]);
+
+  let Interception::Modified(modified) = intercept::<TextPacket, _>(&bytes, |packet| {
+    packet.message = "bye".to_string();
+    PacketAction::Pass
+  }).unwrap() else { panic!("expected a modified packet") };
+  assert_eq!(&modified[..2], &[0x89, 0x20]);
+  assert_eq!(&modified[2..], &chat("bye")[1..]);
+}
+
+#[test]
+fn rejects_other_packets() {
+  let result = intercept::<UpdateBlockPacket, _>(&chat("hello"), |_| unreachable!());
+
+  assert_eq!(result, Err(BinaryError::UnexpectedPacket { expected: 21, found: 9 }));
+}
+
+#[test]
+fn listener_events_use_the_emitter_ids() {
+  assert_eq!(ListenerEvent::Packet(TextPacket::ID).id(), 9);
+  assert_eq!(ListenerEvent::from(WorldEvent::PlayerChat).id(), 7);
+
+  assert_eq!(PacketDirection::Serverbound.bound(), "server");
+  assert_eq!(PacketDirection::Clientbound.bound(), "client");
+}
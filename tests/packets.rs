@@ -0,0 +1,248 @@
+use std::fmt::Debug;
+
+use serenityrs::protocol::binary::error::BinaryError;
+use serenityrs::protocol::packets::boss_event::{BossEvent, BossEventPacket};
+use serenityrs::protocol::packets::disconnect::DisconnectPacket;
+use serenityrs::protocol::packets::modal_form_request::ModalFormRequestPacket;
+use serenityrs::protocol::packets::modal_form_response::{FormCancelReason, ModalFormResponsePacket};
+use serenityrs::protocol::packets::move_player::{MoveMode, MovePlayerPacket};
+use serenityrs::protocol::packets::play_sound::PlaySoundPacket;
+use serenityrs::protocol::packets::set_score::{ScoreEntry, ScoreIdentity, ScoreRemoval, SetScorePacket};
+use serenityrs::protocol::packets::set_title::{SetTitlePacket, TitleType};
+use serenityrs::protocol::packets::spawn_particle_effect::SpawnParticleEffectPacket;
+use serenityrs::protocol::packets::text::{TextPacket, TextType};
+use serenityrs::protocol::packets::transfer::TransferPacket;
+use serenityrs::protocol::packets::update_block::UpdateBlockPacket;
+use serenityrs::protocol::packets::{NetworkPacket, Packet, PacketBody, PacketHeader};
+use serenityrs::protocol::types::block_position::BlockPosition;
+use serenityrs::protocol::types::vector3f::Vector3f;
+
+// (0.5, 64, -3.5) as three little-endian f32s
+const POSITION: [u8; 12] = [0x00, 0x00, 0x00, 0x3f, 0x00, 0x00, 0x80, 0x42, 0x00, 0x00, 0x60, 0xc0];
+
+fn round_trip<P>(bytes: &[u8], packet: P)
+where
+  P: NetworkPacket + Into<Packet> + Clone + Debug + PartialEq
+{
+  assert_eq!(P::from_bytes(bytes).unwrap(), packet);
+  assert_eq!(packet.to_bytes().unwrap(), bytes);
+
+  // The packet enum dispatches the same bytes by id
+  let dispatched = Packet::from_bytes(bytes).unwrap();
+  assert_eq!(dispatched.id(), P::ID);
+  assert_eq!(dispatched, packet.into());
+  assert_eq!(dispatched.to_bytes().unwrap(), bytes);
+}
+
+fn text_packet(text_type: TextType) -> TextPacket {
+  TextPacket {
+    text_type,
+    needs_translation: false,
+    source_name: String::new(),
+    message: String::new(),
+    parameters: Vec::new(),
+    xuid: String::new(),
+    platform_chat_id: String::new(),
+    filtered_message: String::new(),
+  }
+}
+
+#[test]
+fn text() {
+  let mut chat = text_packet(TextType::Chat);
+  chat.source_name = "Steve".to_string();
+  chat.message = "hi".to_string();
+  round_trip(&[0x09, 0x01, 0x00, 0x05, b'S', b't', b'e', b'v', b'e', 0x02, b'h', b'i', 0x00, 0x00, 0x00], chat);
+
+  let mut translation = text_packet(TextType::Translation);
+  translation.needs_translation = true;
+  translation.message = "k".to_string();
+  translation.parameters = vec!["a".to_string(), "b".to_string()];
+  round_trip(&[0x09, 0x02, 0x01, 0x01, b'k', 0x02, 0x01, b'a', 0x01, b'b', 0x00, 0x00, 0x00], translation);
+}
+
+#[test]
+fn set_title() {
+  let packet = SetTitlePacket {
+    title_type: TitleType::Title,
+    text: "Hi".to_string(),
+    fade_in: 10,
+    stay: 70,
+    fade_out: 20,
+    xuid: String::new(),
+    platform_online_id: String::new(),
+    filtered_text: String::new(),
+  };
+
+  round_trip(&[0x58, 0x04, 0x02, b'H', b'i', 0x14, 0x8c, 0x01, 0x28, 0x00, 0x00, 0x00], packet);
+}
+
+#[test]
+fn move_player() {
+  let mut packet = MovePlayerPacket {
+    runtime_id: 1,
+    position: Vector3f { x: 0.5, y: 64.0, z: -3.5 },
+    pitch: 0.0,
+    yaw: 90.0,
+    head_yaw: 90.0,
+    mode: MoveMode::Teleport,
+    on_ground: true,
+    riding_runtime_id: 0,
+    teleport_cause: 1,
+    teleport_source_type: 0,
+    tick: 200,
+  };
+
+  let bytes = [
+    &[0x13, 0x01][..],
+    &POSITION,
+    &[0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xb4, 0x42, 0x00, 0x00, 0xb4, 0x42],
+    &[0x02, 0x01, 0x00],
+    &[0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+    &[0xc8, 0x01],
+  ].concat();
+  round_trip(&bytes, packet.clone());
+
+  // Other modes leave out the teleport cause and source
+  packet.mode = MoveMode::Normal;
+  packet.teleport_cause = 0;
+  let bytes = [&bytes[..26], &[0x00, 0x01, 0x00], &[0xc8, 0x01]].concat();
+  round_trip(&bytes, packet);
+}
+
+#[test]
+fn update_block() {
+  let packet = UpdateBlockPacket { position: BlockPosition { x: 1, y: 64, z: -2 }, block_runtime_id: 300, flags: 3, layer: 0 };
+
+  round_trip(&[0x15, 0x02, 0x40, 0x03, 0xac, 0x02, 0x03, 0x00], packet);
+}
+
+#[test]
+fn play_sound() {
+  let packet = PlaySoundPacket::new("random.click", Vector3f { x: 1.0, y: 64.0, z: -0.5 }, 1.0, 1.0);
+  assert_eq!(packet.position, BlockPosition { x: 8, y: 512, z: -4 });
+  assert_eq!(packet.location(), Vector3f { x: 1.0, y: 64.0, z: -0.5 });
+
+  let bytes = [
+    &[0x56, 0x0c][..],
+    b"random.click",
+    &[0x10, 0x80, 0x04, 0x07],
+    &[0x00, 0x00, 0x80, 0x3f, 0x00, 0x00, 0x80, 0x3f],
+  ].concat();
+  round_trip(&bytes, packet);
+}
+
+#[test]
+fn spawn_particle_effect() {
+  let packet = SpawnParticleEffectPacket {
+    dimension: 0,
+    actor_unique_id: -1,
+    position: Vector3f { x: 0.5, y: 64.0, z: -3.5 },
+    particle_name: "minecraft:heart_particle".to_string(),
+    molang_variables: None,
+  };
+
+  let bytes = [&[0x76, 0x00, 0x01][..], &POSITION, &[0x18], b"minecraft:heart_particle", &[0x00]].concat();
+  round_trip(&bytes, packet);
+}
+
+#[test]
+fn transfer() {
+  let packet = TransferPacket { address: "play.example.com".to_string(), port: 19132, reload_world: false };
+
+  let bytes = [&[0x55, 0x10][..], b"play.example.com", &[0xbc, 0x4a, 0x00]].concat();
+  round_trip(&bytes, packet);
+}
+
+#[test]
+fn disconnect() {
+  let packet = DisconnectPacket { reason: 0, hide_disconnect_screen: false, message: "Kicked".to_string(), filtered_message: String::new() };
+  round_trip(&[0x05, 0x00, 0x00, 0x06, b'K', b'i', b'c', b'k', b'e', b'd', 0x00], packet);
+
+  // A hidden screen leaves out the messages
+  let packet = DisconnectPacket { reason: 0, hide_disconnect_screen: true, message: String::new(), filtered_message: String::new() };
+  round_trip(&[0x05, 0x00, 0x01], packet);
+}
+
+#[test]
+fn modal_forms() {
+  let request = ModalFormRequestPacket { form_id: 1, form_data: r#"{"type":"form"}"#.to_string() };
+  round_trip(&[&[0x64, 0x01, 0x0f][..], br#"{"type":"form"}"#].concat(), request);
+
+  let response = ModalFormResponsePacket { form_id: 1, response_data: Some("0".to_string()), cancel_reason: None };
+  round_trip(&[0x65, 0x01, 0x01, 0x01, b'0', 0x00], response);
+
+  let cancelled = ModalFormResponsePacket { form_id: 1, response_data: None, cancel_reason: Some(FormCancelReason::UserClosed) };
+  round_trip(&[0x65, 0x01, 0x00, 0x01, 0x00], cancelled);
+}
+
+#[test]
+fn set_score() {
+  let change = SetScorePacket::Change {
+    entries: vec![ScoreEntry {
+      scoreboard_id: 1,
+      objective_name: "kills".to_string(),
+      score: 10,
+      identity: ScoreIdentity::FakePlayer { name: "Steve".to_string() },
+    }],
+  };
+  let bytes = [&[0x6c, 0x00, 0x01, 0x02, 0x05][..], b"kills", &[0x0a, 0x00, 0x00, 0x00, 0x03, 0x05], b"Steve"].concat();
+  round_trip(&bytes, change);
+
+  let remove = SetScorePacket::Remove {
+    entries: vec![ScoreRemoval { scoreboard_id: 1, objective_name: "kills".to_string(), score: 10 }],
+  };
+  let bytes = [&[0x6c, 0x01, 0x01, 0x02, 0x05][..], b"kills", &[0x0a, 0x00, 0x00, 0x00]].concat();
+  round_trip(&bytes, remove);
+}
+
+#[test]
+fn boss_event() {
+  let show = BossEventPacket {
+    boss_unique_id: 5,
+    event: BossEvent::Show {
+      title: "Wither".to_string(),
+      filtered_title: String::new(),
+      health_percent: 1.0,
+      screen_darkening: 0,
+      color: 5,
+      overlay: 0,
+    },
+  };
+  let bytes = [&[0x4a, 0x0a, 0x00, 0x06][..], b"Wither", &[0x00, 0x00, 0x00, 0x80, 0x3f, 0x00, 0x00, 0x05, 0x00]].concat();
+  round_trip(&bytes, show);
+
+  round_trip(&[0x4a, 0x0a, 0x02], BossEventPacket { boss_unique_id: 5, event: BossEvent::Hide });
+}
+
+#[test]
+fn unknown_packets_keep_their_payload() {
+  let packet = Packet::from_bytes(&[0x01, 0xaa, 0xbb]).unwrap();
+
+  assert_eq!(packet, Packet::new(PacketBody::Unknown { id: 1, payload: vec![0xaa, 0xbb] }));
+  assert_eq!(packet.to_bytes().unwrap(), [0x01, 0xaa, 0xbb]);
+}
+
+#[test]
+fn headers_carry_sub_client_ids() {
+  // The id of a disconnect packet, sent by the main client to the first sub client
+  let bytes = [0x85, 0x20, 0x00, 0x01];
+  let packet = Packet::from_bytes(&bytes).unwrap();
+
+  assert_eq!(packet.id(), DisconnectPacket::ID);
+  assert_eq!(packet.header(), PacketHeader { id: 5, sender_sub_client: 0, target_sub_client: 1 });
+  assert_eq!(packet.to_bytes().unwrap(), bytes);
+
+  // Sent by the third sub client to the main one
+  let mut packet = Packet::from(DisconnectPacket::from_bytes(&bytes).unwrap());
+  packet.sender_sub_client = 3;
+  assert_eq!(packet.to_bytes().unwrap(), [0x85, 0x18, 0x00, 0x01]);
+  assert_eq!(Packet::from_bytes(&packet.to_bytes().unwrap()).unwrap(), packet);
+}
+
+#[test]
+fn rejects_invalid_packets() {
+  assert_eq!(TransferPacket::from_bytes(&[0x05, 0x00, 0x01]), Err(BinaryError::UnexpectedPacket { expected: 85, found: 5 }));
+  assert_eq!(Packet::from_bytes(&[0x09, 0x0c]), Err(BinaryError::UnknownVariant { name: "TextType", value: 12 }));
+  assert!(matches!(Packet::from_bytes(&[0x55, 0x10, b'a']), Err(BinaryError::UnexpectedEof { .. })));
+}
@@ -0,0 +1,24 @@
+use serenityrs::error::error::SerenityError;
+use serenityrs::utils::panic::catch_panic;
+
+#[test]
+fn caught_panic_becomes_an_error() {
+  let result: Result<(), SerenityError> = catch_panic("Test", || panic!("listener failed: {}", 42));
+
+  match result {
+    Err(SerenityError::Panic { wrapper, message, backtrace }) => {
+      assert_eq!(wrapper, "Test");
+      assert_eq!(message, "listener failed: 42");
+      assert!(!backtrace.is_empty());
+    },
+    other => panic!("expected a panic error, got {:?}", other)
+  }
+}
+
+#[test]
+fn results_pass_through() {
+  assert_eq!(catch_panic("Test", || Ok(5)), Ok(5));
+
+  let error = SerenityError::NapiFailure { wrapper: "Test", message: "failed".to_string() };
+  assert_eq!(catch_panic::<(), _>("Test", || Err(error.clone())), Err(error));
+}
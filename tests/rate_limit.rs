@@ -0,0 +1,73 @@
+use std::time::{Duration, Instant};
+
+use serenityrs::logger::config::{LogLevel, RateLimit};
+use serenityrs::logger::rate_limit::{RateLimiter, SuppressedSummary};
+
+const LIMIT: RateLimit = RateLimit { window: Duration::from_secs(5), max_repeats: 2 };
+
+fn seconds(start: Instant, seconds: u64) -> Instant {
+  start + Duration::from_secs(seconds)
+}
+
+#[test]
+fn suppresses_repeats_past_the_limit_within_a_window() {
+  let mut limiter = RateLimiter::default();
+  let start = Instant::now();
+
+  let allowed: Vec<bool> = (0..5).map(|second| limiter.allow(&LIMIT, LogLevel::Warn, "flood", seconds(start, second))).collect();
+  assert_eq!(allowed, vec![true, true, false, false, false]);
+
+  // Other messages, and the same message at another level, have their own windows
+  assert!(limiter.allow(&LIMIT, LogLevel::Warn, "other", seconds(start, 4)));
+  assert!(limiter.allow(&LIMIT, LogLevel::Error, "flood", seconds(start, 4)));
+}
+
+#[test]
+fn summarizes_suppressed_repeats_once_the_window_passed() {
+  let mut limiter = RateLimiter::default();
+  let start = Instant::now();
+
+  for second in 0..4 {
+    limiter.allow(&LIMIT, LogLevel::Info, "flood", seconds(start, second));
+  }
+  assert_eq!(limiter.next_expiry(&LIMIT), Some(seconds(start, 5)));
+
+  // Nothing is summarized while the window is still open
+  assert!(limiter.expire(&LIMIT, seconds(start, 4)).is_empty());
+
+  let summaries = limiter.expire(&LIMIT, seconds(start, 5));
+  assert_eq!(summaries, vec![SuppressedSummary { level: LogLevel::Info, message: "flood".to_string(), suppressed: 2 }]);
+
+  // Every suppressed repeat is only summarized once
+  assert!(limiter.expire(&LIMIT, seconds(start, 6)).is_empty());
+  assert_eq!(limiter.next_expiry(&LIMIT), None);
+}
+
+#[test]
+fn starts_a_new_window_after_expiry() {
+  let mut limiter = RateLimiter::default();
+  let start = Instant::now();
+
+  for second in 0..3 {
+    limiter.allow(&LIMIT, LogLevel::Info, "flood", seconds(start, second));
+  }
+
+  // The window passed, so the message is written again and counted from scratch
+  assert!(limiter.allow(&LIMIT, LogLevel::Info, "flood", seconds(start, 5)));
+  assert!(limiter.allow(&LIMIT, LogLevel::Info, "flood", seconds(start, 6)));
+  assert!(!limiter.allow(&LIMIT, LogLevel::Info, "flood", seconds(start, 7)));
+  assert_eq!(limiter.next_expiry(&LIMIT), Some(seconds(start, 10)));
+}
+
+#[test]
+fn never_suppresses_messages_without_repeats() {
+  let mut limiter = RateLimiter::default();
+  let start = Instant::now();
+
+  for index in 0..300 {
+    assert!(limiter.allow(&LIMIT, LogLevel::Debug, &index.to_string(), start));
+  }
+
+  assert!(limiter.expire(&LIMIT, seconds(start, 10)).is_empty());
+  assert_eq!(limiter.next_expiry(&LIMIT), None);
+}
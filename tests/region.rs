@@ -0,0 +1,96 @@
+use std::collections::HashSet;
+
+use serenityrs::protocol::types::aabb::Aabb;
+use serenityrs::protocol::types::block_face::BlockFace;
+use serenityrs::protocol::types::block_position::BlockPosition;
+use serenityrs::protocol::types::chunk_position::ChunkPosition;
+use serenityrs::protocol::types::cuboid::Cuboid;
+use serenityrs::protocol::types::vector3f::Vector3f;
+
+#[test]
+fn aabb_queries() {
+  let aabb = Aabb::new(Vector3f::new(2.0, 2.0, 2.0), Vector3f::new(0.0, 0.0, 0.0));
+  assert_eq!(aabb.min, Vector3f::ZERO);
+  assert_eq!(aabb.volume(), 8.0);
+  assert_eq!(aabb.center(), Vector3f::ONE);
+  assert!(aabb.contains(Vector3f::new(2.0, 1.0, 0.0)));
+  assert!(!aabb.contains(Vector3f::new(2.1, 1.0, 0.0)));
+
+  let touching = Aabb::new(Vector3f::new(2.0, 0.0, 0.0), Vector3f::new(3.0, 1.0, 1.0));
+  assert!(!aabb.intersects(&touching));
+  assert!(aabb.expand(0.5).intersects(&touching));
+  assert_eq!(aabb.union(&touching), Aabb::new(Vector3f::ZERO, Vector3f::new(3.0, 2.0, 2.0)));
+  assert_eq!(aabb.intersection(&Aabb::from_center(Vector3f::ZERO, Vector3f::new(2.0, 2.0, 2.0))), Some(Aabb::new(Vector3f::ZERO, Vector3f::ONE)));
+}
+
+#[test]
+fn aabb_ray_intersection() {
+  let aabb = Aabb::new(Vector3f::new(1.0, 1.0, 1.0), Vector3f::new(2.0, 2.0, 2.0));
+
+  assert_eq!(aabb.intersect_ray(Vector3f::new(0.0, 1.5, 1.5), Vector3f::EAST), Some(1.0));
+  assert_eq!(aabb.intersect_ray(Vector3f::new(0.0, 1.5, 1.5), Vector3f::EAST * 2.0), Some(0.5));
+  assert_eq!(aabb.intersect_ray(Vector3f::new(1.5, 1.5, 1.5), Vector3f::UP), Some(0.0));
+  assert_eq!(aabb.intersect_ray(Vector3f::new(0.0, 1.5, 1.5), Vector3f::WEST), None);
+  assert_eq!(aabb.intersect_ray(Vector3f::new(0.0, 3.0, 1.5), Vector3f::EAST), None);
+
+  let hit = aabb.intersect_ray(Vector3f::ZERO, Vector3f::ONE).unwrap();
+  assert!((hit - 1.0).abs() < 1e-12);
+}
+
+#[test]
+fn cuboid_iterates_with_y_innermost() {
+  let cuboid = Cuboid::new(BlockPosition::new(1, 1, 1), BlockPosition::new(0, 0, 0));
+  let blocks: Vec<_> = cuboid.iter().collect();
+
+  assert_eq!(cuboid.volume(), 8);
+  assert_eq!(cuboid.iter().size_hint().1, Some(8));
+  assert_eq!(blocks, vec![
+    BlockPosition::new(0, 0, 0), BlockPosition::new(0, 1, 0), BlockPosition::new(0, 0, 1), BlockPosition::new(0, 1, 1),
+    BlockPosition::new(1, 0, 0), BlockPosition::new(1, 1, 0), BlockPosition::new(1, 0, 1), BlockPosition::new(1, 1, 1),
+  ]);
+}
+
+#[test]
+fn cuboid_faces_edges_and_shell() {
+  let cuboid = Cuboid::new(BlockPosition::new(0, 0, 0), BlockPosition::new(3, 4, 5));
+  let shell: HashSet<_> = cuboid.shell().collect();
+  let edges: HashSet<_> = cuboid.edges().collect();
+
+  // The shell is everything but the inner 2x3x4 blocks
+  assert_eq!(cuboid.shell().count(), 120 - 24);
+  assert_eq!(shell.len(), 120 - 24);
+  assert_eq!(edges.len(), 4 * (4 + 5 + 6) - 16);
+  assert!(edges.is_subset(&shell));
+
+  for face in BlockFace::ALL {
+    let layer = cuboid.face(face);
+    assert!(layer.iter().all(|block| shell.contains(&block)));
+    assert!(!cuboid.contains(layer.min.offset(face)));
+  }
+
+  assert_eq!(Cuboid::new(BlockPosition::new(0, 0, 0), BlockPosition::new(0, 0, 0)).shell().count(), 1);
+  assert_eq!(Cuboid::new(BlockPosition::new(0, 0, 0), BlockPosition::new(2, 1, 2)).shell().count(), 18);
+}
+
+#[test]
+fn cuboid_splits_into_chunks() {
+  let cuboid = Cuboid::new(BlockPosition::new(-2, 0, 10), BlockPosition::new(17, 3, 20));
+  let pieces = cuboid.split_chunks();
+
+  assert_eq!(pieces.len(), 6);
+  assert_eq!(pieces.iter().map(|(_, piece)| piece.volume()).sum::<u64>(), cuboid.volume());
+  for (chunk, piece) in pieces {
+    assert!(piece.iter().all(|block| chunk.contains(block)));
+  }
+  assert_eq!(cuboid.split_chunks()[0], (ChunkPosition { x: -1, z: 0 }, Cuboid::new(BlockPosition::new(-2, 0, 10), BlockPosition::new(-1, 3, 15))));
+}
+
+#[test]
+fn cuboid_and_aabb_convert() {
+  let cuboid = Cuboid::new(BlockPosition::new(-1, 0, 2), BlockPosition::new(1, 0, 3));
+  let aabb = Aabb::from(cuboid);
+
+  assert_eq!(aabb, Aabb::new(Vector3f::new(-1.0, 0.0, 2.0), Vector3f::new(2.0, 1.0, 4.0)));
+  assert_eq!(Cuboid::from(aabb), cuboid);
+  assert_eq!(Cuboid::from(Aabb::new(Vector3f::new(0.5, 0.0, 0.0), Vector3f::new(0.5, 0.2, 1.5))), Cuboid::new(BlockPosition::new(0, 0, 0), BlockPosition::new(0, 0, 1)));
+}
@@ -0,0 +1,89 @@
+use serenityrs::backend::backend::{PlayerBackend, WorldBackend};
+use serenityrs::backend::events::{PlayerChatEvent, PlayerExecuteCommandEvent, PlayerJoinEvent};
+use serenityrs::backend::mock::*;
+use serenityrs::error::error::SerenityError;
+use serenityrs::protocol::types::vector3f::Vector3f;
+use serenityrs::replay::error::ReplayError;
+use serenityrs::replay::replayer::*;
+
+const RECORDING: &str = r#"
+{"signal":"PlayerJoin","world":{"identifier":"default","current_tick":10},"player":{"username":"Steve","xuid":"1","uuid":"u-1","position":[3.0,70.0,-2.0],"dimension":"minecraft:overworld","gamemode":0,"op":false}}
+{"signal":"WorldTick","world":{"identifier":"default","current_tick":11}}
+{"signal":"PlayerChat","world":{"identifier":"default","current_tick":12},"player":{"username":"Steve","xuid":"1","uuid":"u-1","position":[3.0,70.0,-2.0],"dimension":"minecraft:overworld","gamemode":0,"op":false},"message":"hello"}
+{"signal":"PlayerExecuteCommand","world":{"identifier":"default","current_tick":13},"player":{"username":"Steve","xuid":"1","uuid":"u-1","position":[3.0,70.0,-2.0],"dimension":"minecraft:overworld","gamemode":0,"op":true},"command":"/creative"}
+{"signal":"PlayerLeave","world":{"identifier":"default","current_tick":14},"player":{"username":"Steve","xuid":"1","uuid":"u-1","position":[3.0,70.0,-2.0],"dimension":"minecraft:overworld","gamemode":1,"op":true},"reason":0,"message":"bye"}
+"#;
+
+#[derive(Default)]
+struct Handlers {
+  spawn_y: f64,
+}
+
+fn on_join<E: PlayerJoinEvent>(event: &E, spawn_y: f64) -> Result<(), SerenityError> {
+  event.player().teleport(Vector3f { x: 0.0, y: spawn_y, z: 0.0 })
+}
+
+fn on_chat<E: PlayerChatEvent>(event: &mut E) -> Result<(), SerenityError> {
+  event.world().send_message(&format!("<{}> {}", event.player().username(), event.message()))
+}
+
+fn on_command<E: PlayerExecuteCommandEvent>(event: &mut E) -> Result<(), SerenityError> {
+  if event.command() == "/creative" && event.player().is_op()? {
+    event.player().set_gamemode(1)?;
+  }
+
+  Ok(())
+}
+
+impl ReplayHandlers for Handlers {
+  fn on_player_join(&mut self, signal: &MockPlayerJoinSignal) -> Result<(), SerenityError> {
+    on_join(signal, self.spawn_y)
+  }
+
+  fn on_player_chat(&mut self, signal: &mut MockPlayerChatSignal) -> Result<(), SerenityError> {
+    on_chat(signal)
+  }
+
+  fn on_player_execute_command(&mut self, signal: &mut MockPlayerExecuteCommandSignal) -> Result<(), SerenityError> {
+    on_command(signal)
+  }
+}
+
+#[test]
+fn replays_the_recording_through_the_handlers() {
+  let mut replayer = Replayer::new();
+  let calls = replayer.replay(RECORDING.as_bytes(), &mut Handlers { spawn_y: 64.0 }).unwrap();
+
+  assert_eq!(calls, vec![
+    MockCall::new("Steve", "teleport", &["0", "64", "0"]),
+    MockCall::new("default", "send_message", &["<Steve> hello"]),
+    MockCall::new("Steve", "set_gamemode", &["1"]),
+  ]);
+}
+
+#[test]
+fn diffs_the_calls_against_the_baseline() {
+  let baseline = Replayer::new().replay(RECORDING.as_bytes(), &mut Handlers { spawn_y: 64.0 }).unwrap();
+
+  // Round trip the baseline through its file format
+  let mut file = Vec::new();
+  write_baseline(&mut file, &baseline).unwrap();
+  let baseline = read_baseline(file.as_slice()).unwrap();
+
+  let calls = Replayer::new().replay(RECORDING.as_bytes(), &mut Handlers { spawn_y: 80.0 }).unwrap();
+  assert_eq!(diff_calls(&baseline, &calls), vec![CallDiff::Changed {
+    index: 0,
+    expected: MockCall::new("Steve", "teleport", &["0", "64", "0"]),
+    actual: MockCall::new("Steve", "teleport", &["0", "80", "0"]),
+  }]);
+
+  assert_eq!(diff_calls(&baseline, &calls[1..]).len(), 3);
+  assert!(diff_calls(&baseline, &baseline).is_empty());
+}
+
+#[test]
+fn reports_the_line_that_failed_to_parse() {
+  let error = Replayer::new().replay("\n{\"signal\":\"Unknown\"}\n".as_bytes(), &mut Handlers::default()).unwrap_err();
+
+  assert!(matches!(error, ReplayError::Parse { line: 2, .. }));
+}
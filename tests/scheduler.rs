@@ -0,0 +1,156 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use serenityrs::backend::backend::WorldBackend;
+use serenityrs::backend::mock::{MockHost, MockWorld};
+use serenityrs::error::error::SerenityError;
+use serenityrs::serenity::scheduler::Scheduler;
+use serenityrs::world::world::world_ref::WorldRef;
+
+type Runs = Rc<RefCell<Vec<(String, u64)>>>;
+
+fn record(runs: &Runs, label: &str) -> impl FnMut(&MockWorld) -> Result<(), SerenityError> {
+  let runs = runs.clone();
+  let label = label.to_string();
+
+  move |world| {
+    runs.borrow_mut().push((label.clone(), world.current_tick()));
+    Ok(())
+  }
+}
+
+fn tick(scheduler: &Scheduler<MockWorld>, world: &MockWorld) {
+  world.advance_tick();
+  scheduler.tick(world).unwrap();
+}
+
+fn labels(runs: &Runs) -> Vec<String> {
+  runs.borrow().iter().map(|(label, _)| label.clone()).collect()
+}
+
+#[test]
+fn runs_delayed_and_repeating_tasks() {
+  let host = MockHost::new();
+  let world = host.world("default");
+  let scheduler = Scheduler::detached();
+  let runs = Runs::default();
+
+  scheduler.run_later(3, record(&runs, "later"));
+  scheduler.run_repeating(2, record(&runs, "repeating"));
+  // A delay of zero still waits for the next tick
+  scheduler.run_later(0, record(&runs, "next"));
+
+  for _ in 0..6 {
+    tick(&scheduler, &world);
+  }
+
+  assert_eq!(*runs.borrow(), vec![
+    ("next".to_string(), 1),
+    ("repeating".to_string(), 2),
+    ("later".to_string(), 3),
+    ("repeating".to_string(), 4),
+    ("repeating".to_string(), 6),
+  ]);
+  assert_eq!(scheduler.task_count(), 1);
+}
+
+#[test]
+fn defers_tasks_past_the_budget() {
+  let host = MockHost::new();
+  let world = host.world("default");
+  let scheduler = Scheduler::detached();
+  let runs = Runs::default();
+
+  // Without any budget, one task runs per tick and the others keep their order
+  scheduler.set_tick_budget(Duration::ZERO);
+  for label in ["a", "b", "c"] {
+    scheduler.run_later(1, record(&runs, label));
+  }
+
+  tick(&scheduler, &world);
+  assert_eq!(labels(&runs), vec!["a"]);
+
+  // Overdue tasks run before tasks that only just became due
+  scheduler.run_later(1, record(&runs, "d"));
+  tick(&scheduler, &world);
+  tick(&scheduler, &world);
+  tick(&scheduler, &world);
+  assert_eq!(*runs.borrow(), vec![
+    ("a".to_string(), 1),
+    ("b".to_string(), 2),
+    ("c".to_string(), 3),
+    ("d".to_string(), 4),
+  ]);
+}
+
+#[test]
+fn cancelled_tasks_never_run() {
+  let host = MockHost::new();
+  let world = host.world("default");
+  let scheduler = Scheduler::detached();
+  let runs = Runs::default();
+
+  let later = scheduler.run_later(2, record(&runs, "later"));
+  let repeating = scheduler.run_repeating(1, record(&runs, "repeating"));
+  assert_eq!(scheduler.task_count(), 2);
+
+  later.cancel();
+  assert_eq!(scheduler.task_count(), 1);
+
+  tick(&scheduler, &world);
+  repeating.cancel();
+  tick(&scheduler, &world);
+  tick(&scheduler, &world);
+
+  assert_eq!(labels(&runs), vec!["repeating"]);
+  assert_eq!(scheduler.task_count(), 0);
+}
+
+#[test]
+fn scopes_tasks_to_their_world() {
+  let host = MockHost::new();
+  let overworld = host.world("overworld");
+  let nether = host.world("nether");
+  let scheduler = Scheduler::detached();
+  let runs = Runs::default();
+
+  scheduler.run_later_in(&WorldRef::new("nether"), 2, record(&runs, "nether"));
+  scheduler.run_later(2, record(&runs, "global"));
+
+  // Global tasks follow the first world that ticks, scoped tasks only their own world
+  tick(&scheduler, &overworld);
+  tick(&scheduler, &overworld);
+  assert_eq!(labels(&runs), vec!["global"]);
+
+  tick(&scheduler, &nether);
+  assert_eq!(labels(&runs), vec!["global"]);
+  tick(&scheduler, &nether);
+  assert_eq!(*runs.borrow(), vec![("global".to_string(), 2), ("nether".to_string(), 2)]);
+}
+
+#[test]
+fn keeps_the_tasks_when_one_panics_or_fails() {
+  let host = MockHost::new();
+  let world = host.world("default");
+  let scheduler = Scheduler::detached();
+  let runs = Runs::default();
+
+  // Capturing the backtrace of a panic can take longer than the default budget
+  scheduler.set_tick_budget(Duration::from_secs(60));
+  scheduler.run_repeating(1, |_: &MockWorld| -> Result<(), SerenityError> { panic!("boom") });
+  scheduler.run_later(1, |_: &MockWorld| Err(SerenityError::MissingProperty { wrapper: "Test", property: "value".to_string() }));
+  scheduler.run_repeating(1, record(&runs, "after"));
+
+  tick(&scheduler, &world);
+  tick(&scheduler, &world);
+
+  assert_eq!(labels(&runs), vec!["after", "after"]);
+  assert_eq!(scheduler.task_count(), 2);
+
+  let errors = host.calls_to("error");
+  assert_eq!(errors.len(), 3);
+  assert!(errors[0].args[0].starts_with("Scheduled task panicked: boom"));
+  assert!(errors[1].args[0].starts_with("Scheduled task failed:"));
+  assert!(errors[2].args[0].starts_with("Scheduled task panicked: boom"));
+}
@@ -0,0 +1,125 @@
+use std::collections::HashSet;
+
+use serenityrs::protocol::types::block_face::BlockFace;
+use serenityrs::protocol::types::block_position::BlockPosition;
+use serenityrs::protocol::types::rotation::Rotation;
+use serenityrs::protocol::types::vector3f::Vector3f;
+
+#[test]
+fn vector_operators() {
+  let a = Vector3f::new(1.0, 2.0, 3.0);
+  let b = Vector3f::new(4.0, -5.0, 6.0);
+
+  assert_eq!(a + b, Vector3f::new(5.0, -3.0, 9.0));
+  assert_eq!(a - b, Vector3f::new(-3.0, 7.0, -3.0));
+  assert_eq!(a * 2.0, Vector3f::new(2.0, 4.0, 6.0));
+  assert_eq!(b / 2.0, Vector3f::new(2.0, -2.5, 3.0));
+  assert_eq!(-a, Vector3f::new(-1.0, -2.0, -3.0));
+
+  let mut c = a;
+  c += b;
+  c -= a;
+  assert_eq!(c, b);
+}
+
+#[test]
+fn vector_products_and_lengths() {
+  let a = Vector3f::new(1.0, 2.0, 3.0);
+  let b = Vector3f::new(4.0, -5.0, 6.0);
+
+  assert_eq!(a.dot(b), 12.0);
+  assert_eq!(a.cross(b), Vector3f::new(27.0, 6.0, -13.0));
+  assert_eq!(Vector3f::EAST.cross(Vector3f::UP), Vector3f::SOUTH);
+  assert_eq!(Vector3f::new(3.0, 4.0, 0.0).length(), 5.0);
+  assert_eq!(Vector3f::ZERO.distance(Vector3f::new(0.0, 3.0, 4.0)), 5.0);
+  assert_eq!(Vector3f::ZERO.distance_squared(Vector3f::new(0.0, 3.0, 4.0)), 25.0);
+}
+
+#[test]
+fn vector_normalize_and_lerp() {
+  assert_eq!(Vector3f::new(0.0, 0.0, 8.0).normalize(), Vector3f::SOUTH);
+  assert!((Vector3f::new(1.0, 2.0, 3.0).normalize().length() - 1.0).abs() < 1e-12);
+  assert_eq!(Vector3f::ZERO.normalize(), Vector3f::ZERO);
+
+  let a = Vector3f::new(0.0, 10.0, -4.0);
+  let b = Vector3f::new(10.0, 20.0, 4.0);
+  assert_eq!(a.lerp(b, 0.0), a);
+  assert_eq!(a.lerp(b, 1.0), b);
+  assert_eq!(a.lerp(b, 0.5), Vector3f::new(5.0, 15.0, 0.0));
+}
+
+#[test]
+fn vector_to_block_position() {
+  assert_eq!(Vector3f::new(1.7, -0.2, -3.5).floor(), BlockPosition::new(1, -1, -4));
+  assert_eq!(Vector3f::new(1.7, -0.2, -3.5).round(), BlockPosition::new(2, 0, -4));
+  assert_eq!(Vector3f::from(BlockPosition::new(1, -2, 3)), Vector3f::new(1.0, -2.0, 3.0));
+  assert_eq!(BlockPosition::new(1, -2, 3).center(), Vector3f::new(1.5, -1.5, 3.5));
+}
+
+#[test]
+fn block_position_operators() {
+  let a = BlockPosition::new(1, 2, 3);
+  let b = BlockPosition::new(-4, 5, 6);
+
+  assert_eq!(a + b, BlockPosition::new(-3, 7, 9));
+  assert_eq!(a - b, BlockPosition::new(5, -3, -3));
+  assert_eq!(a * 3, BlockPosition::new(3, 6, 9));
+  assert_eq!(-a, BlockPosition::new(-1, -2, -3));
+  assert_eq!(a.manhattan_distance(b), 11);
+}
+
+#[test]
+fn block_position_floor_division() {
+  assert_eq!(BlockPosition::new(-1, 15, 16).div_floor(16), BlockPosition::new(-1, 0, 1));
+  assert_eq!(BlockPosition::new(-16, -17, 0).div_floor(16), BlockPosition::new(-1, -2, 0));
+
+  // Negative divisors round towards negative infinity as well
+  assert_eq!(BlockPosition::new(7, -7, 8).div_floor(-2), BlockPosition::new(-4, 3, -4));
+  assert_eq!(BlockPosition::new(-1, 1, 0).div_floor(-16), BlockPosition::new(0, -1, 0));
+}
+
+#[test]
+fn block_position_is_hashable_and_ordered() {
+  let set: HashSet<BlockPosition> = [BlockPosition::new(1, 2, 3), BlockPosition::new(1, 2, 3), BlockPosition::ZERO].into_iter().collect();
+  assert_eq!(set.len(), 2);
+
+  let mut positions = vec![BlockPosition::new(1, 0, 0), BlockPosition::new(0, 5, 0), BlockPosition::new(0, 0, 9)];
+  positions.sort();
+  assert_eq!(positions, vec![BlockPosition::new(0, 0, 9), BlockPosition::new(0, 5, 0), BlockPosition::new(1, 0, 0)]);
+}
+
+#[test]
+fn block_faces() {
+  let origin = BlockPosition::new(10, 64, -10);
+
+  assert_eq!(origin.offset(BlockFace::Up), BlockPosition::new(10, 65, -10));
+  assert_eq!(origin.offset(BlockFace::North), BlockPosition::new(10, 64, -11));
+  assert_eq!(origin.offset(BlockFace::East), BlockPosition::new(11, 64, -10));
+
+  let neighbors = origin.neighbors();
+  assert_eq!(neighbors.len(), 6);
+  for (face, neighbor) in BlockFace::ALL.into_iter().zip(neighbors) {
+    assert_eq!(neighbor.manhattan_distance(origin), 1);
+    assert_eq!(neighbor.offset(face.opposite()), origin);
+  }
+
+  for face in BlockFace::ALL {
+    assert_eq!(BlockFace::from_id(face.id()), Some(face));
+    assert_eq!(face.direction() + face.opposite().direction(), BlockPosition::ZERO);
+  }
+  assert_eq!(BlockFace::from_id(6), None);
+}
+
+#[test]
+fn rotation_directions() {
+  let close = |a: Vector3f, b: Vector3f| a.distance(b) < 1e-9;
+
+  assert!(close(Rotation::new(0.0, 0.0, 0.0).direction(), Vector3f::SOUTH));
+  assert!(close(Rotation::new(0.0, 90.0, 90.0).direction(), Vector3f::WEST));
+  assert!(close(Rotation::new(0.0, 180.0, 0.0).direction(), Vector3f::NORTH));
+  assert!(close(Rotation::new(90.0, 45.0, 0.0).direction(), Vector3f::DOWN));
+  assert!(close(Rotation::new(-90.0, 0.0, 0.0).direction(), Vector3f::UP));
+
+  let direction = Vector3f::new(1.0, -1.0, 2.0).normalize();
+  assert!(close(Rotation::from_direction(direction).direction(), direction));
+}
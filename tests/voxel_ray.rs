@@ -0,0 +1,46 @@
+use serenityrs::protocol::types::block_face::BlockFace;
+use serenityrs::protocol::types::block_position::BlockPosition;
+use serenityrs::protocol::types::vector3f::Vector3f;
+use serenityrs::protocol::types::voxel_ray::VoxelRay;
+
+#[test]
+fn walks_along_an_axis() {
+  let steps: Vec<_> = VoxelRay::new(Vector3f::new(0.5, 0.5, 0.5), Vector3f::EAST * 3.0, 2.4).collect();
+
+  assert_eq!(steps.iter().map(|step| step.position).collect::<Vec<_>>(), vec![BlockPosition::new(0, 0, 0), BlockPosition::new(1, 0, 0), BlockPosition::new(2, 0, 0)]);
+  assert_eq!(steps[0].face, None);
+  assert_eq!(steps[1].face, Some(BlockFace::West));
+  assert_eq!(steps[1].point, Vector3f::new(1.0, 0.5, 0.5));
+  assert_eq!(steps[2].distance, 1.5);
+}
+
+#[test]
+fn enters_through_the_faces_facing_the_origin() {
+  let down = VoxelRay::new(Vector3f::new(0.5, 10.2, 0.5), Vector3f::DOWN, 5.0).nth(1).unwrap();
+  assert_eq!(down.position, BlockPosition::new(0, 9, 0));
+  assert_eq!(down.face, Some(BlockFace::Up));
+  assert!((down.distance - 0.2).abs() < 1e-9);
+
+  let north = VoxelRay::new(Vector3f::new(-0.5, 0.5, -0.5), Vector3f::NORTH, 5.0).nth(1).unwrap();
+  assert_eq!(north.position, BlockPosition::new(-1, 0, -2));
+  assert_eq!(north.face, Some(BlockFace::South));
+}
+
+#[test]
+fn diagonal_rays_cross_every_block_once() {
+  let steps: Vec<_> = VoxelRay::new(Vector3f::new(0.1, 0.2, 0.3), Vector3f::new(1.0, 0.7, 0.4), 20.0).collect();
+
+  for pair in steps.windows(2) {
+    // Every step enters a neighbouring block through the face facing the previous one
+    assert_eq!(pair[0].position.manhattan_distance(pair[1].position), 1);
+    assert!(pair[1].distance >= pair[0].distance);
+    assert_eq!(pair[1].position.offset(pair[1].face.unwrap()), pair[0].position);
+  }
+  assert!(steps.last().unwrap().distance <= 20.0);
+}
+
+#[test]
+fn rays_without_direction_or_distance() {
+  assert_eq!(VoxelRay::new(Vector3f::ZERO, Vector3f::ZERO, 5.0).count(), 0);
+  assert_eq!(VoxelRay::new(Vector3f::ZERO, Vector3f::UP, 0.0).count(), 1);
+}